@@ -1,5 +1,9 @@
 //! Traits defining the behavior of expressions.
 
+use std::{cell::RefCell, collections::HashMap, marker::PhantomData};
+
+use ff::Field;
+
 use crate::{
     info_traits::{ChallengeInfo, CreateQuery, QueryInfo, SelectorInfo},
     query::{Advice, Fixed, Instance},
@@ -28,6 +32,38 @@ pub trait ExpressionInfo: ExpressionTypes {
     /// If the expression is a query to a fixed cells returns a reference to the query. Otherwise
     /// should return `None`.
     fn as_fixed_query(&self) -> Option<&Self::FixedQuery>;
+
+    /// If the expression is a query to an advice cell returns a reference to the query.
+    /// Otherwise should return `None`.
+    fn as_advice_query(&self) -> Option<&Self::AdviceQuery>;
+
+    /// If the expression is a query to an instance cell returns a reference to the query.
+    /// Otherwise should return `None`.
+    fn as_instance_query(&self) -> Option<&Self::InstanceQuery>;
+
+    /// If the expression is a challenge returns a reference to it. Otherwise should return
+    /// `None`.
+    fn as_challenge(&self) -> Option<&Self::Challenge>;
+}
+
+/// Extension of [`ExpressionInfo`] for decomposing expressions built from operations and
+/// constants.
+pub trait ExpressionOperandsInfo<F>: ExpressionInfo {
+    /// If the expression is a sum returns references to both operands. Otherwise should return
+    /// `None`.
+    fn as_sum(&self) -> Option<(&Self, &Self)>;
+
+    /// If the expression is a product returns references to both operands. Otherwise should
+    /// return `None`.
+    fn as_product(&self) -> Option<(&Self, &Self)>;
+
+    /// If the expression is a constant returns a reference to its value. Otherwise should return
+    /// `None`.
+    fn as_constant(&self) -> Option<&F>;
+
+    /// If the expression is scaled by a constant returns references to the inner expression and
+    /// the factor. Otherwise should return `None`.
+    fn as_scaled(&self) -> Option<(&Self, &F)>;
 }
 
 /// Factory trait for creating expressions.
@@ -61,12 +97,107 @@ pub trait ExprBuilder<F>: ExpressionTypes {
 
     /// Create the Expression::Scaled case.
     fn scaled(lhs: Self, rhs: F) -> Self;
+
+    /// Creates a polynomial on the given variable from a list of `(coefficient, degree)` pairs.
+    ///
+    /// The expression is built using Horner's method. Coefficients with the same degree are
+    /// added together and an empty list of coefficients yields the zero polynomial.
+    fn from_polynomial(coefficients: &[(F, u32)], variable: Self) -> Self
+    where
+        F: Field,
+        Self: Clone,
+    {
+        let mut terms = coefficients.to_vec();
+        terms.sort_by(|(_, lhs), (_, rhs)| rhs.cmp(lhs));
+        let mut terms = terms.into_iter();
+        let Some((coeff, mut degree)) = terms.next() else {
+            return Self::constant(F::ZERO);
+        };
+        let times_variable =
+            |expr: Self, n: u32| (0..n).fold(expr, |expr, _| Self::product(expr, variable.clone()));
+        let mut expr = Self::constant(coeff);
+        for (coeff, next) in terms {
+            expr = Self::sum(times_variable(expr, degree - next), Self::constant(coeff));
+            degree = next;
+        }
+        times_variable(expr, degree)
+    }
 }
 
 /// Allows evaluating the type with an [`EvalExpression`] evaluator.
 pub trait EvaluableExpr<F>: ExpressionTypes {
     /// Evaluates the expression.
     fn evaluate<E: EvalExpression<F, Self>>(&self, evaluator: &E) -> E::Output;
+
+    /// Returns the degree of the expression, computed with [`DegreeEvaluator`].
+    fn degree(&self) -> usize {
+        self.evaluate(&DegreeEvaluator)
+    }
+
+    /// Evaluates the expression, reusing the result of a previous evaluation of the same
+    /// expression with the evaluator of the cache.
+    ///
+    /// Results are keyed on the address of the expression. The cache borrows the expressions it
+    /// holds results for, so they can't be moved or dropped while it is alive.
+    fn cached_evaluate<'a, E: EvalExpression<F, Self>>(
+        &'a self,
+        cache: &EvaluationCache<'a, Self, E, E::Output>,
+    ) -> E::Output
+    where
+        E::Output: Clone,
+    {
+        let key = self as *const Self as *const ();
+        let cached = cache.results.borrow().get(&key).cloned();
+        if let Some(output) = cached {
+            return output;
+        }
+        let output = self.evaluate(cache.evaluator);
+        cache.results.borrow_mut().insert(key, output.clone());
+        output
+    }
+}
+
+/// Results of evaluating expressions with an evaluator, used by
+/// [`EvaluableExpr::cached_evaluate`].
+///
+/// The cache is tied to a single evaluator and is dropped together with its results. Evaluators
+/// that change their state during evaluation must not be cached, since the evaluations served
+/// from the cache don't reach the evaluator.
+pub struct EvaluationCache<'a, X, E, O> {
+    evaluator: &'a E,
+    results: RefCell<HashMap<*const (), O>>,
+    _exprs: PhantomData<&'a X>,
+}
+
+impl<'a, X, E, O> EvaluationCache<'a, X, E, O> {
+    /// Creates an empty cache for the evaluator.
+    pub fn new(evaluator: &'a E) -> Self {
+        Self {
+            evaluator,
+            results: Default::default(),
+            _exprs: PhantomData,
+        }
+    }
+
+    /// Returns the evaluator of the cache.
+    pub fn evaluator(&self) -> &'a E {
+        self.evaluator
+    }
+
+    /// Returns the number of cached results.
+    pub fn len(&self) -> usize {
+        self.results.borrow().len()
+    }
+
+    /// Returns true if there are no cached results.
+    pub fn is_empty(&self) -> bool {
+        self.results.borrow().is_empty()
+    }
+
+    /// Removes all the cached results.
+    pub fn clear(&self) {
+        self.results.borrow_mut().clear()
+    }
 }
 
 /// Evaluates an [`EvaluableExpr`].
@@ -107,3 +238,54 @@ where
     /// Evaluate the [`Expression::Scaled`] case.
     fn scaled(&self, lhs: Self::Output, rhs: &F) -> Self::Output;
 }
+
+/// Evaluator that computes the degree of an expression.
+///
+/// Constants and queries have degree 1, sums take the maximum degree of their operands and
+/// products add the degrees of their operands. Negation and scaling preserve the degree.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct DegreeEvaluator;
+
+impl<F, E: ExpressionTypes> EvalExpression<F, E> for DegreeEvaluator {
+    type Output = usize;
+
+    fn constant(&self, _: &F) -> Self::Output {
+        1
+    }
+
+    fn selector(&self, _: &E::Selector) -> Self::Output {
+        1
+    }
+
+    fn fixed(&self, _: &E::FixedQuery) -> Self::Output {
+        1
+    }
+
+    fn advice(&self, _: &E::AdviceQuery) -> Self::Output {
+        1
+    }
+
+    fn instance(&self, _: &E::InstanceQuery) -> Self::Output {
+        1
+    }
+
+    fn challenge(&self, _: &E::Challenge) -> Self::Output {
+        1
+    }
+
+    fn negated(&self, expr: Self::Output) -> Self::Output {
+        expr
+    }
+
+    fn sum(&self, lhs: Self::Output, rhs: Self::Output) -> Self::Output {
+        lhs.max(rhs)
+    }
+
+    fn product(&self, lhs: Self::Output, rhs: Self::Output) -> Self::Output {
+        lhs + rhs
+    }
+
+    fn scaled(&self, lhs: Self::Output, _: &F) -> Self::Output {
+        lhs
+    }
+}
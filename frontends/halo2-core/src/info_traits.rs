@@ -31,6 +31,38 @@ pub trait GateInfo<P> {
 
     /// Returns the list of polynomials that make up the gate.
     fn polynomials(&self) -> &[P];
+
+    /// Returns the maximum degree of the polynomials of the gate.
+    ///
+    /// The degree is computed with [`EvaluableExpr::degree`]. If the gate has no polynomials
+    /// returns 0.
+    fn max_degree<F>(&self) -> usize
+    where
+        P: EvaluableExpr<F>,
+        Self: Sized,
+    {
+        max_degree(self.polynomials())
+    }
+}
+
+impl<P> dyn GateInfo<P> + '_ {
+    /// Returns the maximum degree of the polynomials of the gate.
+    ///
+    /// Same as [`GateInfo::max_degree`] but can be called on trait objects.
+    pub fn max_polynomial_degree<F>(&self) -> usize
+    where
+        P: EvaluableExpr<F>,
+    {
+        max_degree(self.polynomials())
+    }
+}
+
+fn max_degree<F, P: EvaluableExpr<F>>(polynomials: &[P]) -> usize {
+    polynomials
+        .iter()
+        .map(EvaluableExpr::degree)
+        .max()
+        .unwrap_or_default()
 }
 
 /// Trait for retrieving information about cell queries.
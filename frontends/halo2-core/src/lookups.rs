@@ -9,4 +9,8 @@ pub struct LookupData<'syn, E> {
     pub arguments: &'syn [E],
     /// Expressions representing the columns of the table.
     pub table: &'syn [E],
+    /// Expression of the multiplicity polynomial, if the lookup scheme uses one (e.g. LogUp).
+    ///
+    /// Plookup-style lookups don't have a multiplicity and leave this as `None`.
+    pub multiplicity: Option<&'syn E>,
 }
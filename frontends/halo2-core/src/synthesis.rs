@@ -73,4 +73,21 @@ pub trait SynthesizerLike<F: Field> {
     ///
     /// Panics if attempted to pop a group without pushing one prior.
     fn exit_group(&mut self, meta: impl GroupInfo);
+
+    /// Records the current state of the synthesizer.
+    ///
+    /// If the synthesis fails, [`SynthesizerLike::rollback`] restores the synthesizer to the
+    /// recorded state. The default implementation does nothing.
+    fn snapshot(&mut self) {}
+
+    /// Restores the state recorded by the last call to [`SynthesizerLike::snapshot`].
+    ///
+    /// The default implementation does nothing.
+    fn rollback(&mut self) {}
+
+    /// Drops the state recorded by the last call to [`SynthesizerLike::snapshot`] once the
+    /// synthesis succeeded.
+    ///
+    /// The default implementation does nothing.
+    fn discard_snapshot(&mut self) {}
 }
@@ -16,3 +16,19 @@ pub trait Wrapped: Sized {
     /// Wraps the value into its wrapper.
     fn wrap(self) -> Self::Wrapper;
 }
+
+impl<T: Wrapped> Wrapped for Option<T> {
+    type Wrapper = Option<T::Wrapper>;
+
+    fn wrap(self) -> Self::Wrapper {
+        self.map(Wrapped::wrap)
+    }
+}
+
+impl<A: Wrapped, B: Wrapped> Wrapped for (A, B) {
+    type Wrapper = (A::Wrapper, B::Wrapper);
+
+    fn wrap(self) -> Self::Wrapper {
+        (self.0.wrap(), self.1.wrap())
+    }
+}
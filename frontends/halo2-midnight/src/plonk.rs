@@ -6,7 +6,10 @@ use crate::{
 };
 use ff::Field;
 use halo2_frontend_core::{
-    expressions::{EvalExpression, EvaluableExpr, ExprBuilder, ExpressionInfo, ExpressionTypes},
+    expressions::{
+        EvalExpression, EvaluableExpr, ExprBuilder, ExpressionInfo, ExpressionOperandsInfo,
+        ExpressionTypes,
+    },
     info_traits::{
         ChallengeInfo, ConstraintSystemInfo, CreateQuery, GateInfo, QueryInfo, SelectorInfo,
     },
@@ -146,7 +149,7 @@ impl<FC: ColumnWrapper + ColumnConversion<TC>, TC: halo2_frontend_core::table::C
 //===----------------------------------------------------------------------===//
 
 ///Newtype wrapper over [`ConstraintSystem`](midnight_proofs::plonk::ConstraintSystem).
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct ConstraintSystem<F: Field> {
     cs: midnight_proofs::plonk::ConstraintSystem<F>,
     gates: Option<Vec<_Gate<F>>>,
@@ -211,6 +214,7 @@ impl<F: Field> ConstraintSystemInfo<F> for ConstraintSystem<F> {
                 name: name.as_str(),
                 arguments: inputs.as_slice(),
                 table: table.as_slice(),
+                multiplicity: None,
             })
             .collect()
     }
@@ -226,6 +230,12 @@ pub struct _Expression<F: Field> {
     inner: Expression<F>,
     as_negation: Option<Box<_Expression<F>>>,
     as_fixed_query: Option<_FixedQuery>,
+    as_advice_query: Option<_AdviceQuery>,
+    as_instance_query: Option<_InstanceQuery>,
+    as_challenge: Option<_Challenge>,
+    as_sum: Option<Box<(_Expression<F>, _Expression<F>)>>,
+    as_product: Option<Box<(_Expression<F>, _Expression<F>)>>,
+    as_scaled: Option<Box<_Expression<F>>>,
 }
 
 impl<F: Field> _Expression<F> {
@@ -245,6 +255,34 @@ impl<F: Field> From<Expression<F>> for _Expression<F> {
                 Expression::Fixed(query) => Some((*query).into()),
                 _ => None,
             },
+            as_advice_query: match &inner {
+                Expression::Advice(query) => Some((*query).into()),
+                _ => None,
+            },
+            as_instance_query: match &inner {
+                Expression::Instance(query) => Some((*query).into()),
+                _ => None,
+            },
+            as_challenge: match &inner {
+                Expression::Challenge(challenge) => Some((*challenge).into()),
+                _ => None,
+            },
+            as_sum: match &inner {
+                Expression::Sum(lhs, rhs) => {
+                    Some(Box::new(((**lhs).clone().into(), (**rhs).clone().into())))
+                }
+                _ => None,
+            },
+            as_product: match &inner {
+                Expression::Product(lhs, rhs) => {
+                    Some(Box::new(((**lhs).clone().into(), (**rhs).clone().into())))
+                }
+                _ => None,
+            },
+            as_scaled: match &inner {
+                Expression::Scaled(expr, _) => Some(Box::new((**expr).clone().into())),
+                _ => None,
+            },
             inner,
         }
     }
@@ -266,6 +304,42 @@ impl<F: Field> ExpressionInfo for _Expression<F> {
     fn as_fixed_query(&self) -> Option<&Self::FixedQuery> {
         self.as_fixed_query.as_ref()
     }
+
+    fn as_advice_query(&self) -> Option<&Self::AdviceQuery> {
+        self.as_advice_query.as_ref()
+    }
+
+    fn as_instance_query(&self) -> Option<&Self::InstanceQuery> {
+        self.as_instance_query.as_ref()
+    }
+
+    fn as_challenge(&self) -> Option<&Self::Challenge> {
+        self.as_challenge.as_ref()
+    }
+}
+
+impl<F: Field> ExpressionOperandsInfo<F> for _Expression<F> {
+    fn as_sum(&self) -> Option<(&Self, &Self)> {
+        self.as_sum.as_deref().map(|(lhs, rhs)| (lhs, rhs))
+    }
+
+    fn as_product(&self) -> Option<(&Self, &Self)> {
+        self.as_product.as_deref().map(|(lhs, rhs)| (lhs, rhs))
+    }
+
+    fn as_constant(&self) -> Option<&F> {
+        match &self.inner {
+            Expression::Constant(f) => Some(f),
+            _ => None,
+        }
+    }
+
+    fn as_scaled(&self) -> Option<(&Self, &F)> {
+        match (&self.inner, self.as_scaled.as_deref()) {
+            (Expression::Scaled(_, f), Some(expr)) => Some((expr, f)),
+            _ => None,
+        }
+    }
 }
 
 impl<F: Field> EvaluableExpr<F> for _Expression<F> {
@@ -372,7 +446,7 @@ impl<F: Field> CreateQuery<_Expression<F>> for _FixedQuery {
 //===----------------------------------------------------------------------===//
 
 /// Newtype wrapper over [`Gate`].
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct _Gate<F: Field> {
     name: String,
     polynomials: Vec<_Expression<F>>,
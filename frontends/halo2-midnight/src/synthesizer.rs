@@ -27,6 +27,8 @@ pub struct SynthesizerAssignment<'a, F: Field, S: SynthesizerLike<F>> {
 
 impl<'a, F: Field, S: SynthesizerLike<F>> SynthesizerAssignment<'a, F, S> {
     /// Synthesizes the given circuit, recording the synthesis information in the synthetizer.
+    ///
+    /// If the synthesis fails the synthetizer is rolled back to the state it had before.
     pub fn synthesize<C: Circuit<F>>(
         circuit: &C,
         config: C::Config,
@@ -38,9 +40,15 @@ impl<'a, F: Field, S: SynthesizerLike<F>> SynthesizerAssignment<'a, F, S> {
             _marker: Default::default(),
         };
         let constants = cs.inner().constants().clone();
-        C::FloorPlanner::synthesize(&mut assign, circuit, config, constants)?;
-
-        Ok(())
+        assign.synthetizer.snapshot();
+        let result = C::FloorPlanner::synthesize(&mut assign, circuit, config, constants);
+        if result.is_err() {
+            // Do not leave partial state (i.e. open regions) behind if the synthesis failed.
+            assign.synthetizer.rollback();
+        } else {
+            assign.synthetizer.discard_snapshot();
+        }
+        result
     }
 }
 
@@ -1,11 +1,13 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 
 use super::lowering::ExprLowering as _;
 use super::lowering::lowerable::LowerableStmt;
 use super::{func::FuncIO, lowering::Lowering};
 use crate::io::{AdviceIO, InstanceIO};
-use crate::ir::expr::Felt;
+use crate::ir::expr::{Felt, IRAexpr};
+use crate::ir::groups::GroupBody;
 use crate::ir::{IRCtx, ResolvedIRCircuit};
 use anyhow::Result;
 
@@ -95,18 +97,129 @@ pub trait CodegenStrategy {
         codegen: &C,
         ctx: &IRCtx,
         ir: &ResolvedIRCircuit,
+        progress: &ProgressReporter,
     ) -> Result<()>
     where
         C: Codegen<'c, 'st>;
+
+    /// Returns the number of functions the strategy generates for the circuit.
+    fn function_count(&self, ir: &ResolvedIRCircuit) -> usize;
+}
+
+/// Snapshot of the progress of the code generation.
+#[derive(Debug, Copy, Clone)]
+pub struct CodegenProgress<'a> {
+    /// Number of groups already generated.
+    pub groups_done: usize,
+    /// Number of groups that are going to be generated.
+    pub groups_total: usize,
+    /// Name of the group that is about to be generated.
+    pub current_group_name: &'a str,
+    /// Time since the code generation started.
+    pub elapsed: Duration,
+}
+
+/// Notifies a callback each time a strategy starts generating a group.
+pub struct ProgressReporter<'p> {
+    start: Instant,
+    groups_done: Cell<usize>,
+    groups_total: Cell<usize>,
+    callback: &'p dyn Fn(CodegenProgress),
+}
+
+impl<'p> ProgressReporter<'p> {
+    pub fn new(callback: &'p dyn Fn(CodegenProgress)) -> Self {
+        Self {
+            start: Instant::now(),
+            groups_done: Cell::new(0),
+            groups_total: Cell::new(0),
+            callback,
+        }
+    }
+
+    /// Sets the number of groups the strategy is going to generate.
+    pub fn set_groups_total(&self, total: usize) {
+        self.groups_total.set(total);
+    }
+
+    /// Reports that the strategy is starting to generate the given group.
+    pub fn group_started(&self, name: &str) {
+        (self.callback)(CodegenProgress {
+            groups_done: self.groups_done.get(),
+            groups_total: self.groups_total.get(),
+            current_group_name: name,
+            elapsed: self.start.elapsed(),
+        });
+        self.groups_done.set(self.groups_done.get() + 1);
+    }
 }
 
 pub trait CodegenParams {
     /// Returns true if inlining is enabled.
     fn inlining_enabled(&self) -> bool;
+
+    /// Returns the maximum depth of the groups that get inlined.
+    ///
+    /// By default there is no limit.
+    fn inlining_max_depth(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns true if the group with the given name can be inlined.
+    ///
+    /// Only used if [`CodegenParams::restricts_inlined_groups`] returns true. By default all
+    /// groups can be inlined.
+    #[allow(unused_variables)]
+    fn inlines_group(&self, name: &str) -> bool {
+        true
+    }
+
+    /// Returns true if some groups can't be inlined.
+    ///
+    /// By default returns false.
+    fn restricts_inlined_groups(&self) -> bool {
+        false
+    }
+
+    /// Returns true if the group should be generated when not inlining.
+    ///
+    /// Only used if [`CodegenParams::restricts_emitted_groups`] returns true. By default every
+    /// group is generated.
+    #[allow(unused_variables)]
+    fn emits_group(&self, group: &GroupBody<IRAexpr>) -> bool {
+        true
+    }
+
+    /// Returns true if only some groups are generated when not inlining.
+    ///
+    /// By default returns false.
+    fn restricts_emitted_groups(&self) -> bool {
+        false
+    }
 }
 
 impl<T: CodegenParams> CodegenParams for Rc<RefCell<T>> {
     fn inlining_enabled(&self) -> bool {
         self.borrow().inlining_enabled()
     }
+
+    fn inlining_max_depth(&self) -> Option<usize> {
+        self.borrow().inlining_max_depth()
+    }
+
+    fn inlines_group(&self, name: &str) -> bool {
+        self.borrow().inlines_group(name)
+    }
+
+    fn restricts_inlined_groups(&self) -> bool {
+        self.borrow().restricts_inlined_groups()
+    }
+
+    fn emits_group(&self, group: &GroupBody<IRAexpr>) -> bool {
+        self.borrow().emits_group(group)
+    }
+
+    fn restricts_emitted_groups(&self) -> bool {
+        self.borrow().restricts_emitted_groups()
+    }
 }
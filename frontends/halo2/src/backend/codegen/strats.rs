@@ -1,21 +1,73 @@
 pub mod inline {
 
     use crate::{
-        backend::codegen::{Codegen, CodegenStrategy},
+        backend::codegen::{Codegen, CodegenStrategy, ProgressReporter},
         ir::{IRCtx, ResolvedIRCircuit},
     };
     use anyhow::Result;
+    use std::collections::{HashSet, VecDeque};
 
     /// Code generation strategy that generates the all the code inside the main function.
+    ///
+    /// The inlining can be limited by depth and by the name of the groups. The groups that are not
+    /// inlined are generated as separate functions.
     #[derive(Default)]
-    pub struct InlineConstraintsStrat {}
+    pub struct InlineConstraintsStrat<'f> {
+        max_depth: Option<usize>,
+        group_filter: Option<Box<dyn Fn(&str) -> bool + 'f>>,
+    }
+
+    impl<'f> InlineConstraintsStrat<'f> {
+        /// Only inlines the groups that are called at most `n` levels deep from the main
+        /// function.
+        pub fn with_max_depth(mut self, n: usize) -> Self {
+            self.max_depth = Some(n);
+            self
+        }
+
+        /// Only inlines the groups whose name is accepted by the filter.
+        pub fn with_group_filter(mut self, f: impl Fn(&str) -> bool + 'f) -> Self {
+            self.group_filter = Some(Box::new(f));
+            self
+        }
+
+        /// Returns the ids of the groups that get inlined into the main function.
+        ///
+        /// A group is inlined if it is reachable from the main group through inlined groups
+        /// within the maximum depth and the filter accepts it.
+        fn inlined_groups(&self, ir: &ResolvedIRCircuit) -> HashSet<usize> {
+            if self.max_depth.is_none() && self.group_filter.is_none() {
+                return ir.groups().iter().map(|g| g.id()).collect();
+            }
+            let main_id = ir.main().id();
+            let mut inlined = HashSet::from([main_id]);
+            let mut worklist = VecDeque::from([(main_id, 0)]);
+            while let Some((id, depth)) = worklist.pop_front() {
+                if self.max_depth.is_some_and(|n| depth >= n) {
+                    continue;
+                }
+                for callsite in ir.groups()[id].callsites() {
+                    let callee = &ir.groups()[callsite.callee_id()];
+                    if inlined.contains(&callee.id())
+                        || !self.group_filter.as_ref().is_none_or(|f| f(callee.name()))
+                    {
+                        continue;
+                    }
+                    inlined.insert(callee.id());
+                    worklist.push_back((callee.id(), depth + 1));
+                }
+            }
+            inlined
+        }
+    }
 
-    impl CodegenStrategy for InlineConstraintsStrat {
+    impl CodegenStrategy for InlineConstraintsStrat<'_> {
         fn codegen<'c: 'st, 's, 'st, C>(
             &self,
             codegen: &C,
             ctx: &IRCtx,
             ir: &ResolvedIRCircuit,
+            progress: &ProgressReporter,
         ) -> Result<()>
         where
             C: Codegen<'c, 'st>,
@@ -25,14 +77,42 @@ pub mod inline {
                 std::any::type_name_of_val(self)
             );
 
+            let inlined = self.inlined_groups(ir);
+            let (inlined, outlined): (Vec<_>, Vec<_>) = ir
+                .groups()
+                .iter()
+                .cloned()
+                .partition(|g| inlined.contains(&g.id()));
+            progress.set_groups_total(inlined.len() + outlined.len());
+            for group in outlined {
+                progress.group_started(group.name());
+                log::debug!("Generating body of function {}", group.name());
+                let advice_io = ctx.advice_io_of_group(group.id());
+                let instance_io = ctx.instance_io_of_group(group.id());
+                let name = group.name().to_owned();
+                codegen.define_function_with_body(
+                    &name,
+                    advice_io.inputs_count() + instance_io.inputs_count(),
+                    advice_io.outputs_count() + instance_io.outputs_count(),
+                    |_, _, _| Ok([group]),
+                )?;
+            }
+
             log::debug!("Generating main body");
             let main_id = ir.main().id();
             codegen.define_main_function_with_body(
                 ctx.advice_io_of_group(main_id),
                 ctx.instance_io_of_group(main_id),
-                ir.groups().to_vec(),
+                inlined
+                    .into_iter()
+                    .inspect(|group| progress.group_started(group.name())),
             )
         }
+
+        fn function_count(&self, ir: &ResolvedIRCircuit) -> usize {
+            // The main function plus a function per group that is not inlined.
+            1 + ir.groups().len() - self.inlined_groups(ir).len()
+        }
     }
 }
 
@@ -43,7 +123,7 @@ pub mod groups {
     use crate::ir::groups::GroupBody;
     use crate::synthesis::groups::GroupKey;
     use crate::{
-        backend::codegen::{Codegen, CodegenStrategy},
+        backend::codegen::{Codegen, CodegenStrategy, ProgressReporter},
         ir::{
             ResolvedIRCircuit,
             equivalency::{EqvRelation, SymbolicEqv},
@@ -54,37 +134,74 @@ pub mod groups {
     use std::collections::{HashMap, HashSet};
 
     /// Code generation strategy that write the code of each group in a separate function.
+    ///
+    /// If a filter is configured only the groups accepted by it, and the groups they call, are
+    /// generated. The rest of the groups are skipped, except for the main group which is always
+    /// generated but without a body if the filter doesn't select it.
     #[derive(Default)]
-    pub struct GroupConstraintsStrat {}
+    pub struct GroupConstraintsStrat<'f> {
+        filter: Option<Box<GroupFilter<'f>>>,
+    }
+
+    type GroupFilter<'f> = dyn Fn(&GroupBody<IRAexpr>) -> bool + 'f;
+
+    impl<'f> GroupConstraintsStrat<'f> {
+        /// Only generates the groups accepted by the filter and their dependencies.
+        pub fn with_filter(mut self, f: impl Fn(&GroupBody<IRAexpr>) -> bool + 'f) -> Self {
+            self.filter = Some(Box::new(f));
+            self
+        }
+
+        /// Removes the groups that are not selected by the filter. The main group is always kept.
+        fn retain_selected(&self, groups: &mut Vec<GroupBody<IRAexpr>>) -> HashSet<usize> {
+            let selected = self.selected_groups(groups);
+            groups.retain(|g| {
+                let keep = g.is_main() || selected.contains(&g.id());
+                if !keep {
+                    log::debug!("Group \"{}\" was filtered out", g.name());
+                }
+                keep
+            });
+            selected
+        }
+
+        /// Returns the ids of the groups accepted by the filter and the groups they call
+        /// transitively.
+        fn selected_groups(&self, groups: &[GroupBody<IRAexpr>]) -> HashSet<usize> {
+            let Some(filter) = &self.filter else {
+                return groups.iter().map(|g| g.id()).collect();
+            };
+            let groups_by_id: HashMap<_, _> = groups.iter().map(|g| (g.id(), g)).collect();
+            let mut worklist: Vec<_> = groups
+                .iter()
+                .filter(|g| filter(g))
+                .map(|g| g.id())
+                .collect();
+            let mut selected = HashSet::new();
+            while let Some(id) = worklist.pop() {
+                if !selected.insert(id) {
+                    continue;
+                }
+                if let Some(group) = groups_by_id.get(&id) {
+                    worklist.extend(group.callsites().iter().map(|c| c.callee_id()));
+                }
+            }
+            selected
+        }
+    }
 
-    impl CodegenStrategy for GroupConstraintsStrat {
+    impl CodegenStrategy for GroupConstraintsStrat<'_> {
         fn codegen<'c: 'st, 's, 'st, C>(
             &self,
             codegen: &C,
             ctx: &IRCtx,
             ir: &ResolvedIRCircuit,
+            progress: &ProgressReporter,
         ) -> Result<()>
         where
             C: Codegen<'c, 'st>,
         {
-            let mut groups_ir = ir.groups().to_vec();
-            // Select leaders and generate the final names.
-            // If the group was renamed its index will contain Some(_).
-            let (leaders, updated_calldata) = select_leaders(&groups_ir);
-
-            log::debug!("Leaders for the non-main groups: {leaders:?}");
-            log::debug!("Updated calldata: {updated_calldata:?}");
-            // Build the final list of IR and invoke codegen
-            groups_ir.retain_mut(|g| {
-                // Keep a group if its main or is in the leaders list.
-                let keep = g.is_main() || leaders.contains(&g.id());
-                if keep {
-                    // If we are keeping it update the names if necessary.
-                    update_names(g, &updated_calldata)
-                }
-                keep
-            });
-
+            let mut groups_ir = leader_groups(ir);
             let mut err_count = 0;
             for group in &groups_ir {
                 log::debug!("Validating group \"{}\"", group.name());
@@ -99,15 +216,23 @@ pub mod groups {
             if err_count > 0 {
                 anyhow::bail!("Codegen failed due to {err_count} validation errors");
             }
+            let selected = self.retain_selected(&mut groups_ir);
+            progress.set_groups_total(groups_ir.len());
             // Create a function per group.
             for group in groups_ir {
+                progress.group_started(group.name());
                 log::debug!("Generating code for group \"{}\"", group.name());
 
                 let advice_io = ctx.advice_io_of_group(group.id());
                 let instance_io = ctx.instance_io_of_group(group.id());
                 if group.is_main() {
+                    let emit_body = selected.contains(&group.id());
                     log::debug!("Generating main body");
-                    codegen.define_main_function_with_body(advice_io, instance_io, [group])?;
+                    codegen.define_main_function_with_body(
+                        advice_io,
+                        instance_io,
+                        emit_body.then_some(group),
+                    )?;
                 } else {
                     log::debug!("Generating body of function {}", group.name());
                     let name = group.name().to_owned();
@@ -122,6 +247,35 @@ pub mod groups {
             }
             Ok(())
         }
+
+        fn function_count(&self, ir: &ResolvedIRCircuit) -> usize {
+            let mut groups_ir = leader_groups(ir);
+            self.retain_selected(&mut groups_ir);
+            groups_ir.len()
+        }
+    }
+
+    /// Returns the main group and the leaders of the equivalence classes of the rest of the
+    /// groups, with their final names.
+    fn leader_groups(ir: &ResolvedIRCircuit) -> Vec<GroupBody<IRAexpr>> {
+        let mut groups_ir = ir.groups().to_vec();
+        // Select leaders and generate the final names.
+        // If the group was renamed its index will contain Some(_).
+        let (leaders, updated_calldata) = select_leaders(&groups_ir);
+
+        log::debug!("Leaders for the non-main groups: {leaders:?}");
+        log::debug!("Updated calldata: {updated_calldata:?}");
+        // Build the final list of IR and invoke codegen
+        groups_ir.retain_mut(|g| {
+            // Keep a group if its main or is in the leaders list.
+            let keep = g.is_main() || leaders.contains(&g.id());
+            if keep {
+                // If we are keeping it update the names if necessary.
+                update_names(g, &updated_calldata)
+            }
+            keep
+        });
+        groups_ir
     }
 
     /// Organizes the groups by their key. Each group contains a list with the index to the group.
@@ -0,0 +1,17 @@
+pub use codegen::DotCodegen;
+pub use params::{DotParams, DotParamsBuilder};
+
+use super::Backend;
+
+mod codegen;
+mod lowering;
+pub(crate) mod params;
+
+pub type DotBackend<'s> = Backend<DotCodegen<'s>, DotParams>;
+
+/// Output produced by the DOT backend.
+///
+/// Contains a GraphViz graph where each group is a subgraph, each cell is a node, equality
+/// constraints between cells are dashed edges and the rest of constraints are labeled solid
+/// edges.
+pub type DotOutput = String;
@@ -0,0 +1,97 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use super::{
+    DotOutput,
+    lowering::{DotFunction, DotFunctionLowering, DotFunctionRef},
+    params::DotParams,
+};
+use crate::{
+    backend::codegen::Codegen,
+    io::{AdviceIO, InstanceIO},
+};
+use anyhow::Result;
+
+pub struct DotCodegen<'s> {
+    _params: &'s DotParams,
+    functions: RefCell<Vec<DotFunctionRef>>,
+}
+
+impl DotCodegen<'_> {
+    fn add_function(&self, name: &str, is_main: bool) -> DotFunctionLowering {
+        let mut functions = self.functions.borrow_mut();
+        let function = Rc::new(RefCell::new(DotFunction::new(
+            name,
+            functions.len(),
+            is_main,
+        )));
+        functions.push(function.clone());
+        DotFunctionLowering::new(function)
+    }
+}
+
+impl<'c: 's, 's> Codegen<'c, 's> for DotCodegen<'s> {
+    type FuncOutput = DotFunctionLowering;
+    type Output = DotOutput;
+    type State = DotParams;
+
+    fn initialize(state: &'s Self::State) -> Self {
+        Self {
+            _params: state,
+            functions: Default::default(),
+        }
+    }
+
+    fn define_main_function(
+        &self,
+        _advice_io: &AdviceIO,
+        _instance_io: &InstanceIO,
+    ) -> Result<Self::FuncOutput> {
+        Ok(self.add_function("Main", true))
+    }
+
+    fn define_function(
+        &self,
+        name: &str,
+        _inputs: usize,
+        _outputs: usize,
+    ) -> Result<Self::FuncOutput> {
+        Ok(self.add_function(name, false))
+    }
+
+    fn generate_output(self) -> Result<Self::Output> {
+        let functions = self.functions.into_inner();
+        let anchors = functions
+            .iter()
+            .map(|f| {
+                let f = f.borrow();
+                (f.name().to_owned(), f.anchor())
+            })
+            .collect::<HashMap<_, _>>();
+
+        let mut lines = vec![
+            "digraph circuit {".to_owned(),
+            "  compound=true;".to_owned(),
+        ];
+        for function in &functions {
+            function.borrow().write_subgraph(&mut lines);
+        }
+        for function in &functions {
+            let function = function.borrow();
+            for callee in function.calls() {
+                match anchors.get(callee) {
+                    Some(anchor) => lines.push(format!(
+                        "  {} -> {anchor} [label=\"call\", style=bold];",
+                        function.anchor()
+                    )),
+                    None => log::warn!(
+                        "Group \"{}\" calls \"{}\" which was not generated",
+                        function.name(),
+                        callee
+                    ),
+                }
+            }
+        }
+        lines.push("}".to_owned());
+        Ok(lines.join("\n") + "\n")
+    }
+}
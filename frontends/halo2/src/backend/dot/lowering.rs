@@ -0,0 +1,416 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    rc::Rc,
+};
+
+use crate::{
+    backend::{
+        func::FuncIO,
+        lowering::{ExprLowering, Lowering},
+    },
+    ir::{CmpOp, expr::Felt},
+};
+use anyhow::Result;
+
+/// Expression lowered by the DOT backend.
+///
+/// Keeps a textual representation of the expression, for labels, and the nodes of the cells the
+/// expression depends on, for edges.
+#[derive(Clone, Debug)]
+pub struct DotExpr {
+    text: String,
+    nodes: BTreeSet<String>,
+    is_cell: bool,
+}
+
+impl DotExpr {
+    fn leaf(text: String) -> Self {
+        Self {
+            text,
+            nodes: Default::default(),
+            is_cell: false,
+        }
+    }
+
+    fn cell(text: String, node: String) -> Self {
+        Self {
+            text,
+            nodes: [node].into(),
+            is_cell: true,
+        }
+    }
+
+    fn unary(op: &str, expr: &Self) -> Self {
+        Self {
+            text: format!("{op}{}", expr.text),
+            nodes: expr.nodes.clone(),
+            is_cell: false,
+        }
+    }
+
+    fn binary(lhs: &Self, op: &str, rhs: &Self) -> Self {
+        Self {
+            text: format!("({} {op} {})", lhs.text, rhs.text),
+            nodes: lhs.nodes.union(&rhs.nodes).cloned().collect(),
+            is_cell: false,
+        }
+    }
+}
+
+/// Returns the name of the gate if the comment is the one emitted by the IR before the
+/// constraints of a gate.
+fn gate_of_comment(comment: &str) -> Option<String> {
+    let (name, _) = comment.strip_prefix("gate '")?.split_once("' @ ")?;
+    Some(name.to_owned())
+}
+
+/// Returns the text escaped for being used inside a quoted DOT string.
+pub fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Contents of the subgraph of a group.
+#[derive(Debug)]
+pub struct DotFunction {
+    name: String,
+    index: usize,
+    is_main: bool,
+    /// Maps the label of each cell to its node id.
+    nodes: BTreeMap<String, String>,
+    edges: Vec<String>,
+    calls: Vec<String>,
+    constraints: usize,
+    /// Number of gate constraints, used for labeling them when the IR doesn't name their gate.
+    gate_constraints: usize,
+    /// Name of the gate the following constraints belong to, taken from the debug comments of
+    /// the IR.
+    current_gate: Option<String>,
+}
+
+impl DotFunction {
+    pub fn new(name: &str, index: usize, is_main: bool) -> Self {
+        Self {
+            name: name.to_owned(),
+            index,
+            is_main,
+            nodes: Default::default(),
+            edges: Default::default(),
+            calls: Default::default(),
+            constraints: 0,
+            gate_constraints: 0,
+            current_gate: None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the id of the node that represents the group itself.
+    pub fn anchor(&self) -> String {
+        format!("g{}", self.index)
+    }
+
+    /// Returns the names of the functions this function calls.
+    pub fn calls(&self) -> &[String] {
+        &self.calls
+    }
+
+    /// Writes the function as a cluster subgraph.
+    pub fn write_subgraph(&self, out: &mut Vec<String>) {
+        out.push(format!("  subgraph cluster_{} {{", self.index));
+        out.push(format!("    label=\"{}\";", escape(&self.name)));
+        if self.is_main {
+            out.push("    style=bold;".to_owned());
+        }
+        out.push(format!(
+            "    {} [label=\"{}\", shape=folder];",
+            self.anchor(),
+            escape(&self.name)
+        ));
+        for (label, id) in &self.nodes {
+            out.push(format!("    {id} [label=\"{}\"];", escape(label)));
+        }
+        for edge in &self.edges {
+            out.push(format!("    {edge}"));
+        }
+        out.push("  }".to_owned());
+    }
+}
+
+pub type DotFunctionRef = Rc<RefCell<DotFunction>>;
+
+#[derive(Clone, Debug)]
+pub struct DotFunctionLowering {
+    function: DotFunctionRef,
+}
+
+impl DotFunctionLowering {
+    pub fn new(function: DotFunctionRef) -> Self {
+        Self { function }
+    }
+
+    fn node(&self, func_io: FuncIO) -> DotExpr {
+        let mut function = self.function.borrow_mut();
+        let fresh = format!("g{}_{}", function.index, function.nodes.len());
+        let label = func_io.to_string();
+        let id = function.nodes.entry(label.clone()).or_insert(fresh).clone();
+        DotExpr::cell(label, id)
+    }
+
+    /// Adds a box node for the constraint and connects to it every cell the constraint uses.
+    fn constraint_node(&self, label: &str, expr: &DotExpr) {
+        let mut function = self.function.borrow_mut();
+        function.constraints += 1;
+        let id = format!("g{}_c{}", function.index, function.constraints);
+        function.edges.push(format!(
+            "{id} [label=\"{}\", shape=box];",
+            escape(&expr.text)
+        ));
+        for node in &expr.nodes {
+            function
+                .edges
+                .push(format!("{node} -> {id} [label=\"{}\"];", escape(label)));
+        }
+    }
+}
+
+impl Lowering for DotFunctionLowering {
+    fn generate_constraint(
+        &self,
+        op: CmpOp,
+        lhs: &Self::CellOutput,
+        rhs: &Self::CellOutput,
+    ) -> Result<()> {
+        if let (CmpOp::Eq, true, true) = (op, lhs.is_cell, rhs.is_cell) {
+            let mut function = self.function.borrow_mut();
+            function.constraints += 1;
+            for (lhs, rhs) in std::iter::zip(&lhs.nodes, &rhs.nodes) {
+                function
+                    .edges
+                    .push(format!("{lhs} -> {rhs} [style=dashed, dir=none];"));
+            }
+            return Ok(());
+        }
+        let label = {
+            let mut function = self.function.borrow_mut();
+            function.gate_constraints += 1;
+            match &function.current_gate {
+                Some(name) => format!("gate {name}"),
+                None => format!("gate {}", function.gate_constraints),
+            }
+        };
+        self.constraint_node(&label, &DotExpr::binary(lhs, &op.to_string(), rhs));
+        Ok(())
+    }
+
+    fn num_constraints(&self) -> usize {
+        self.function.borrow().constraints
+    }
+
+    fn generate_comment(&self, s: String) -> Result<()> {
+        self.function.borrow_mut().current_gate = gate_of_comment(&s);
+        Ok(())
+    }
+
+    fn generate_assume_deterministic(&self, func_io: FuncIO) -> Result<()> {
+        self.node(func_io);
+        Ok(())
+    }
+
+    fn generate_call(
+        &self,
+        name: &str,
+        // The nodes of the inputs were created when they were lowered.
+        _selectors: &[Self::CellOutput],
+        outputs: &[FuncIO],
+    ) -> Result<()> {
+        for output in outputs {
+            self.node(*output);
+        }
+        self.function.borrow_mut().calls.push(name.to_owned());
+        Ok(())
+    }
+
+    fn generate_assert(&self, expr: &Self::CellOutput) -> Result<()> {
+        self.constraint_node("assert", expr);
+        Ok(())
+    }
+
+    fn generate_post_condition(&self, expr: &Self::CellOutput) -> Result<()> {
+        self.constraint_node("post-condition", expr);
+        Ok(())
+    }
+}
+
+impl ExprLowering for DotFunctionLowering {
+    type CellOutput = DotExpr;
+
+    fn lower_sum(
+        &self,
+        lhs: &Self::CellOutput,
+        rhs: &Self::CellOutput,
+    ) -> Result<Self::CellOutput> {
+        Ok(DotExpr::binary(lhs, "+", rhs))
+    }
+
+    fn lower_product(
+        &self,
+        lhs: &Self::CellOutput,
+        rhs: &Self::CellOutput,
+    ) -> Result<Self::CellOutput> {
+        Ok(DotExpr::binary(lhs, "*", rhs))
+    }
+
+    fn lower_neg(&self, expr: &Self::CellOutput) -> Result<Self::CellOutput> {
+        Ok(DotExpr::unary("-", expr))
+    }
+
+    fn lower_constant(&self, f: Felt) -> Result<Self::CellOutput> {
+        Ok(DotExpr::leaf(f.to_string()))
+    }
+
+    fn lower_eq(&self, lhs: &Self::CellOutput, rhs: &Self::CellOutput) -> Result<Self::CellOutput> {
+        Ok(DotExpr::binary(lhs, "==", rhs))
+    }
+
+    fn lower_lt(&self, lhs: &Self::CellOutput, rhs: &Self::CellOutput) -> Result<Self::CellOutput> {
+        Ok(DotExpr::binary(lhs, "<", rhs))
+    }
+
+    fn lower_le(&self, lhs: &Self::CellOutput, rhs: &Self::CellOutput) -> Result<Self::CellOutput> {
+        Ok(DotExpr::binary(lhs, "<=", rhs))
+    }
+
+    fn lower_gt(&self, lhs: &Self::CellOutput, rhs: &Self::CellOutput) -> Result<Self::CellOutput> {
+        Ok(DotExpr::binary(lhs, ">", rhs))
+    }
+
+    fn lower_ge(&self, lhs: &Self::CellOutput, rhs: &Self::CellOutput) -> Result<Self::CellOutput> {
+        Ok(DotExpr::binary(lhs, ">=", rhs))
+    }
+
+    fn lower_ne(&self, lhs: &Self::CellOutput, rhs: &Self::CellOutput) -> Result<Self::CellOutput> {
+        Ok(DotExpr::binary(lhs, "!=", rhs))
+    }
+
+    fn lower_and(
+        &self,
+        lhs: &Self::CellOutput,
+        rhs: &Self::CellOutput,
+    ) -> Result<Self::CellOutput> {
+        Ok(DotExpr::binary(lhs, "&&", rhs))
+    }
+
+    fn lower_or(&self, lhs: &Self::CellOutput, rhs: &Self::CellOutput) -> Result<Self::CellOutput> {
+        Ok(DotExpr::binary(lhs, "||", rhs))
+    }
+
+    fn lower_not(&self, value: &Self::CellOutput) -> Result<Self::CellOutput> {
+        Ok(DotExpr::unary("!", value))
+    }
+
+    fn lower_true(&self) -> Result<Self::CellOutput> {
+        Ok(DotExpr::leaf("true".to_owned()))
+    }
+
+    fn lower_false(&self) -> Result<Self::CellOutput> {
+        Ok(DotExpr::leaf("false".to_owned()))
+    }
+
+    fn lower_det(&self, expr: &Self::CellOutput) -> Result<Self::CellOutput> {
+        Ok(DotExpr::unary("det ", expr))
+    }
+
+    fn lower_implies(
+        &self,
+        lhs: &Self::CellOutput,
+        rhs: &Self::CellOutput,
+    ) -> Result<Self::CellOutput> {
+        Ok(DotExpr::binary(lhs, "=>", rhs))
+    }
+
+    fn lower_iff(
+        &self,
+        lhs: &Self::CellOutput,
+        rhs: &Self::CellOutput,
+    ) -> Result<Self::CellOutput> {
+        Ok(DotExpr::binary(lhs, "<=>", rhs))
+    }
+
+    fn lower_function_input(&self, i: usize) -> FuncIO {
+        FuncIO::input(i)
+    }
+
+    fn lower_function_output(&self, o: usize) -> FuncIO {
+        FuncIO::output(o)
+    }
+
+    fn lower_funcio<IO>(&self, io: IO) -> Result<Self::CellOutput>
+    where
+        IO: Into<FuncIO>,
+    {
+        Ok(self.node(io.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lowering() -> DotFunctionLowering {
+        DotFunctionLowering::new(Rc::new(RefCell::new(DotFunction::new("f", 0, false))))
+    }
+
+    #[test]
+    fn copy_constraint_is_dashed_edge() {
+        let l = lowering();
+        let lhs = l.lower_funcio(FuncIO::advice_abs(0, 0)).unwrap();
+        let rhs = l.lower_funcio(FuncIO::advice_abs(1, 0)).unwrap();
+        l.generate_constraint(CmpOp::Eq, &lhs, &rhs).unwrap();
+        assert_eq!(l.num_constraints(), 1);
+        assert_eq!(
+            l.function.borrow().edges,
+            vec!["g0_0 -> g0_1 [style=dashed, dir=none];".to_owned()]
+        );
+    }
+
+    #[test]
+    fn gate_is_labeled_solid_edge() {
+        let l = lowering();
+        let cell = l.lower_funcio(FuncIO::advice_abs(0, 0)).unwrap();
+        let prod = l.lower_product(&cell, &cell).unwrap();
+        let zero = l.lower_constant(Felt::from(0usize)).unwrap();
+        l.generate_constraint(CmpOp::Eq, &prod, &zero).unwrap();
+        assert_eq!(
+            l.function.borrow().edges,
+            vec![
+                "g0_c1 [label=\"((adv[0,0] * adv[0,0]) == 0)\", shape=box];".to_owned(),
+                "g0_0 -> g0_c1 [label=\"gate 1\"];".to_owned(),
+            ]
+        );
+    }
+
+    #[test]
+    fn gate_labels_follow_the_ir() {
+        let l = lowering();
+        let lhs = l.lower_funcio(FuncIO::advice_abs(0, 0)).unwrap();
+        let rhs = l.lower_funcio(FuncIO::advice_abs(1, 0)).unwrap();
+        let zero = l.lower_constant(Felt::from(0usize)).unwrap();
+        // Copy constraints don't count as gates.
+        l.generate_constraint(CmpOp::Eq, &lhs, &rhs).unwrap();
+        l.generate_constraint(CmpOp::Eq, &lhs, &zero).unwrap();
+        l.generate_comment("gate 'mul' @ region 0 'first row' @ rows 0..=0".to_owned())
+            .unwrap();
+        l.generate_constraint(CmpOp::Eq, &rhs, &zero).unwrap();
+        l.generate_comment("Equality constraints".to_owned())
+            .unwrap();
+        l.generate_constraint(CmpOp::Lt, &rhs, &zero).unwrap();
+
+        let edges = &l.function.borrow().edges;
+        assert_eq!(edges[2], "g0_0 -> g0_c2 [label=\"gate 1\"];");
+        assert_eq!(edges[4], "g0_1 -> g0_c3 [label=\"gate mul\"];");
+        assert_eq!(edges[6], "g0_1 -> g0_c4 [label=\"gate 3\"];");
+    }
+}
@@ -0,0 +1,54 @@
+use crate::backend::codegen::CodegenParams;
+
+/// Configuration for the DOT backend.
+#[derive(Clone, Debug, Default)]
+pub struct DotParams {
+    inline: bool,
+}
+
+impl DotParams {
+    /// Returns wether inlining is enabled or not.
+    pub fn inline(&self) -> bool {
+        self.inline
+    }
+}
+
+impl CodegenParams for DotParams {
+    fn inlining_enabled(&self) -> bool {
+        self.inline
+    }
+}
+
+/// Builder for creating [`DotParams`] instances.
+#[derive(Debug, Default)]
+pub struct DotParamsBuilder(DotParams);
+
+impl DotParamsBuilder {
+    /// Creates a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets lowering to inlining everything into the main subgraph.
+    pub fn inline(&mut self) -> &mut Self {
+        self.0.inline = true;
+        self
+    }
+
+    /// Sets lowering to creating a separate subgraph for each group.
+    pub fn no_inline(&mut self) -> &mut Self {
+        self.0.inline = false;
+        self
+    }
+
+    /// Completes the build process and returns the parameters.
+    pub fn build(&mut self) -> DotParams {
+        std::mem::take(&mut self.0)
+    }
+}
+
+impl From<DotParamsBuilder> for DotParams {
+    fn from(builder: DotParamsBuilder) -> Self {
+        builder.0
+    }
+}
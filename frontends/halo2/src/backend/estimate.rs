@@ -0,0 +1,75 @@
+//! Approximations of the size of the output of a backend.
+
+use std::cell::Cell;
+
+use crate::ir::{ResolvedIRCircuit, expr::IRAexpr, stmt::IRStmt};
+
+/// Estimated size of the output of a backend, computed without lowering the circuit.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct BackendEstimate {
+    group_count: usize,
+    estimated_variable_count: usize,
+    estimated_constraint_count: usize,
+    estimated_expr_nodes: usize,
+}
+
+impl BackendEstimate {
+    /// Computes the estimate by counting the statements and expressions of the groups.
+    ///
+    /// The number of groups is computed by the caller, since it depends on the strategy used
+    /// for generating the code.
+    pub(crate) fn new(ir: &ResolvedIRCircuit, group_count: usize) -> Self {
+        let mut estimate = Self {
+            group_count,
+            ..Default::default()
+        };
+        let expr_nodes = Cell::new(0);
+        let count_nodes = |expr: &IRAexpr| expr_nodes.set(expr_nodes.get() + expr.size());
+        for group in ir.groups() {
+            estimate.estimated_variable_count += group.input_count() + group.output_count();
+            for callsite in group.callsites() {
+                estimate.estimated_variable_count += callsite.output_vars().len();
+                estimate.estimated_constraint_count += callsite.outputs().len();
+                callsite
+                    .inputs()
+                    .iter()
+                    .chain(callsite.outputs())
+                    .for_each(count_nodes);
+            }
+            for stmt in group.statements() {
+                estimate.estimated_constraint_count += stmt
+                    .iter()
+                    .filter(|s| {
+                        matches!(
+                            s,
+                            IRStmt::Constraint(_) | IRStmt::Assert(_) | IRStmt::PostCond(_)
+                        )
+                    })
+                    .count();
+                stmt.map_into(&count_nodes);
+            }
+        }
+        estimate.estimated_expr_nodes = expr_nodes.get();
+        estimate
+    }
+
+    /// Returns the number of functions or modules the backend would generate.
+    pub fn group_count(&self) -> usize {
+        self.group_count
+    }
+
+    /// Returns the approximate number of variables, counting inputs, outputs and call outputs.
+    pub fn estimated_variable_count(&self) -> usize {
+        self.estimated_variable_count
+    }
+
+    /// Returns the approximate number of constraints.
+    pub fn estimated_constraint_count(&self) -> usize {
+        self.estimated_constraint_count
+    }
+
+    /// Returns the number of nodes in all the expressions of the circuit.
+    pub fn estimated_expr_nodes(&self) -> usize {
+        self.estimated_expr_nodes
+    }
+}
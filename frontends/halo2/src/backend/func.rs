@@ -7,6 +7,7 @@ use crate::{
 
 /// Argument number of a function
 #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "json-export", derive(serde::Serialize, serde::Deserialize))]
 pub struct ArgNo(usize);
 
 impl From<usize> for ArgNo {
@@ -43,6 +44,7 @@ impl fmt::Debug for ArgNo {
 
 /// An identifier that Backend::FuncOutput will use to identify an output field in the function.
 #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "json-export", derive(serde::Serialize, serde::Deserialize))]
 pub struct FieldId(usize);
 
 impl From<usize> for FieldId {
@@ -86,6 +88,7 @@ enum Offset {
 
 /// A reference to a cell in the circuit.
 #[derive(Clone, Copy, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "json-export", derive(serde::Serialize, serde::Deserialize))]
 pub struct CellRef {
     col: usize,
     base: Option<usize>,
@@ -197,6 +200,7 @@ impl EqvRelation<CellRef> for SymbolicEqv {
 }
 
 #[derive(Clone, Copy, Hash, Eq, PartialEq, PartialOrd, Ord)]
+#[cfg_attr(feature = "json-export", derive(serde::Serialize, serde::Deserialize))]
 pub enum FuncIO {
     /// Points to the n-th input argument
     Arg(ArgNo),
@@ -217,6 +221,16 @@ pub enum FuncIO {
 }
 
 impl FuncIO {
+    /// Creates a reference to the n-th input argument.
+    pub fn input(idx: usize) -> Self {
+        Self::Arg(idx.into())
+    }
+
+    /// Creates a reference to the n-th output.
+    pub fn output(idx: usize) -> Self {
+        Self::Field(idx.into())
+    }
+
     pub fn advice_abs(col: usize, row: usize) -> Self {
         Self::Advice(CellRef::absolute(col, row))
     }
@@ -472,6 +486,15 @@ mod tests {
             != hash(CellRef::relative(col, base, offset + 1))
     }
 
+    /// Tests that the input and output constructors create arguments and fields respectively.
+    #[quickcheck]
+    fn input_and_output_constructors(idx: usize) -> bool {
+        let _ = TestLogger::init(LevelFilter::Debug, Config::default());
+        FuncIO::input(idx) == FuncIO::Arg(ArgNo::from(idx))
+            && FuncIO::output(idx) == FuncIO::Field(FieldId::from(idx))
+            && FuncIO::input(idx) != FuncIO::output(idx)
+    }
+
     /// Tests that two relative references with the same column and offset are symbolically equivalent.
     #[quickcheck]
     fn same_relative_sym_eqv(col: usize, base: usize, offset: usize) -> bool {
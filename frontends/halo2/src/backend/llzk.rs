@@ -1,5 +1,6 @@
+use anyhow::Result;
 pub use codegen::LlzkCodegen;
-use melior::ir::Module;
+use melior::{Context, dialect::DialectRegistry, ir::Module};
 pub use state::LlzkCodegenState;
 
 use super::Backend;
@@ -26,6 +27,24 @@ impl<'c> LlzkOutput<'c> {
     pub fn module(&self) -> &Module<'c> {
         &self.module
     }
+
+    /// Returns the textual MLIR representation of the module.
+    pub fn to_mlir_text(&self) -> String {
+        self.module.as_operation().to_string()
+    }
+
+    /// Parses a module from its textual MLIR representation.
+    ///
+    /// The LLZK dialects are registered in the context before parsing.
+    pub fn from_mlir_text(ctx: &'c Context, s: &str) -> Result<Self> {
+        let registry = DialectRegistry::new();
+        llzk::register_all_llzk_dialects(&registry);
+        ctx.append_dialect_registry(&registry);
+        ctx.load_all_available_dialects();
+        Module::parse(ctx, s)
+            .map(Self::from)
+            .ok_or_else(|| anyhow::anyhow!("Failed to parse MLIR module"))
+    }
 }
 
 impl<'c> From<Module<'c>> for LlzkOutput<'c> {
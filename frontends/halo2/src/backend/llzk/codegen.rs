@@ -207,6 +207,27 @@ mod tests {
         )
     }
 
+    #[rstest]
+    fn mlir_text_round_trip(ctx: LlzkContext) {
+        let state: LlzkCodegenState = LlzkParamsBuilder::new(&ctx).no_optimize().build().into();
+        let codegen = LlzkCodegen::initialize(&state);
+        let advice_col = Column::new(0, Advice);
+        let instance_col = Column::new(0, Instance);
+        let advice_io = AdviceIO::new(&[(advice_col, &[0, 1])], &[(advice_col, &[2])]).unwrap();
+        let instance_io =
+            InstanceIO::new(&[(instance_col, &[0])], &[(instance_col, &[1])]).unwrap();
+        let main = codegen
+            .define_main_function(&advice_io, &instance_io)
+            .unwrap();
+        codegen.on_scope_end(main).unwrap();
+        let output = codegen.generate_output().unwrap();
+
+        let text = output.to_mlir_text();
+        let parsed = LlzkOutput::from_mlir_text(&ctx, &text).unwrap();
+        verify_operation_with_diags(&parsed.module().as_operation()).unwrap();
+        assert_eq!(text, parsed.to_mlir_text());
+    }
+
     main_function_test! {
         define_main_function_mixed_io,
         "test_files/mixed_io.mlir",
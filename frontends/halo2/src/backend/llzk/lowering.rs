@@ -209,6 +209,18 @@ impl<'c> Lowering for LlzkStructLowering<'c, '_> {
         self.generate_assert(&cond)
     }
 
+    /// LLZK does not define a range operation so the range is lowered as two assertions. These are
+    /// not `constrain.eq` operations and thus are not counted by [`Lowering::num_constraints`].
+    fn generate_range_constraint(
+        &self,
+        value: &Self::CellOutput,
+        low: &Self::CellOutput,
+        high: &Self::CellOutput,
+    ) -> Result<()> {
+        self.generate_constraint(CmpOp::Ge, value, low)?;
+        self.generate_constraint(CmpOp::Le, value, high)
+    }
+
     fn num_constraints(&self) -> usize {
         self.get_constrain_func()
             .map(|op| {
@@ -477,6 +489,50 @@ impl<'c> ExprLowering for LlzkStructLowering<'c, '_> {
             Location::unknown(self.context())
         )))
     }
+
+    fn lower_xor(
+        &self,
+        lhs: &Self::CellOutput,
+        rhs: &Self::CellOutput,
+    ) -> Result<Self::CellOutput> {
+        wrap!(self.append_expr(felt::bit_xor(
+            Location::unknown(self.context()),
+            lhs.into(),
+            rhs.into()
+        )?))
+    }
+
+    fn lower_shl(&self, val: &Self::CellOutput, shift: u32) -> Result<Self::CellOutput> {
+        let shift = self.lower_constant_impl(Felt::from(shift))?;
+        wrap!(self.append_expr(felt::shl(
+            Location::unknown(self.context()),
+            val.into(),
+            shift
+        )?))
+    }
+
+    fn lower_shr(&self, val: &Self::CellOutput, shift: u32) -> Result<Self::CellOutput> {
+        let shift = self.lower_constant_impl(Felt::from(shift))?;
+        wrap!(self.append_expr(felt::shr(
+            Location::unknown(self.context()),
+            val.into(),
+            shift
+        )?))
+    }
+
+    fn lower_conditional(
+        &self,
+        cond: &Self::CellOutput,
+        then: &Self::CellOutput,
+        r#else: &Self::CellOutput,
+    ) -> Result<Self::CellOutput> {
+        wrap!(self.append_expr(bool::select(
+            Location::unknown(self.context()),
+            cond.into(),
+            then.into(),
+            r#else.into()
+        )?))
+    }
 }
 
 #[cfg(test)]
@@ -802,6 +858,71 @@ mod tests {
         })
     }
 
+    #[rstest]
+    fn lower_xor(fragment_main: FragmentCfg) {
+        fragment_test(
+            fragment_main,
+            r"%0 = struct.readf %arg1[@reg] : <@Signal<[]>>, !felt.type
+              %1 = felt.bit_xor %0, %0",
+            |l| {
+                let arg = l.lower_funcio(l.lower_function_input(0))?;
+                l.lower_xor(&arg, &arg)?;
+                Ok(())
+            },
+        )
+    }
+
+    #[rstest]
+    fn lower_shl(fragment_main: FragmentCfg) {
+        fragment_test(
+            fragment_main,
+            r"%0 = struct.readf %arg1[@reg] : <@Signal<[]>>, !felt.type
+              %felt_const_3 = felt.const 3
+              %1 = felt.shl %0, %felt_const_3",
+            |l| {
+                let arg = l.lower_funcio(l.lower_function_input(0))?;
+                l.lower_shl(&arg, 3)?;
+                Ok(())
+            },
+        )
+    }
+
+    #[rstest]
+    fn lower_conditional(fragment_main: FragmentCfg) {
+        fragment_test(
+            fragment_main,
+            r"%0 = struct.readf %arg1[@reg] : <@Signal<[]>>, !felt.type
+              %true = arith.constant true
+              %1 = arith.select %true, %0, %0 : !felt.type",
+            |l| {
+                let arg = l.lower_funcio(l.lower_function_input(0))?;
+                let t = l.lower_true()?;
+                l.lower_conditional(&t, &arg, &arg)?;
+                Ok(())
+            },
+        )
+    }
+
+    #[rstest]
+    fn generate_range_constraint(fragment_main: FragmentCfg) {
+        fragment_test(
+            fragment_main,
+            r#"%0 = struct.readf %arg1[@reg] : <@Signal<[]>>, !felt.type
+              %1 = struct.readf %arg2[@reg] : <@Signal<[]>>, !felt.type
+              %2 = bool.cmp ge(%0, %1)
+              "bool.assert"(%2) : (i1) -> ()
+              %3 = bool.cmp le(%0, %0)
+              "bool.assert"(%3) : (i1) -> ()"#,
+            |l| {
+                let val = l.lower_funcio(l.lower_function_input(0))?;
+                let low = l.lower_funcio(l.lower_function_input(1))?;
+                l.generate_range_constraint(&val, &low, &val)?;
+                assert_eq!(l.num_constraints(), 0);
+                Ok(())
+            },
+        )
+    }
+
     #[rstest]
     fn lower_true(fragment_main: FragmentCfg) {
         fragment_test(fragment_main, "%true = arith.constant true", |l| {
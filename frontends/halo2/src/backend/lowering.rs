@@ -32,10 +32,35 @@ pub trait Lowering: ExprLowering {
         Ok(())
     }
 
+    fn generate_range_constraint(
+        &self,
+        value: &Self::CellOutput,
+        low: &Self::CellOutput,
+        high: &Self::CellOutput,
+    ) -> Result<()> {
+        let before = self.num_constraints();
+        self.generate_constraint(CmpOp::Ge, value, low)?;
+        self.generate_constraint(CmpOp::Le, value, high)?;
+        let after = self.num_constraints();
+        if after != before + 2 {
+            bail!(
+                "Range constraint was expected to generate 2 constraints but generated {}",
+                after.saturating_sub(before)
+            );
+        }
+        Ok(())
+    }
+
     fn generate_comment(&self, s: String) -> Result<()>;
 
     fn generate_assume_deterministic(&self, func_io: FuncIO) -> Result<()>;
 
+    fn generate_assume_deterministic_many(&self, func_ios: &[FuncIO]) -> Result<()> {
+        func_ios
+            .iter()
+            .try_for_each(|func_io| self.generate_assume_deterministic(*func_io))
+    }
+
     fn generate_call(
         &self,
         name: &str,
@@ -85,6 +110,28 @@ pub trait ExprLowering {
     fn lower_iff(&self, lhs: &Self::CellOutput, rhs: &Self::CellOutput)
     -> Result<Self::CellOutput>;
 
+    fn lower_xor(
+        &self,
+        _lhs: &Self::CellOutput,
+        _rhs: &Self::CellOutput,
+    ) -> Result<Self::CellOutput> {
+        bail!("bitwise xor is not supported by this backend")
+    }
+    fn lower_shl(&self, _val: &Self::CellOutput, _shift: u32) -> Result<Self::CellOutput> {
+        bail!("left shift is not supported by this backend")
+    }
+    fn lower_shr(&self, _val: &Self::CellOutput, _shift: u32) -> Result<Self::CellOutput> {
+        bail!("right shift is not supported by this backend")
+    }
+    fn lower_conditional(
+        &self,
+        _cond: &Self::CellOutput,
+        _then: &Self::CellOutput,
+        _else: &Self::CellOutput,
+    ) -> Result<Self::CellOutput> {
+        bail!("conditional expressions are not supported by this backend")
+    }
+
     fn lower_function_input(&self, i: usize) -> FuncIO;
     fn lower_function_output(&self, o: usize) -> FuncIO;
 
@@ -7,14 +7,24 @@ use crate::{
 use anyhow::Result;
 
 pub mod codegen;
+#[cfg(feature = "dot-backend")]
+pub mod dot;
+pub mod estimate;
 pub mod func;
 #[cfg(feature = "llzk-backend")]
 pub mod llzk;
 pub mod lowering;
 #[cfg(feature = "picus-backend")]
 pub mod picus;
+#[cfg(feature = "smtlib2-backend")]
+pub mod smtlib2;
 
-use codegen::{Codegen, CodegenStrategy, strats::groups::GroupConstraintsStrat};
+use estimate::BackendEstimate;
+
+use codegen::{
+    Codegen, CodegenProgress, CodegenStrategy, ProgressReporter,
+    strats::groups::GroupConstraintsStrat,
+};
 
 /// Entrypoint for the backend.
 pub struct Backend<C, S> {
@@ -42,12 +52,57 @@ where
         C::initialize(&self.state)
     }
 
+    /// Estimates the size of the output without generating code.
+    ///
+    /// The number of groups is the number of functions the default strategy would generate.
+    pub fn estimate(&self, ir: &ResolvedIRCircuit) -> BackendEstimate {
+        let group_count = if self.state.inlining_enabled() {
+            self.inline_strat().function_count(ir)
+        } else {
+            self.groups_strat().function_count(ir)
+        };
+        BackendEstimate::new(ir, group_count)
+    }
+
+    /// Creates the inlining strategy configured by the params.
+    fn inline_strat(&self) -> InlineConstraintsStrat<'_> {
+        let mut strat = InlineConstraintsStrat::default();
+        if self.state.restricts_inlined_groups() {
+            strat = strat.with_group_filter(|name| self.state.inlines_group(name));
+        }
+        if let Some(n) = self.state.inlining_max_depth() {
+            strat = strat.with_max_depth(n);
+        }
+        strat
+    }
+
+    /// Creates the strategy that generates a function per group configured by the params.
+    fn groups_strat(&self) -> GroupConstraintsStrat<'_> {
+        let mut strat = GroupConstraintsStrat::default();
+        if self.state.restricts_emitted_groups() {
+            strat = strat.with_filter(|g| self.state.emits_group(g));
+        }
+        strat
+    }
+
     /// Generate code using the default strategy.
     pub fn codegen(&'b self, ir: &ResolvedIRCircuit, ctx: &IRCtx) -> Result<C::Output> {
+        self.codegen_with_progress(ir, ctx, |_| {})
+    }
+
+    /// Generate code using the default strategy and calls the callback at the start of each
+    /// group.
+    pub fn codegen_with_progress(
+        &'b self,
+        ir: &ResolvedIRCircuit,
+        ctx: &IRCtx,
+        callback: impl Fn(CodegenProgress),
+    ) -> Result<C::Output> {
+        let progress = ProgressReporter::new(&callback);
         if self.state.inlining_enabled() {
-            self.codegen_with_strat(ir, ctx, InlineConstraintsStrat::default())
+            self.codegen_with_strat(ir, ctx, self.inline_strat(), &progress)
         } else {
-            self.codegen_with_strat(ir, ctx, GroupConstraintsStrat::default())
+            self.codegen_with_strat(ir, ctx, self.groups_strat(), &progress)
         }
     }
 
@@ -57,6 +112,7 @@ where
         ir: &ResolvedIRCircuit,
         ctx: &IRCtx,
         strat: impl CodegenStrategy,
+        progress: &ProgressReporter,
     ) -> Result<C::Output> {
         log::debug!("Initializing code generator");
         let codegen = self.create_codegen();
@@ -66,7 +122,7 @@ where
             std::any::type_name_of_val(&strat)
         );
 
-        strat.codegen(&codegen, ctx, ir)?;
+        strat.codegen(&codegen, ctx, ir, progress)?;
 
         log::debug!("Code generation completed");
         codegen.generate_output()
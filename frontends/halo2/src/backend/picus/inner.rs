@@ -3,7 +3,10 @@ use crate::{
         codegen::CodegenParams,
         picus::{params::PicusParams, PicusModule, Pipeline, PipelineBuilder},
     },
-    ir::expr::Felt,
+    ir::{
+        expr::{Felt, IRAexpr},
+        groups::GroupBody,
+    },
 };
 
 use anyhow::Result;
@@ -16,6 +19,7 @@ use super::{
 use picus::{
     opt::passes::{
         ConsolidateVarNamesPass, EnsureMaxExprSizePass, FoldExprsPass, ReplaceKnownConstsPass,
+        StrengthReductionPass,
     },
     vars::VarStr,
 };
@@ -61,6 +65,9 @@ impl PicusCodegenInner {
             .add_pass::<ConsolidateVarNamesPass>()
             .add_pass::<ReplaceKnownConstsPass>()
             .add_pass::<FoldExprsPass>();
+        if let Some(max_const) = self.params.strength_reduction() {
+            pipeline = pipeline.add_pass_with_params::<StrengthReductionPass>(max_const);
+        }
         if let Some(expr_cutoff) = self.params.expr_cutoff() {
             pipeline = pipeline.add_pass_with_params::<EnsureMaxExprSizePass<NamingConvention>>((
                 expr_cutoff,
@@ -92,6 +99,10 @@ impl PicusCodegenInner {
         Ok(scope)
     }
 
+    pub fn deduplicate_modules(&self) -> bool {
+        self.params.deduplicate_modules()
+    }
+
     pub fn entrypoint(&self) -> String {
         self.params.entrypoint().to_owned()
     }
@@ -101,4 +112,24 @@ impl CodegenParams for PicusCodegenInner {
     fn inlining_enabled(&self) -> bool {
         self.params.inline()
     }
+
+    fn inlining_max_depth(&self) -> Option<usize> {
+        self.params.inline_max_depth()
+    }
+
+    fn inlines_group(&self, name: &str) -> bool {
+        !self.params.is_outlined(name)
+    }
+
+    fn restricts_inlined_groups(&self) -> bool {
+        self.params.has_outlined_groups()
+    }
+
+    fn emits_group(&self, group: &GroupBody<IRAexpr>) -> bool {
+        self.params.emits_group(group.name())
+    }
+
+    fn restricts_emitted_groups(&self) -> bool {
+        self.params.has_emitted_groups()
+    }
 }
@@ -76,6 +76,7 @@ impl Lowering for PicusModuleLowering {
                 .copied()
                 .map(|o| self.lower_func_io(o))
                 .collect(),
+            None,
         )?;
         self.module.borrow_mut().add_stmt(stmt);
         Ok(())
@@ -15,7 +15,7 @@ use anyhow::Result;
 use inner::PicusCodegenInner;
 pub use lowering::PicusModuleLowering;
 pub use params::PicusParams;
-use picus::{opt::MutOptimizer as _, vars::VarStr};
+use picus::{analysis::calls::PicusCallValidator, opt::MutOptimizer as _, vars::VarStr};
 use utils::mk_io;
 use vars::{NamingConvention, VarKey, VarKeySeed};
 
@@ -31,6 +31,9 @@ type InnerState = Rc<RefCell<PicusCodegenInner>>;
 pub type PicusModule = picus::Module<VarKey>;
 /// Output produced by the picus backend.
 pub type PicusOutput = picus::Program<VarKey>;
+/// Statistics about the output produced by the picus backend.
+pub type PicusOutputStats = picus::ProgramStats;
+pub use picus::{OptimizationDelta, optimization_delta};
 type PipelineBuilder = picus::opt::OptimizerPipelineBuilder<VarKey>;
 type Pipeline = picus::opt::OptimizerPipeline<VarKey>;
 
@@ -83,11 +86,15 @@ impl PicusCodegen {
                 }
 
                 let dups = dups;
+                let conv = self.naming_convention();
                 for (k, names) in dups {
                     if names.len() == 1 {
                         continue;
                     }
-                    log::error!("Mismatched variable! (key = {k:?}) (names = {names:?})");
+                    log::error!(
+                        "Mismatched variable! (key = {}) (names = {names:?})",
+                        VarKey::IO(k).display(conv)
+                    );
                 }
                 anyhow::bail!(
                     "Inconsistency detected in circuit variables. Was expecting {} IO variables by {} were generated",
@@ -99,6 +106,20 @@ impl PicusCodegen {
         Ok(())
     }
 
+    fn call_outputs_check(&self, output: &PicusOutput) -> Result<()> {
+        let mismatches = PicusCallValidator.validate(output);
+        for mismatch in &mismatches {
+            log::error!("{mismatch}");
+        }
+        if !mismatches.is_empty() {
+            anyhow::bail!(
+                "Found {} calls with a mismatched number of outputs",
+                mismatches.len()
+            );
+        }
+        Ok(())
+    }
+
     fn optimization_pipeline(&self) -> Option<Pipeline> {
         self.inner.borrow().optimization_pipeline()
     }
@@ -153,8 +174,19 @@ impl<'c: 's, 's> Codegen<'c, 's> for PicusCodegen {
             self.inner.borrow().modules().to_vec(),
         );
         self.var_consistency_check(&output)?;
+        self.call_outputs_check(&output)?;
         if let Some(mut opt) = self.optimization_pipeline() {
+            let before = *output.stats();
             opt.optimize(&mut output)?;
+            log::debug!(
+                "Optimization results: {}",
+                optimization_delta(&before, output.stats())
+            );
+        }
+        if self.inner.borrow().deduplicate_modules() {
+            let entrypoint = self.inner.borrow().entrypoint();
+            let removed = output.deduplicate_modules(&entrypoint);
+            log::debug!("Removed {removed} duplicated modules");
         }
         Ok(output)
     }
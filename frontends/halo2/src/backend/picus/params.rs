@@ -1,4 +1,7 @@
-use super::vars::NamingConvention;
+use internment::Intern;
+use picus::opt::passes::StrengthReductionPass;
+
+use super::vars::{NamingConvention, RESERVED_PREFIXES};
 
 /// Configuration for the Picus backend.
 #[derive(Clone, Debug)]
@@ -7,7 +10,12 @@ pub struct PicusParams {
     entrypoint: Option<String>,
     naming_convention: NamingConvention,
     optimize: bool,
+    strength_reduction: Option<usize>,
     inline: bool,
+    inline_max_depth: Option<usize>,
+    outlined_groups: Vec<String>,
+    emitted_groups: Vec<String>,
+    deduplicate_modules: bool,
 }
 
 impl PicusParams {
@@ -21,6 +29,12 @@ impl PicusParams {
         self.optimize
     }
 
+    /// Returns the largest constant whose multiplications are rewritten into additions, if
+    /// configured.
+    pub fn strength_reduction(&self) -> Option<usize> {
+        self.strength_reduction
+    }
+
     /// Returns the maximum size of expressions, if configured.
     pub fn expr_cutoff(&self) -> Option<usize> {
         self.expr_cutoff
@@ -37,7 +51,12 @@ impl PicusParams {
             entrypoint: None,
             naming_convention: NamingConvention::Short,
             optimize: true,
+            strength_reduction: None,
             inline: false,
+            inline_max_depth: None,
+            outlined_groups: vec![],
+            emitted_groups: vec![],
+            deduplicate_modules: false,
         }
     }
 
@@ -45,6 +64,38 @@ impl PicusParams {
     pub fn inline(&self) -> bool {
         self.inline
     }
+
+    /// Returns the maximum depth of the groups that get inlined, if configured.
+    pub fn inline_max_depth(&self) -> Option<usize> {
+        self.inline_max_depth
+    }
+
+    /// Returns true if the group is kept as a separate module when inlining.
+    pub fn is_outlined(&self, group: &str) -> bool {
+        self.outlined_groups.iter().any(|name| name == group)
+    }
+
+    /// Returns true if any group was selected with [`PicusParamsBuilder::outline_group`].
+    pub fn has_outlined_groups(&self) -> bool {
+        !self.outlined_groups.is_empty()
+    }
+
+    /// Returns true if the body of the module of the group is generated.
+    ///
+    /// If no groups were selected with [`PicusParamsBuilder::only_group`] returns true.
+    pub fn emits_group(&self, group: &str) -> bool {
+        self.emitted_groups.is_empty() || self.emitted_groups.iter().any(|name| name == group)
+    }
+
+    /// Returns true if any group was selected with [`PicusParamsBuilder::only_group`].
+    pub fn has_emitted_groups(&self) -> bool {
+        !self.emitted_groups.is_empty()
+    }
+
+    /// Returns wether duplicated modules are removed from the output or not.
+    pub fn deduplicate_modules(&self) -> bool {
+        self.deduplicate_modules
+    }
 }
 
 impl Default for PicusParams {
@@ -87,6 +138,29 @@ impl PicusParamsBuilder {
         self
     }
 
+    /// Sets a naming convention that uses the given prefixes for inputs, outputs and temporaries,
+    /// and the given separator between the parts of the names.
+    ///
+    /// The prefixes must be non-empty and different from each other, can only contain ASCII
+    /// alphanumeric characters or `_`, and cannot start with the prefixes used for the other
+    /// variables (`adv`, `fix`, `lkp`, `cout`, `chall` and `l`). The separator must be `_`, since an
+    /// alphanumeric separator would make the names ambiguous.
+    pub fn custom_names(
+        &mut self,
+        input_prefix: &str,
+        output_prefix: &str,
+        temp_prefix: &str,
+        separator: char,
+    ) -> &mut Self {
+        self.0.naming_convention = NamingConvention::Custom {
+            input_prefix: Intern::from_ref(input_prefix),
+            output_prefix: Intern::from_ref(output_prefix),
+            temp_prefix: Intern::from_ref(temp_prefix),
+            separator,
+        };
+        self
+    }
+
     /// Enables optimizations.
     pub fn optimize(&mut self) -> &mut Self {
         self.0.optimize = true;
@@ -99,6 +173,20 @@ impl PicusParamsBuilder {
         self
     }
 
+    /// Rewrites multiplications by constants up to `max_const` into additions when optimizing.
+    ///
+    /// The threshold must be between 2 and [`StrengthReductionPass::MAX_CONST`].
+    pub fn strength_reduction(&mut self, max_const: usize) -> &mut Self {
+        self.0.strength_reduction = Some(max_const);
+        self
+    }
+
+    /// Disables rewriting multiplications by constants into additions.
+    pub fn no_strength_reduction(&mut self) -> &mut Self {
+        self.0.strength_reduction = None;
+        self
+    }
+
     /// Sets lowering to inlining everything into one module.
     pub fn inline(&mut self) -> &mut Self {
         self.0.inline = true;
@@ -111,14 +199,274 @@ impl PicusParamsBuilder {
         self
     }
 
+    /// When inlining, creates separate modules for the groups called more than `depth` levels
+    /// deep from the top-level module.
+    pub fn inline_max_depth(&mut self, depth: usize) -> &mut Self {
+        self.0.inline_max_depth = Some(depth);
+        self
+    }
+
+    /// When inlining, keeps the group with the given name, and the groups it calls, in separate
+    /// modules.
+    pub fn outline_group(&mut self, name: &str) -> &mut Self {
+        self.0.outlined_groups.push(name.to_owned());
+        self
+    }
+
+    /// Only generates the modules of the given group and the groups it calls. The rest of the
+    /// modules are skipped, except for the top-level module which is generated empty if it was not
+    /// selected. Can be called multiple times to select several groups.
+    pub fn only_group(&mut self, name: &str) -> &mut Self {
+        self.0.emitted_groups.push(name.to_owned());
+        self
+    }
+
+    /// Enables removing modules with the same contents as a previous module. Calls to the removed
+    /// modules are replaced by calls to the module that was kept.
+    pub fn deduplicate_modules(&mut self) -> &mut Self {
+        self.0.deduplicate_modules = true;
+        self
+    }
+
+    /// Disables removing duplicated modules.
+    pub fn no_deduplicate_modules(&mut self) -> &mut Self {
+        self.0.deduplicate_modules = false;
+        self
+    }
+
     /// Finishes the build process and returns the parameters.
-    pub fn build(&mut self) -> PicusParams {
-        std::mem::take(&mut self.0)
+    ///
+    /// Fails if any of the configured values is invalid. The error reports all the violations.
+    pub fn build(&mut self) -> Result<PicusParams, PicusParamsError> {
+        let params = std::mem::take(&mut self.0);
+        let mut violations = vec![];
+        if params.expr_cutoff == Some(0) {
+            violations
+                .push("expr_cutoff: the maximum size of expressions must be non-zero".to_owned());
+        }
+        if let Some(max_const) = params.strength_reduction {
+            if !(2..=StrengthReductionPass::MAX_CONST).contains(&max_const) {
+                violations.push(format!(
+                    "strength_reduction: the largest constant must be between 2 and {} ({max_const})",
+                    StrengthReductionPass::MAX_CONST
+                ));
+            }
+        }
+        if let Some(entrypoint) = params.entrypoint.as_deref() {
+            if entrypoint.is_empty() {
+                violations.push(
+                    "entrypoint: the name of the top-level module cannot be empty".to_owned(),
+                );
+            } else if entrypoint.chars().any(char::is_whitespace) {
+                violations.push(format!(
+                    "entrypoint: the name of the top-level module cannot contain whitespace ({entrypoint:?})"
+                ));
+            }
+        }
+        if let NamingConvention::Custom {
+            input_prefix,
+            output_prefix,
+            temp_prefix,
+            separator,
+        } = params.naming_convention
+        {
+            let is_valid = |c: char| c.is_ascii_alphanumeric() || c == '_';
+            let prefixes = [
+                ("input_prefix", input_prefix),
+                ("output_prefix", output_prefix),
+                ("temp_prefix", temp_prefix),
+            ];
+            for (field, prefix) in prefixes {
+                if prefix.is_empty() {
+                    violations.push(format!("naming_convention: {field} cannot be empty"));
+                } else if !prefix.chars().all(is_valid) {
+                    violations.push(format!(
+                        "naming_convention: {field} {:?} contains characters that are not valid in Picus identifiers",
+                        prefix.as_str()
+                    ));
+                } else if let Some(reserved) = RESERVED_PREFIXES
+                    .iter()
+                    .find(|reserved| prefix.starts_with(*reserved))
+                {
+                    violations.push(format!(
+                        "naming_convention: {field} {:?} starts with the reserved prefix {reserved:?}",
+                        prefix.as_str()
+                    ));
+                }
+            }
+            for (n, (lhs, lhs_prefix)) in prefixes.iter().enumerate() {
+                for (rhs, rhs_prefix) in &prefixes[n + 1..] {
+                    if !lhs_prefix.is_empty() && lhs_prefix == rhs_prefix {
+                        violations.push(format!(
+                            "naming_convention: {lhs} and {rhs} must be different ({:?})",
+                            lhs_prefix.as_str()
+                        ));
+                    }
+                }
+            }
+            if separator.is_ascii_alphanumeric() {
+                violations.push(format!(
+                    "naming_convention: separator {separator:?} cannot be alphanumeric since it makes the names ambiguous"
+                ));
+            } else if !is_valid(separator) {
+                violations.push(format!(
+                    "naming_convention: separator {separator:?} is not valid in Picus identifiers"
+                ));
+            }
+        }
+        if violations.is_empty() {
+            Ok(params)
+        } else {
+            Err(PicusParamsError { violations })
+        }
+    }
+}
+
+/// Error returned by [`PicusParamsBuilder::build`] when the configuration is not valid.
+#[derive(Debug, Clone)]
+pub struct PicusParamsError {
+    violations: Vec<String>,
+}
+
+impl PicusParamsError {
+    /// Returns the list of violations found in the configuration.
+    pub fn violations(&self) -> &[String] {
+        &self.violations
     }
 }
 
+impl std::fmt::Display for PicusParamsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Invalid Picus parameters:")?;
+        for violation in &self.violations {
+            writeln!(f, "  - {violation}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for PicusParamsError {}
+
 impl From<PicusParamsBuilder> for PicusParams {
     fn from(builder: PicusParamsBuilder) -> PicusParams {
         builder.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_params_are_valid() {
+        assert!(PicusParamsBuilder::new().build().is_ok());
+    }
+
+    #[test]
+    fn build_reports_every_violation() {
+        let err = PicusParamsBuilder::new()
+            .expr_cutoff(0)
+            .entrypoint("my main")
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err.violations(),
+            [
+                "expr_cutoff: the maximum size of expressions must be non-zero",
+                "entrypoint: the name of the top-level module cannot contain whitespace (\"my main\")",
+            ]
+        );
+        let err = PicusParamsBuilder::new()
+            .entrypoint("")
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err.violations(),
+            ["entrypoint: the name of the top-level module cannot be empty"]
+        );
+    }
+
+    #[test]
+    fn custom_names_are_valid() {
+        let params = PicusParamsBuilder::new()
+            .custom_names("signal_in", "signal_out", "tmp", '_')
+            .build();
+        assert!(params.is_ok());
+    }
+
+    #[test]
+    fn strength_reduction_threshold_is_bounded() {
+        assert!(
+            PicusParamsBuilder::new()
+                .strength_reduction(4)
+                .build()
+                .is_ok()
+        );
+        let err = PicusParamsBuilder::new()
+            .strength_reduction(100)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err.violations(),
+            ["strength_reduction: the largest constant must be between 2 and 8 (100)"]
+        );
+    }
+
+    #[test]
+    fn custom_prefixes_cannot_be_empty() {
+        let err = PicusParamsBuilder::new()
+            .custom_names("", "out", "tmp", '_')
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err.violations(),
+            ["naming_convention: input_prefix cannot be empty"]
+        );
+    }
+
+    #[test]
+    fn custom_prefixes_must_be_distinct() {
+        let err = PicusParamsBuilder::new()
+            .custom_names("v", "v", "v", '_')
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err.violations(),
+            [
+                "naming_convention: input_prefix and output_prefix must be different (\"v\")",
+                "naming_convention: input_prefix and temp_prefix must be different (\"v\")",
+                "naming_convention: output_prefix and temp_prefix must be different (\"v\")",
+            ]
+        );
+    }
+
+    // With `1` as separator the cells (1, 11) and (11, 1) would both be named `adv1111`.
+    #[test]
+    fn custom_separator_cannot_be_alphanumeric() {
+        let err = PicusParamsBuilder::new()
+            .custom_names("in", "out", "tmp", '1')
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err.violations(),
+            [
+                "naming_convention: separator '1' cannot be alphanumeric since it makes the names ambiguous"
+            ]
+        );
+    }
+
+    #[test]
+    fn custom_prefixes_cannot_use_reserved_prefixes() {
+        let err = PicusParamsBuilder::new()
+            .custom_names("adv", "couts", "tmp", '_')
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err.violations(),
+            [
+                "naming_convention: input_prefix \"adv\" starts with the reserved prefix \"adv\"",
+                "naming_convention: output_prefix \"couts\" starts with the reserved prefix \"cout\"",
+            ]
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use internment::Intern;
 use picus::vars::Temp;
 pub use picus::vars::{VarKind, VarStr};
 
@@ -49,9 +50,21 @@ impl Temp<'_> for VarKey {
     }
 }
 
+/// Prefixes of the names generated for cells, lookups, call outputs, challenges and lifted
+/// expressions. The user defined prefixes of [`NamingConvention::Custom`] cannot start with them.
+pub(super) const RESERVED_PREFIXES: [&str; 6] = ["adv", "fix", "lkp", "cout", "chall", "l"];
+
 #[derive(Debug, Clone, Copy)]
 pub enum NamingConvention {
     Short,
+    /// Like [`NamingConvention::Short`] but with user defined prefixes for inputs, outputs and
+    /// temporaries, and a user defined separator between the parts of the name.
+    Custom {
+        input_prefix: Intern<String>,
+        output_prefix: Intern<String>,
+        temp_prefix: Intern<String>,
+        separator: char,
+    },
 }
 
 impl NamingConvention {
@@ -69,24 +82,79 @@ impl NamingConvention {
                 FuncIO::Temp(temp) => format!("t{}", *temp),
                 FuncIO::Challenge(index, phase, _) => format!("chall_{index}_{phase}"),
             },
+            NamingConvention::Custom {
+                input_prefix,
+                output_prefix,
+                temp_prefix,
+                separator: s,
+            } => match func_io {
+                FuncIO::Arg(arg_no) => format!("{input_prefix}{s}{arg_no}"),
+                FuncIO::Field(field_id) => format!("{output_prefix}{s}{field_id}"),
+                FuncIO::Advice(adv) => format!("adv{s}{}{s}{}", adv.col(), adv.row()),
+                FuncIO::Fixed(fix) => format!("fix{s}{}{s}{}", fix.col(), fix.row()),
+                FuncIO::TableLookup(id, col, row, idx, ridx) => {
+                    format!("lkp{s}{id}{s}{col}{s}{row}{s}{idx}{s}{ridx}")
+                }
+                FuncIO::CallOutput(module, out) => format!("cout{s}{module}{s}{out}"),
+                FuncIO::Temp(temp) => format!("{temp_prefix}{s}{}", *temp),
+                FuncIO::Challenge(index, phase, _) => format!("chall{s}{index}{s}{phase}"),
+            },
         }
     }
 
     fn format_temp(&self) -> String {
         match self {
             // These temps are exclusive from the Picus backend so we use 'pt' for 'Picus temp'.
-            NamingConvention::Short => "pt",
+            NamingConvention::Short => "pt".to_owned(),
+            NamingConvention::Custom {
+                temp_prefix,
+                separator,
+                ..
+            } => format!("{temp_prefix}{separator}p"),
         }
-        .to_owned()
     }
 
     fn format_lifted(&self, id: usize) -> String {
         match self {
             NamingConvention::Short => format!("l{id}"),
+            NamingConvention::Custom { separator, .. } => format!("l{separator}{id}"),
+        }
+    }
+
+    /// Formats the key with this naming convention.
+    ///
+    /// The returned name does not include the suffix that is added when the name collides with
+    /// another variable in the same module.
+    pub fn format_key(&self, key: &VarKey) -> String {
+        match key {
+            VarKey::IO(func_io) => self.format_io(*func_io),
+            VarKey::Temp => self.format_temp(),
+            VarKey::Lifted(id) => self.format_lifted(*id),
         }
     }
 }
 
+impl VarKey {
+    /// Returns a value that displays the key with the given naming convention, matching the name
+    /// of the variable in the emitted Picus modules.
+    pub fn display(&self, conv: NamingConvention) -> DisplayVarKey<'_> {
+        DisplayVarKey { key: self, conv }
+    }
+}
+
+/// Displays a [`VarKey`] with a naming convention. Created with [`VarKey::display`].
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayVarKey<'k> {
+    key: &'k VarKey,
+    conv: NamingConvention,
+}
+
+impl std::fmt::Display for DisplayVarKey<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.conv.format_key(self.key))
+    }
+}
+
 /// Struct containing the metadata necessary to create a [`VarStr`].
 #[derive(Clone, Debug)]
 pub struct VarKeySeed(VarKeySeedInner, NamingConvention);
@@ -117,13 +185,8 @@ impl From<VarKeySeed> for VarKey {
 
 impl From<VarKeySeed> for VarStr {
     fn from(seed: VarKeySeed) -> VarStr {
-        match seed.0 {
-            VarKeySeedInner::IO(func_io) => seed.1.format_io(func_io),
-            VarKeySeedInner::Temp => seed.1.format_temp(),
-            VarKeySeedInner::Lifted(id) => seed.1.format_lifted(id),
-        }
-        .try_into()
-        .unwrap()
+        let conv = seed.1;
+        conv.format_key(&seed.into()).try_into().unwrap()
     }
 }
 
@@ -175,3 +238,28 @@ impl<T: Into<FuncIO>> From<T> for VarKeySeedInner {
         Self::IO(value.into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_follows_the_naming_convention() {
+        let arg = VarKey::IO(FuncIO::Arg(3.into()));
+        let field = VarKey::IO(FuncIO::Field(1.into()));
+        let short = NamingConvention::Short;
+        assert_eq!(arg.display(short).to_string(), "in_3");
+        assert_eq!(field.display(short).to_string(), "out_1");
+        assert_eq!(VarKey::Lifted(2).display(short).to_string(), "l2");
+
+        let custom = NamingConvention::Custom {
+            input_prefix: Intern::from_ref("signal_in"),
+            output_prefix: Intern::from_ref("signal_out"),
+            temp_prefix: Intern::from_ref("tmp"),
+            separator: '_',
+        };
+        assert_eq!(arg.display(custom).to_string(), "signal_in_3");
+        assert_eq!(field.display(custom).to_string(), "signal_out_1");
+        assert_eq!(VarKey::Temp.display(custom).to_string(), "tmp_p");
+    }
+}
@@ -0,0 +1,15 @@
+pub use codegen::SmtLib2Codegen;
+pub use params::{SmtLib2Params, SmtLib2ParamsBuilder};
+
+use super::Backend;
+
+mod codegen;
+mod lowering;
+pub(crate) mod params;
+
+pub type SmtLib2Backend<'s> = Backend<SmtLib2Codegen<'s>, SmtLib2Params>;
+
+/// Output produced by the SMT-LIB2 backend.
+///
+/// Contains a `declare-const` for each circuit variable and an `assert` for each constraint.
+pub type SmtLib2Output = String;
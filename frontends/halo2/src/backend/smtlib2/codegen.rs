@@ -0,0 +1,143 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+};
+
+use super::{
+    SmtLib2Output,
+    lowering::{FieldEncoding, SmtFunction, SmtFunctionRef, SmtLib2FunctionLowering},
+    params::SmtLib2Params,
+};
+use crate::{
+    backend::codegen::Codegen,
+    io::{AdviceIO, InstanceIO},
+    ir::expr::Felt,
+};
+use anyhow::{Result, anyhow, bail};
+
+pub struct SmtLib2Codegen<'s> {
+    params: &'s SmtLib2Params,
+    encoding: Cell<Option<FieldEncoding>>,
+    functions: RefCell<Vec<SmtFunctionRef>>,
+}
+
+impl SmtLib2Codegen<'_> {
+    fn encoding(&self) -> Result<FieldEncoding> {
+        self.encoding
+            .get()
+            .ok_or_else(|| anyhow!("Prime was not set!"))
+    }
+
+    fn add_function(
+        &self,
+        name: &str,
+        inputs: usize,
+        outputs: usize,
+        is_main: bool,
+    ) -> Result<SmtLib2FunctionLowering> {
+        let function = Rc::new(RefCell::new(SmtFunction::new(
+            name, inputs, outputs, is_main,
+        )));
+        self.functions.borrow_mut().push(function.clone());
+        Ok(SmtLib2FunctionLowering::new(function, self.encoding()?))
+    }
+
+    /// Returns the functions ordered such that every function is defined after the functions it
+    /// calls.
+    fn sorted_functions(&self) -> Result<Vec<SmtFunctionRef>> {
+        let functions = self.functions.borrow();
+        let by_name = functions
+            .iter()
+            .enumerate()
+            .map(|(idx, f)| (f.borrow().name().to_owned(), idx))
+            .collect::<HashMap<_, _>>();
+        let mut visited = vec![false; functions.len()];
+        let mut sorted = Vec::with_capacity(functions.len());
+
+        fn visit(
+            idx: usize,
+            functions: &[SmtFunctionRef],
+            by_name: &HashMap<String, usize>,
+            visited: &mut [bool],
+            sorted: &mut Vec<SmtFunctionRef>,
+        ) -> Result<()> {
+            if visited[idx] {
+                return Ok(());
+            }
+            visited[idx] = true;
+            for callee in functions[idx].borrow().callees() {
+                let Some(callee_idx) = by_name.get(callee) else {
+                    bail!("Call to undefined function {callee:?}");
+                };
+                visit(*callee_idx, functions, by_name, visited, sorted)?;
+            }
+            sorted.push(functions[idx].clone());
+            Ok(())
+        }
+
+        for idx in 0..functions.len() {
+            visit(idx, &functions, &by_name, &mut visited, &mut sorted)?;
+        }
+        Ok(sorted)
+    }
+}
+
+impl<'c: 's, 's> Codegen<'c, 's> for SmtLib2Codegen<'s> {
+    type FuncOutput = SmtLib2FunctionLowering;
+    type Output = SmtLib2Output;
+    type State = SmtLib2Params;
+
+    fn initialize(state: &'s Self::State) -> Self {
+        Self {
+            params: state,
+            encoding: Default::default(),
+            functions: Default::default(),
+        }
+    }
+
+    fn set_prime_field(&self, prime: Felt) -> Result<()> {
+        self.encoding.set(Some(FieldEncoding::new(
+            prime,
+            self.params.bitvector_mode(),
+        )));
+        Ok(())
+    }
+
+    fn define_main_function(
+        &self,
+        advice_io: &AdviceIO,
+        instance_io: &InstanceIO,
+    ) -> Result<Self::FuncOutput> {
+        self.add_function(
+            "Main",
+            instance_io.inputs().len() + advice_io.inputs().len(),
+            instance_io.outputs().len() + advice_io.outputs().len(),
+            true,
+        )
+    }
+
+    fn define_function(
+        &self,
+        name: &str,
+        inputs: usize,
+        outputs: usize,
+    ) -> Result<Self::FuncOutput> {
+        self.add_function(name, inputs, outputs, false)
+    }
+
+    fn generate_output(self) -> Result<Self::Output> {
+        let encoding = self.encoding()?;
+        let mut lines = vec!["(set-logic ALL)".to_owned()];
+        for function in self.sorted_functions()? {
+            let function = function.borrow();
+            if function.is_main() {
+                function.write_assertions(&encoding, &mut lines);
+            } else {
+                function.write_definition(&encoding, &mut lines);
+            }
+        }
+        lines.push("(check-sat)".to_owned());
+        Ok(lines.join("\n") + "\n")
+    }
+}
@@ -0,0 +1,583 @@
+use std::{cell::RefCell, collections::BTreeSet, rc::Rc};
+
+use crate::{
+    backend::{
+        func::FuncIO,
+        lowering::{ExprLowering, Lowering},
+    },
+    ir::{CmpOp, expr::Felt},
+};
+use anyhow::Result;
+
+/// Term in SMT-LIB2 syntax.
+pub type SmtTerm = String;
+
+/// Describes how field elements are encoded in SMT-LIB2.
+///
+/// Every operation is reduced modulo the prime, so the operands of any operation are always in
+/// the range `[0, prime)`. In bitvector mode the width is twice the bit size of the prime so that
+/// products of reduced values never overflow.
+#[derive(Clone, Copy, Debug)]
+pub struct FieldEncoding {
+    prime: Felt,
+    width: Option<u64>,
+}
+
+impl FieldEncoding {
+    pub fn new(prime: Felt, bitvector_mode: bool) -> Self {
+        Self {
+            prime,
+            width: bitvector_mode.then(|| 2 * prime.bits()),
+        }
+    }
+
+    /// Returns the sort used for field elements.
+    pub fn sort(&self) -> String {
+        match self.width {
+            Some(width) => format!("(_ BitVec {width})"),
+            None => "Int".to_owned(),
+        }
+    }
+
+    fn literal(&self, value: Felt) -> SmtTerm {
+        match self.width {
+            Some(width) => format!("(_ bv{value} {width})"),
+            None => value.to_string(),
+        }
+    }
+
+    fn reduce(&self, term: SmtTerm) -> SmtTerm {
+        let prime = self.literal(self.prime);
+        match self.width {
+            Some(_) => format!("(bvurem {term} {prime})"),
+            None => format!("(mod {term} {prime})"),
+        }
+    }
+
+    /// Returns the constant reduced modulo the prime.
+    pub fn constant(&self, value: Felt) -> SmtTerm {
+        self.literal(value % self.prime)
+    }
+
+    pub fn add(&self, lhs: &str, rhs: &str) -> SmtTerm {
+        self.reduce(match self.width {
+            Some(_) => format!("(bvadd {lhs} {rhs})"),
+            None => format!("(+ {lhs} {rhs})"),
+        })
+    }
+
+    pub fn mul(&self, lhs: &str, rhs: &str) -> SmtTerm {
+        self.reduce(match self.width {
+            Some(_) => format!("(bvmul {lhs} {rhs})"),
+            None => format!("(* {lhs} {rhs})"),
+        })
+    }
+
+    pub fn neg(&self, expr: &str) -> SmtTerm {
+        self.reduce(match self.width {
+            Some(_) => format!("(bvsub {} {expr})", self.literal(self.prime)),
+            None => format!("(- {expr})"),
+        })
+    }
+
+    pub fn cmp(&self, op: CmpOp, lhs: &str, rhs: &str) -> SmtTerm {
+        let op = match (op, self.width) {
+            (CmpOp::Eq, _) => "=",
+            (CmpOp::Ne, _) => "distinct",
+            (CmpOp::Lt, None) => "<",
+            (CmpOp::Le, None) => "<=",
+            (CmpOp::Gt, None) => ">",
+            (CmpOp::Ge, None) => ">=",
+            (CmpOp::Lt, Some(_)) => "bvult",
+            (CmpOp::Le, Some(_)) => "bvule",
+            (CmpOp::Gt, Some(_)) => "bvugt",
+            (CmpOp::Ge, Some(_)) => "bvuge",
+        };
+        format!("({op} {lhs} {rhs})")
+    }
+
+    /// Returns a term that asserts that the variable is a valid field element.
+    pub fn in_range(&self, var: &str) -> SmtTerm {
+        let prime = self.literal(self.prime);
+        match self.width {
+            Some(_) => format!("(bvult {var} {prime})"),
+            None => format!("(and (<= 0 {var}) (< {var} {prime}))"),
+        }
+    }
+}
+
+/// Returns the name of the variable that represents the IO.
+///
+/// Challenges are passed to the functions as inputs so they share the name of the input.
+pub fn var_name(func_io: FuncIO) -> String {
+    match func_io {
+        FuncIO::Arg(arg_no) => format!("in_{arg_no}"),
+        FuncIO::Field(field_id) => format!("out_{field_id}"),
+        FuncIO::Advice(adv) => format!("adv_{}_{}", adv.col(), adv.row()),
+        FuncIO::Fixed(fix) => format!("fix_{}_{}", fix.col(), fix.row()),
+        FuncIO::TableLookup(id, col, row, idx, ridx) => {
+            format!("lkp{id}_{col}_{row}_{idx}_{ridx}")
+        }
+        FuncIO::CallOutput(call, out) => format!("cout_{call}_{out}"),
+        FuncIO::Temp(temp) => format!("t{}", *temp),
+        FuncIO::Challenge(_, _, arg_no) => format!("in_{arg_no}"),
+    }
+}
+
+/// Returns the name as a valid SMT-LIB2 symbol, quoting it if necessary.
+pub fn symbol(name: &str) -> String {
+    let is_simple = |c: char| c.is_ascii_alphanumeric() || "~!@$%^&*_-+=<>.?/".contains(c);
+    if !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(is_simple)
+    {
+        name.to_owned()
+    } else {
+        format!("|{}|", name.replace(['|', '\\'], "_"))
+    }
+}
+
+/// Line in the body of a function.
+#[derive(Clone, Debug)]
+pub enum SmtItem {
+    Comment(String),
+    Assert(SmtTerm),
+}
+
+/// Contents of a function generated by the backend.
+#[derive(Debug)]
+pub struct SmtFunction {
+    name: String,
+    inputs: usize,
+    outputs: usize,
+    is_main: bool,
+    vars: BTreeSet<FuncIO>,
+    body: Vec<SmtItem>,
+    callees: Vec<String>,
+}
+
+impl SmtFunction {
+    pub fn new(name: &str, inputs: usize, outputs: usize, is_main: bool) -> Self {
+        Self {
+            name: name.to_owned(),
+            inputs,
+            outputs,
+            is_main,
+            vars: Default::default(),
+            body: Default::default(),
+            callees: Default::default(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn is_main(&self) -> bool {
+        self.is_main
+    }
+
+    /// Returns the names of the functions this function calls.
+    pub fn callees(&self) -> &[String] {
+        &self.callees
+    }
+
+    fn params(&self) -> impl Iterator<Item = FuncIO> {
+        (0..self.inputs)
+            .map(FuncIO::input)
+            .chain((0..self.outputs).map(FuncIO::output))
+    }
+
+    fn locals(&self) -> BTreeSet<String> {
+        let params = self.params().map(var_name).collect::<BTreeSet<_>>();
+        self.vars
+            .iter()
+            .copied()
+            .map(var_name)
+            .filter(|name| !params.contains(name))
+            .collect()
+    }
+
+    fn asserts_len(&self) -> usize {
+        self.body
+            .iter()
+            .filter(|item| matches!(item, SmtItem::Assert(_)))
+            .count()
+    }
+
+    /// Writes the function as a `define-fun` that holds if its body holds for some value of the
+    /// local variables.
+    pub fn write_definition(&self, encoding: &FieldEncoding, out: &mut Vec<String>) {
+        let sort = encoding.sort();
+        let params = self
+            .params()
+            .map(|param| format!("({} {sort})", var_name(param)))
+            .collect::<Vec<_>>()
+            .join(" ");
+        out.push(format!(
+            "(define-fun {} ({params}) Bool",
+            symbol(&self.name)
+        ));
+        let locals = self.locals();
+        let mut conjuncts = locals
+            .iter()
+            .map(|local| SmtItem::Assert(encoding.in_range(local)))
+            .chain(self.body.iter().cloned())
+            .collect::<Vec<_>>();
+        let count = locals.len() + self.asserts_len();
+        if count == 0 {
+            conjuncts.push(SmtItem::Assert("true".to_owned()));
+        }
+        let mut close = ")".to_owned();
+        if !locals.is_empty() {
+            let bindings = locals
+                .iter()
+                .map(|local| format!("({local} {sort})"))
+                .collect::<Vec<_>>()
+                .join(" ");
+            out.push(format!("  (exists ({bindings})"));
+            close.push(')');
+        }
+        if count > 1 {
+            out.push("  (and".to_owned());
+            close.push(')');
+        }
+        for item in conjuncts {
+            match item {
+                SmtItem::Comment(comment) => out.push(format!("    ; {comment}")),
+                SmtItem::Assert(term) => out.push(format!("    {term}")),
+            }
+        }
+        out.push(format!("  {close}"));
+    }
+
+    /// Writes the function as a series of top-level declarations and assertions.
+    pub fn write_assertions(&self, encoding: &FieldEncoding, out: &mut Vec<String>) {
+        let sort = encoding.sort();
+        out.push(format!("; {}", self.name));
+        let vars = self
+            .params()
+            .chain(self.vars.iter().copied())
+            .map(var_name)
+            .collect::<BTreeSet<_>>();
+        for var in &vars {
+            out.push(format!("(declare-const {var} {sort})"));
+        }
+        for var in &vars {
+            out.push(format!("(assert {})", encoding.in_range(var)));
+        }
+        for item in &self.body {
+            match item {
+                SmtItem::Comment(comment) => out.push(format!("; {comment}")),
+                SmtItem::Assert(term) => out.push(format!("(assert {term})")),
+            }
+        }
+    }
+}
+
+pub type SmtFunctionRef = Rc<RefCell<SmtFunction>>;
+
+#[derive(Clone, Debug)]
+pub struct SmtLib2FunctionLowering {
+    function: SmtFunctionRef,
+    encoding: FieldEncoding,
+}
+
+impl SmtLib2FunctionLowering {
+    pub fn new(function: SmtFunctionRef, encoding: FieldEncoding) -> Self {
+        Self { function, encoding }
+    }
+
+    fn push(&self, item: SmtItem) {
+        self.function.borrow_mut().body.push(item);
+    }
+
+    fn declare(&self, func_io: FuncIO) -> SmtTerm {
+        self.function.borrow_mut().vars.insert(func_io);
+        var_name(func_io)
+    }
+}
+
+impl Lowering for SmtLib2FunctionLowering {
+    fn generate_constraint(
+        &self,
+        op: CmpOp,
+        lhs: &Self::CellOutput,
+        rhs: &Self::CellOutput,
+    ) -> Result<()> {
+        self.push(SmtItem::Assert(self.encoding.cmp(op, lhs, rhs)));
+        Ok(())
+    }
+
+    fn num_constraints(&self) -> usize {
+        self.function.borrow().asserts_len()
+    }
+
+    fn generate_comment(&self, s: String) -> Result<()> {
+        for line in s.lines() {
+            self.push(SmtItem::Comment(line.to_owned()));
+        }
+        Ok(())
+    }
+
+    fn generate_assume_deterministic(&self, func_io: FuncIO) -> Result<()> {
+        // Determinism is a property of the whole circuit and cannot be assumed in SMT-LIB2.
+        let var = self.declare(func_io);
+        self.push(SmtItem::Comment(format!("assume-deterministic {var}")));
+        Ok(())
+    }
+
+    fn generate_call(
+        &self,
+        name: &str,
+        selectors: &[Self::CellOutput],
+        outputs: &[FuncIO],
+    ) -> Result<()> {
+        let args = selectors
+            .iter()
+            .cloned()
+            .chain(outputs.iter().map(|output| self.declare(*output)))
+            .collect::<Vec<_>>();
+        let callee = symbol(name);
+        self.push(SmtItem::Assert(if args.is_empty() {
+            callee
+        } else {
+            format!("({callee} {})", args.join(" "))
+        }));
+        self.function.borrow_mut().callees.push(name.to_owned());
+        Ok(())
+    }
+
+    fn generate_assert(&self, expr: &Self::CellOutput) -> Result<()> {
+        self.push(SmtItem::Assert(expr.clone()));
+        Ok(())
+    }
+
+    fn generate_post_condition(&self, expr: &Self::CellOutput) -> Result<()> {
+        // Post conditions have to be proven by the solver so they are not asserted.
+        self.push(SmtItem::Comment(format!("post-condition {expr}")));
+        Ok(())
+    }
+}
+
+impl ExprLowering for SmtLib2FunctionLowering {
+    type CellOutput = SmtTerm;
+
+    fn lower_sum(
+        &self,
+        lhs: &Self::CellOutput,
+        rhs: &Self::CellOutput,
+    ) -> Result<Self::CellOutput> {
+        Ok(self.encoding.add(lhs, rhs))
+    }
+
+    fn lower_product(
+        &self,
+        lhs: &Self::CellOutput,
+        rhs: &Self::CellOutput,
+    ) -> Result<Self::CellOutput> {
+        Ok(self.encoding.mul(lhs, rhs))
+    }
+
+    fn lower_neg(&self, expr: &Self::CellOutput) -> Result<Self::CellOutput> {
+        Ok(self.encoding.neg(expr))
+    }
+
+    fn lower_constant(&self, f: Felt) -> Result<Self::CellOutput> {
+        Ok(self.encoding.constant(f))
+    }
+
+    fn lower_eq(&self, lhs: &Self::CellOutput, rhs: &Self::CellOutput) -> Result<Self::CellOutput> {
+        Ok(self.encoding.cmp(CmpOp::Eq, lhs, rhs))
+    }
+
+    fn lower_lt(&self, lhs: &Self::CellOutput, rhs: &Self::CellOutput) -> Result<Self::CellOutput> {
+        Ok(self.encoding.cmp(CmpOp::Lt, lhs, rhs))
+    }
+
+    fn lower_le(&self, lhs: &Self::CellOutput, rhs: &Self::CellOutput) -> Result<Self::CellOutput> {
+        Ok(self.encoding.cmp(CmpOp::Le, lhs, rhs))
+    }
+
+    fn lower_gt(&self, lhs: &Self::CellOutput, rhs: &Self::CellOutput) -> Result<Self::CellOutput> {
+        Ok(self.encoding.cmp(CmpOp::Gt, lhs, rhs))
+    }
+
+    fn lower_ge(&self, lhs: &Self::CellOutput, rhs: &Self::CellOutput) -> Result<Self::CellOutput> {
+        Ok(self.encoding.cmp(CmpOp::Ge, lhs, rhs))
+    }
+
+    fn lower_ne(&self, lhs: &Self::CellOutput, rhs: &Self::CellOutput) -> Result<Self::CellOutput> {
+        Ok(self.encoding.cmp(CmpOp::Ne, lhs, rhs))
+    }
+
+    fn lower_and(
+        &self,
+        lhs: &Self::CellOutput,
+        rhs: &Self::CellOutput,
+    ) -> Result<Self::CellOutput> {
+        Ok(format!("(and {lhs} {rhs})"))
+    }
+
+    fn lower_or(&self, lhs: &Self::CellOutput, rhs: &Self::CellOutput) -> Result<Self::CellOutput> {
+        Ok(format!("(or {lhs} {rhs})"))
+    }
+
+    fn lower_not(&self, value: &Self::CellOutput) -> Result<Self::CellOutput> {
+        Ok(format!("(not {value})"))
+    }
+
+    fn lower_true(&self) -> Result<Self::CellOutput> {
+        Ok("true".to_owned())
+    }
+
+    fn lower_false(&self) -> Result<Self::CellOutput> {
+        Ok("false".to_owned())
+    }
+
+    fn lower_det(&self, _expr: &Self::CellOutput) -> Result<Self::CellOutput> {
+        // Determinism is a property checked by Picus and has no meaning as an assertion.
+        Ok("true".to_owned())
+    }
+
+    fn lower_implies(
+        &self,
+        lhs: &Self::CellOutput,
+        rhs: &Self::CellOutput,
+    ) -> Result<Self::CellOutput> {
+        Ok(format!("(=> {lhs} {rhs})"))
+    }
+
+    fn lower_iff(
+        &self,
+        lhs: &Self::CellOutput,
+        rhs: &Self::CellOutput,
+    ) -> Result<Self::CellOutput> {
+        Ok(format!("(= {lhs} {rhs})"))
+    }
+
+    fn lower_function_input(&self, i: usize) -> FuncIO {
+        FuncIO::input(i)
+    }
+
+    fn lower_function_output(&self, o: usize) -> FuncIO {
+        FuncIO::output(o)
+    }
+
+    fn lower_funcio<IO>(&self, io: IO) -> Result<Self::CellOutput>
+    where
+        IO: Into<FuncIO>,
+    {
+        Ok(self.declare(io.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lowering(bitvector_mode: bool) -> SmtLib2FunctionLowering {
+        let function = Rc::new(RefCell::new(SmtFunction::new("f", 1, 1, false)));
+        SmtLib2FunctionLowering::new(
+            function,
+            FieldEncoding::new(Felt::from(7usize), bitvector_mode),
+        )
+    }
+
+    #[test]
+    fn int_encoding() {
+        let l = lowering(false);
+        let lhs = l.lower_funcio(FuncIO::input(0)).unwrap();
+        let rhs = l.lower_constant(Felt::from(9usize)).unwrap();
+        let sum = l.lower_sum(&lhs, &rhs).unwrap();
+        assert_eq!(sum, "(mod (+ in_0 2) 7)");
+        l.generate_constraint(CmpOp::Eq, &sum, &l.lower_funcio(FuncIO::output(0)).unwrap())
+            .unwrap();
+        assert_eq!(l.num_constraints(), 1);
+    }
+
+    #[test]
+    fn range_constraints_are_two_comparisons() {
+        let l = lowering(false);
+        let val = l.lower_funcio(FuncIO::input(0)).unwrap();
+        let low = l.lower_constant(Felt::from(1usize)).unwrap();
+        let high = l.lower_constant(Felt::from(5usize)).unwrap();
+        l.generate_range_constraint(&val, &low, &high).unwrap();
+        assert_eq!(l.num_constraints(), 2);
+        let mut out = vec![];
+        l.function.borrow().write_definition(&l.encoding, &mut out);
+        similar_asserts::assert_eq!(
+            out.join("\n"),
+            "(define-fun f ((in_0 Int) (out_0 Int)) Bool
+  (and
+    (>= in_0 1)
+    (<= in_0 5)
+  ))"
+        );
+    }
+
+    #[test]
+    fn assume_deterministic_many_comments_every_var() {
+        let l = lowering(false);
+        l.generate_assume_deterministic_many(&[FuncIO::input(0), FuncIO::output(0)])
+            .unwrap();
+        assert_eq!(l.num_constraints(), 0);
+        let mut out = vec![];
+        l.function.borrow().write_definition(&l.encoding, &mut out);
+        similar_asserts::assert_eq!(
+            out.join("\n"),
+            "(define-fun f ((in_0 Int) (out_0 Int)) Bool
+    ; assume-deterministic in_0
+    ; assume-deterministic out_0
+    true
+  )"
+        );
+    }
+
+    #[test]
+    fn bitvector_encoding() {
+        let l = lowering(true);
+        let val = l.lower_funcio(FuncIO::input(0)).unwrap();
+        assert_eq!(
+            l.lower_neg(&val).unwrap(),
+            "(bvurem (bvsub (_ bv7 6) in_0) (_ bv7 6))"
+        );
+        assert_eq!(l.encoding.sort(), "(_ BitVec 6)");
+    }
+
+    #[test]
+    fn bitwise_operations_are_unsupported() {
+        let l = lowering(false);
+        let val = l.lower_funcio(FuncIO::input(0)).unwrap();
+        assert!(l.lower_xor(&val, &val).is_err());
+        assert!(l.lower_shl(&val, 1).is_err());
+        assert!(l.lower_shr(&val, 1).is_err());
+    }
+
+    #[test]
+    fn conditional_expressions_are_unsupported() {
+        let l = lowering(false);
+        let cond = l.lower_true().unwrap();
+        let val = l.lower_funcio(FuncIO::input(0)).unwrap();
+        assert!(l.lower_conditional(&cond, &val, &val).is_err());
+    }
+
+    #[test]
+    fn definition_quantifies_locals() {
+        let l = lowering(false);
+        let output = l.lower_funcio(FuncIO::output(0)).unwrap();
+        let temp = l.lower_funcio(FuncIO::advice_abs(0, 1)).unwrap();
+        l.generate_constraint(CmpOp::Eq, &output, &temp).unwrap();
+        let mut out = vec![];
+        l.function.borrow().write_definition(&l.encoding, &mut out);
+        similar_asserts::assert_eq!(
+            out.join("\n"),
+            "(define-fun f ((in_0 Int) (out_0 Int)) Bool
+  (exists ((adv_0_1 Int))
+  (and
+    (and (<= 0 adv_0_1) (< adv_0_1 7))
+    (= out_0 adv_0_1)
+  )))"
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use crate::backend::codegen::CodegenParams;
+
+/// Configuration for the SMT-LIB2 backend.
+#[derive(Clone, Debug, Default)]
+pub struct SmtLib2Params {
+    inline: bool,
+    bitvector_mode: bool,
+}
+
+impl SmtLib2Params {
+    /// Returns wether inlining is enabled or not.
+    pub fn inline(&self) -> bool {
+        self.inline
+    }
+
+    /// Returns true if field elements are encoded as bitvectors instead of integers.
+    pub fn bitvector_mode(&self) -> bool {
+        self.bitvector_mode
+    }
+}
+
+impl CodegenParams for SmtLib2Params {
+    fn inlining_enabled(&self) -> bool {
+        self.inline
+    }
+}
+
+/// Builder for creating [`SmtLib2Params`] instances.
+#[derive(Debug, Default)]
+pub struct SmtLib2ParamsBuilder(SmtLib2Params);
+
+impl SmtLib2ParamsBuilder {
+    /// Creates a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets lowering to inlining everything into the main assertions.
+    pub fn inline(&mut self) -> &mut Self {
+        self.0.inline = true;
+        self
+    }
+
+    /// Sets lowering to creating a separate function for each group.
+    pub fn no_inline(&mut self) -> &mut Self {
+        self.0.inline = false;
+        self
+    }
+
+    /// Encodes field elements as bitvectors using the BV theory.
+    pub fn bitvector_mode(&mut self) -> &mut Self {
+        self.0.bitvector_mode = true;
+        self
+    }
+
+    /// Encodes field elements as integers using the Int theory.
+    pub fn no_bitvector_mode(&mut self) -> &mut Self {
+        self.0.bitvector_mode = false;
+        self
+    }
+
+    /// Completes the build process and returns the parameters.
+    pub fn build(&mut self) -> SmtLib2Params {
+        std::mem::take(&mut self.0)
+    }
+}
+
+impl From<SmtLib2ParamsBuilder> for SmtLib2Params {
+    fn from(builder: SmtLib2ParamsBuilder) -> Self {
+        builder.0
+    }
+}
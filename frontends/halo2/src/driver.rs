@@ -1,11 +1,18 @@
 //! Driver struct for handling synthesis and lowering.
 
-use std::collections::HashMap;
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt,
+    hash::{DefaultHasher, Hash, Hasher as _},
+};
 
 #[cfg(feature = "llzk-backend")]
 use crate::backend::llzk::{LlzkBackend, LlzkOutput, LlzkParams};
 #[cfg(feature = "picus-backend")]
 use crate::backend::picus::{PicusBackend, PicusOutput, PicusParams};
+#[cfg(feature = "smtlib2-backend")]
+use crate::backend::smtlib2::{SmtLib2Backend, SmtLib2Output, SmtLib2Params};
 use crate::{
     CircuitSynthesis,
     io::{AdviceIO, InstanceIO},
@@ -13,7 +20,7 @@ use crate::{
         IRCtx, ResolvedIRCircuit, UnresolvedIRCircuit,
         generate::{IRGenParams, generate_ir},
     },
-    synthesis::{SynthesizedCircuit, Synthesizer},
+    synthesis::{SynthesizedCircuit, Synthesizer, errors::SynthesisErrors, regions::RegionData},
 };
 use ff::PrimeField;
 use halo2_frontend_core::{
@@ -26,10 +33,91 @@ use halo2_frontend_core::{
 pub struct Driver {
     ir_ctxs: HashMap<usize, IRCtx>,
     id_count: usize,
+    cache: Option<SynthesisCache>,
+    strict_synthesis: bool,
+}
+
+/// Result of synthesizing a circuit of type `C`.
+type CircuitSynthesisOf<F, C> =
+    SynthesizedCircuit<F, <<C as CircuitSynthesis<F>>::CS as ConstraintSystemInfo<F>>::Polynomial>;
+
+/// Results of previous synthesis runs keyed by a hash of the circuit type and a caller supplied
+/// key.
+#[derive(Default)]
+struct SynthesisCache(HashMap<u64, Box<dyn Any>>);
+
+impl fmt::Debug for SynthesisCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.0.keys()).finish()
+    }
 }
 
 impl Driver {
+    /// Creates a driver that caches the results of [`Driver::synthesize_cached`].
+    pub fn with_cache() -> Self {
+        Self {
+            cache: Some(Default::default()),
+            ..Default::default()
+        }
+    }
+
+    /// Makes the synthesis fail if the circuit assigns the same advice cell twice inside a region,
+    /// assigns different values to the same fixed cell or if two regions use the same cells.
+    ///
+    /// By default the reassignments only emit a warning and overlaps are not checked.
+    pub fn with_strict_synthesis(mut self) -> Self {
+        self.strict_synthesis = true;
+        self
+    }
+
+    /// Synthesizes a circuit reusing a previous result if the cache is enabled and a circuit of
+    /// the same type was synthesized before with the same key.
+    ///
+    /// The cache can't inspect the circuit, so the key identifies the circuit among the circuits
+    /// of its type. It must capture everything that affects the synthesis, such as the parameters
+    /// of the circuit and a version that changes whenever its implementation changes. Two
+    /// circuits of the same type synthesized with the same key are assumed to yield the same
+    /// synthesis. If the cache is not enabled behaves like [`Driver::synthesize`].
+    pub fn synthesize_cached<F, C, K>(
+        &mut self,
+        circuit: &C,
+        key: K,
+    ) -> anyhow::Result<CircuitSynthesisOf<F, C>>
+    where
+        C: CircuitSynthesis<F> + 'static,
+        <C::CS as ConstraintSystemInfo<F>>::Polynomial: Clone + 'static,
+        F: PrimeField,
+        K: Hash,
+    {
+        let mut hasher = DefaultHasher::new();
+        TypeId::of::<C>().hash(&mut hasher);
+        key.hash(&mut hasher);
+        let key = hasher.finish();
+
+        if let Some(hit) = self
+            .cache
+            .as_ref()
+            .and_then(|cache| cache.0.get(&key))
+            .and_then(|entry| entry.downcast_ref::<CircuitSynthesisOf<F, C>>())
+        {
+            log::debug!(
+                "Reusing cached synthesis for {}",
+                std::any::type_name::<C>()
+            );
+            return Ok(hit.clone());
+        }
+
+        let synthesized = self.synthesize(circuit)?;
+        if let Some(cache) = &mut self.cache {
+            cache.0.insert(key, Box::new(synthesized.clone()));
+        }
+        Ok(synthesized)
+    }
+
     /// Synthesizes a circuit .
+    ///
+    /// Since the synthesis consumes the configuration, a copy of it is kept for
+    /// [`CircuitSynthesis::validate`].
     pub fn synthesize<F, C>(
         &mut self,
         circuit: &C,
@@ -39,18 +127,81 @@ impl Driver {
         F: PrimeField,
     {
         let mut cs = C::CS::default();
-        let mut syn = Synthesizer::new(self.next_id());
         let config = C::configure(&mut cs);
+        let (advice_io, instance_io) = Self::io_hints::<F, C>(&config)?;
+        self.synthesize_configured(circuit, cs, config.clone(), &config, advice_io, instance_io)
+    }
 
+    /// Synthesizes a batch of circuits that share the same configuration.
+    ///
+    /// The constraint system is configured once and a copy of it is used for synthesizing each
+    /// circuit.
+    pub fn synthesize_batch<F, C>(
+        &mut self,
+        circuits: &[C],
+    ) -> anyhow::Result<Vec<CircuitSynthesisOf<F, C>>>
+    where
+        C: CircuitSynthesis<F>,
+        C::CS: Clone,
+        F: PrimeField,
+    {
+        let mut cs = C::CS::default();
+        let config = C::configure(&mut cs);
+        let (advice_io, instance_io) = Self::io_hints::<F, C>(&config)?;
+        circuits
+            .iter()
+            .map(|circuit| {
+                self.synthesize_configured(
+                    circuit,
+                    cs.clone(),
+                    config.clone(),
+                    &config,
+                    advice_io.clone(),
+                    instance_io.clone(),
+                )
+            })
+            .collect()
+    }
+
+    fn io_hints<F, C>(config: &C::Config) -> anyhow::Result<(AdviceIO, InstanceIO)>
+    where
+        C: CircuitSynthesis<F>,
+        F: PrimeField,
+    {
         log::debug!("Validating io hints");
-        let advice_io: AdviceIO = C::advice_io(&config)?;
-        let instance_io: InstanceIO = C::instance_io(&config)?;
+        Ok((C::advice_io(config)?, C::instance_io(config)?))
+    }
 
+    fn synthesize_configured<F, C>(
+        &mut self,
+        circuit: &C,
+        mut cs: C::CS,
+        config: C::Config,
+        validation_config: &C::Config,
+        advice_io: AdviceIO,
+        instance_io: InstanceIO,
+    ) -> anyhow::Result<CircuitSynthesisOf<F, C>>
+    where
+        C: CircuitSynthesis<F>,
+        F: PrimeField,
+    {
+        let mut syn = Synthesizer::new(self.next_id());
+        syn.set_strict_mode(self.strict_synthesis);
         syn.configure_io(advice_io, instance_io);
         log::debug!("Starting synthesis");
         C::synthesize(circuit.circuit(), config, &mut syn, &cs)?;
         cs.synthesis_completed();
         let synthesized = syn.build(cs)?;
+        if self.strict_synthesis {
+            let overlaps = synthesized.check_region_overlaps();
+            if !overlaps.is_empty() {
+                return Err(
+                    SynthesisErrors::new(overlaps.into_iter().map(Into::into).collect()).into(),
+                );
+            }
+        }
+        log::debug!("Validating synthesis");
+        C::validate(validation_config, &synthesized)?;
         log::debug!("Synthesis completed successfuly");
         Ok(synthesized)
     }
@@ -61,6 +212,43 @@ impl Driver {
         syn: &'syn SynthesizedCircuit<F, E>,
         params: IRGenParams<'cb, '_, F, E>,
     ) -> anyhow::Result<UnresolvedIRCircuit<'drv, 'syn, 'sco, F, E>>
+    where
+        F: PrimeField,
+        E: Clone + ExprBuilder<F> + ExpressionInfo + EvaluableExpr<F> + std::fmt::Debug,
+        'syn: 'sco,
+        'drv: 'sco + 'syn,
+        'cb: 'sco + 'syn,
+    {
+        self.generate_ir_impl(syn, params, None)
+    }
+
+    /// Generates the IR of the synthesized circuit only for the regions that pass the filter.
+    ///
+    /// Regions that do not pass the filter are still part of their groups, but their gates and
+    /// lookups are not lowered. This keeps the indices of the groups and regions consistent with
+    /// the full circuit.
+    pub fn generate_ir_filtered<'syn, 'drv, 'cb, 'sco, F, E>(
+        &'drv mut self,
+        syn: &'syn SynthesizedCircuit<F, E>,
+        params: IRGenParams<'cb, '_, F, E>,
+        filter: impl Fn(&RegionData) -> bool,
+    ) -> anyhow::Result<UnresolvedIRCircuit<'drv, 'syn, 'sco, F, E>>
+    where
+        F: PrimeField,
+        E: Clone + ExprBuilder<F> + ExpressionInfo + EvaluableExpr<F> + std::fmt::Debug,
+        'syn: 'sco,
+        'drv: 'sco + 'syn,
+        'cb: 'sco + 'syn,
+    {
+        self.generate_ir_impl(syn, params, Some(&filter))
+    }
+
+    fn generate_ir_impl<'syn, 'drv, 'cb, 'sco, F, E>(
+        &'drv mut self,
+        syn: &'syn SynthesizedCircuit<F, E>,
+        params: IRGenParams<'cb, '_, F, E>,
+        region_filter: Option<&dyn Fn(&RegionData) -> bool>,
+    ) -> anyhow::Result<UnresolvedIRCircuit<'drv, 'syn, 'sco, F, E>>
     where
         F: PrimeField,
         E: Clone + ExprBuilder<F> + ExpressionInfo + EvaluableExpr<F> + std::fmt::Debug,
@@ -69,7 +257,7 @@ impl Driver {
         'cb: 'sco + 'syn,
     {
         let ctx = self.get_or_create_ir_ctx(syn);
-        let ir = generate_ir(syn, params, ctx)?;
+        let ir = generate_ir(syn, params, ctx, region_filter)?;
         let enumerated_groups = syn.groups().iter().enumerate().collect::<Vec<_>>();
         let mut regions_to_groups = vec![];
 
@@ -115,6 +303,28 @@ impl Driver {
         PicusBackend::initialize(params).codegen(ir, ir.ctx())
     }
 
+    /// Estimates the size of the picus program without generating it.
+    #[cfg(feature = "picus-backend")]
+    pub fn picus_estimate(
+        &self,
+        ir: &ResolvedIRCircuit,
+        params: PicusParams,
+    ) -> crate::BackendEstimate {
+        PicusBackend::initialize(params).estimate(ir)
+    }
+
+    /// Creates a picus program from the circuit synthesis and calls the callback at the start of
+    /// each group.
+    #[cfg(feature = "picus-backend")]
+    pub fn picus_with_progress(
+        &self,
+        ir: &ResolvedIRCircuit,
+        params: PicusParams,
+        callback: impl Fn(crate::CodegenProgress),
+    ) -> anyhow::Result<PicusOutput> {
+        PicusBackend::initialize(params).codegen_with_progress(ir, ir.ctx(), callback)
+    }
+
     /// Creates a llzk module from the circuit synthesis.
     #[cfg(feature = "llzk-backend")]
     pub fn llzk<'c>(
@@ -125,9 +335,148 @@ impl Driver {
         LlzkBackend::initialize(params).codegen(ir, ir.ctx())
     }
 
+    /// Estimates the size of the llzk module without generating it.
+    #[cfg(feature = "llzk-backend")]
+    pub fn llzk_estimate(
+        &self,
+        ir: &ResolvedIRCircuit,
+        params: LlzkParams,
+    ) -> crate::BackendEstimate {
+        LlzkBackend::initialize(params).estimate(ir)
+    }
+
+    /// Creates a llzk module from the circuit synthesis and calls the callback at the start of
+    /// each group.
+    #[cfg(feature = "llzk-backend")]
+    pub fn llzk_with_progress<'c>(
+        &self,
+        ir: &ResolvedIRCircuit,
+        params: LlzkParams<'c>,
+        callback: impl Fn(crate::CodegenProgress),
+    ) -> anyhow::Result<LlzkOutput<'c>> {
+        LlzkBackend::initialize(params).codegen_with_progress(ir, ir.ctx(), callback)
+    }
+
+    /// Creates a SMT-LIB2 script from the circuit synthesis.
+    #[cfg(feature = "smtlib2-backend")]
+    pub fn smtlib2(
+        &self,
+        ir: &ResolvedIRCircuit,
+        params: SmtLib2Params,
+    ) -> anyhow::Result<SmtLib2Output> {
+        SmtLib2Backend::initialize(params).codegen(ir, ir.ctx())
+    }
+
+    /// Estimates the size of the SMT-LIB2 script without generating it.
+    #[cfg(feature = "smtlib2-backend")]
+    pub fn smtlib2_estimate(
+        &self,
+        ir: &ResolvedIRCircuit,
+        params: SmtLib2Params,
+    ) -> crate::BackendEstimate {
+        SmtLib2Backend::initialize(params).estimate(ir)
+    }
+
+    /// Creates a SMT-LIB2 script from the circuit synthesis and calls the callback at the start
+    /// of each group.
+    #[cfg(feature = "smtlib2-backend")]
+    pub fn smtlib2_with_progress(
+        &self,
+        ir: &ResolvedIRCircuit,
+        params: SmtLib2Params,
+        callback: impl Fn(crate::CodegenProgress),
+    ) -> anyhow::Result<SmtLib2Output> {
+        SmtLib2Backend::initialize(params).codegen_with_progress(ir, ir.ctx(), callback)
+    }
+
     fn next_id(&mut self) -> usize {
         let id = self.id_count;
         self.id_count += 1;
         id
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CircuitIO;
+    use halo2_midnight_integration::{plonk::ConstraintSystem, synthesizer::SynthesizerAssignment};
+    use halo2_proofs::plonk::{Circuit as _, Error};
+    use halo2_test_circuits::mul::{MulCircuit, MulConfig};
+    use halo2curves::bn256::Fr;
+
+    thread_local! {
+        /// Number of times [`MulSynthesis`] was configured in the current thread.
+        static CONFIGURE_CALLS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+    }
+
+    #[derive(Default)]
+    struct MulSynthesis(MulCircuit<Fr>);
+
+    impl CircuitSynthesis<Fr> for MulSynthesis {
+        type Circuit = MulCircuit<Fr>;
+        type Config = MulConfig;
+        type CS = ConstraintSystem<Fr>;
+        type Error = Error;
+
+        fn circuit(&self) -> &Self::Circuit {
+            &self.0
+        }
+
+        fn configure(cs: &mut Self::CS) -> Self::Config {
+            CONFIGURE_CALLS.with(|calls| calls.set(calls.get() + 1));
+            MulCircuit::<Fr>::configure(cs.inner_mut())
+        }
+
+        fn advice_io(_: &Self::Config) -> anyhow::Result<AdviceIO> {
+            Ok(CircuitIO::empty())
+        }
+
+        fn instance_io(_: &Self::Config) -> anyhow::Result<InstanceIO> {
+            Ok(CircuitIO::empty())
+        }
+
+        fn synthesize(
+            circuit: &Self::Circuit,
+            config: Self::Config,
+            synthesizer: &mut Synthesizer<Fr>,
+            cs: &Self::CS,
+        ) -> Result<(), Self::Error> {
+            SynthesizerAssignment::synthesize(circuit, config, synthesizer, cs)
+        }
+    }
+
+    #[test]
+    fn cached_synthesis_is_keyed_per_instance() {
+        let mut driver = Driver::with_cache();
+        let first = MulSynthesis::default();
+        let second = MulSynthesis::default();
+
+        let a = driver.synthesize_cached(&first, ("first", 1)).unwrap();
+        let b = driver.synthesize_cached(&first, ("first", 1)).unwrap();
+        assert_eq!(a.id(), b.id());
+
+        let c = driver.synthesize_cached(&second, ("second", 1)).unwrap();
+        assert_ne!(a.id(), c.id());
+
+        let d = driver.synthesize_cached(&first, ("first", 2)).unwrap();
+        assert_ne!(a.id(), d.id());
+    }
+
+    #[test]
+    fn synthesis_configures_the_circuit_once() {
+        let mut driver = Driver::default();
+        CONFIGURE_CALLS.with(|calls| calls.set(0));
+        driver.synthesize(&MulSynthesis::default()).unwrap();
+        assert_eq!(CONFIGURE_CALLS.with(|calls| calls.get()), 1);
+    }
+
+    #[test]
+    fn synthesis_cache_is_opt_in() {
+        let mut driver = Driver::default();
+        let circuit = MulSynthesis::default();
+        let a = driver.synthesize_cached(&circuit, 0).unwrap();
+        let b = driver.synthesize_cached(&circuit, 0).unwrap();
+        assert_ne!(a.id(), b.id());
+    }
+}
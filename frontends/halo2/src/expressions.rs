@@ -1,18 +1,26 @@
 //! Traits and types related to expressions.
 
-use std::borrow::Cow;
+use std::{borrow::Cow, cell::RefCell, collections::HashMap};
 
 use crate::{
+    backend::func::FuncIO,
     resolvers::{
-        ChallengeResolver, FixedQueryResolver, QueryResolver, ResolversProvider, SelectorResolver,
-        boxed_resolver,
+        ChallengeResolver, FixedQueryResolver, QueryResolver, ResolvedQuery, ResolvedSelector,
+        ResolversProvider, SelectorResolver, boxed_resolver,
     },
     synthesis::regions::{RegionData, RegionRow},
 };
 
 pub(crate) mod constant_folding;
 
+use anyhow::Result;
 use ff::Field;
+use halo2_frontend_core::{
+    expressions::{EvalExpression, EvaluableExpr, ExpressionTypes},
+    info_traits::{ChallengeInfo, QueryInfo, SelectorInfo},
+    query::{Advice, Fixed, Instance},
+    table::Rotation,
+};
 
 /// Indicates to the driver that the expression should be scoped in that row of the circuit.
 ///
@@ -135,6 +143,184 @@ where
     pub(crate) fn challenge_resolver(&self) -> &dyn ChallengeResolver {
         self.resolvers.challenge_resolver()
     }
+
+    /// Returns a version of the expression that is not tied to the lifetimes of the synthesis.
+    ///
+    /// The expression is cloned if it was borrowed and every selector, query and challenge in it
+    /// is resolved eagerly with the current resolvers. The results, including the errors, are
+    /// stored in the new scope. This traverses the whole expression and allocates, so it should
+    /// only be used when the expression needs to outlive the synthesis.
+    pub fn into_owned(self) -> ScopedExpression<'static, 'static, F, E>
+    where
+        E: EvaluableExpr<F> + 'static,
+    {
+        let snapshot = ResolversSnapshot::record(self.expression.as_ref(), self.resolvers());
+        ScopedExpression::new(self.expression.into_owned(), snapshot)
+    }
+}
+
+type Recorded<T> = std::result::Result<T, String>;
+
+/// Resolvers that return the resolutions recorded from other resolvers.
+///
+/// Used for detaching a [`ScopedExpression`] from the lifetime of its original resolvers.
+#[derive(Clone)]
+struct ResolversSnapshot<F> {
+    fixed: HashMap<(usize, Rotation), Recorded<ResolvedQuery<F>>>,
+    advice: HashMap<(usize, Rotation), Recorded<ResolvedQuery<F>>>,
+    instance: HashMap<(usize, Rotation), Recorded<ResolvedQuery<F>>>,
+    selectors: HashMap<usize, Recorded<ResolvedSelector>>,
+    challenges: HashMap<(usize, u8), Recorded<FuncIO>>,
+}
+
+impl<F: Field> ResolversSnapshot<F> {
+    /// Resolves all the selectors, queries and challenges in the expression.
+    fn record<E: EvaluableExpr<F>>(expr: &E, resolvers: &dyn ResolversProvider<F>) -> Self {
+        let recorder = SnapshotRecorder {
+            resolvers,
+            snapshot: RefCell::new(Self {
+                fixed: Default::default(),
+                advice: Default::default(),
+                instance: Default::default(),
+                selectors: Default::default(),
+                challenges: Default::default(),
+            }),
+        };
+        expr.evaluate(&recorder);
+        recorder.snapshot.into_inner()
+    }
+
+    fn lookup<K, T>(recorded: &HashMap<K, Recorded<T>>, key: &K, what: &str) -> Result<T>
+    where
+        K: std::hash::Hash + Eq + std::fmt::Debug,
+        T: Clone,
+    {
+        match recorded.get(key) {
+            Some(Ok(value)) => Ok(value.clone()),
+            Some(Err(err)) => Err(anyhow::anyhow!("{err}")),
+            None => anyhow::bail!("{what} {key:?} was not part of the owned expression"),
+        }
+    }
+}
+
+impl<F: Field> QueryResolver<F> for ResolversSnapshot<F> {
+    fn resolve_fixed_query(&self, query: &dyn QueryInfo<Kind = Fixed>) -> Result<ResolvedQuery<F>> {
+        let key = (query.column_index(), query.rotation());
+        Self::lookup(&self.fixed, &key, "Fixed query")
+    }
+
+    fn resolve_advice_query(
+        &self,
+        query: &dyn QueryInfo<Kind = Advice>,
+    ) -> Result<ResolvedQuery<F>> {
+        let key = (query.column_index(), query.rotation());
+        Self::lookup(&self.advice, &key, "Advice query")
+    }
+
+    fn resolve_instance_query(
+        &self,
+        query: &dyn QueryInfo<Kind = Instance>,
+    ) -> Result<ResolvedQuery<F>> {
+        let key = (query.column_index(), query.rotation());
+        Self::lookup(&self.instance, &key, "Instance query")
+    }
+}
+
+impl<F: Field> SelectorResolver for ResolversSnapshot<F> {
+    fn resolve_selector(&self, selector: &dyn SelectorInfo) -> Result<ResolvedSelector> {
+        Self::lookup(&self.selectors, &selector.id(), "Selector")
+    }
+}
+
+impl<F: Field> ChallengeResolver for ResolversSnapshot<F> {
+    fn resolve_challenge(&self, challenge: &dyn ChallengeInfo) -> Result<FuncIO> {
+        let key = (challenge.index(), challenge.phase());
+        Self::lookup(&self.challenges, &key, "Challenge")
+    }
+}
+
+/// Evaluator that resolves every leaf of an expression and records the result.
+struct SnapshotRecorder<'r, F> {
+    resolvers: &'r dyn ResolversProvider<F>,
+    snapshot: RefCell<ResolversSnapshot<F>>,
+}
+
+fn record<T>(result: Result<T>) -> Recorded<T> {
+    result.map_err(|err| format!("{err:#}"))
+}
+
+impl<F: Field, E: ExpressionTypes> EvalExpression<F, E> for SnapshotRecorder<'_, F> {
+    type Output = ();
+
+    fn constant(&self, _: &F) -> Self::Output {}
+
+    fn selector(&self, selector: &E::Selector) -> Self::Output {
+        let resolved = record(
+            self.resolvers
+                .selector_resolver()
+                .resolve_selector(selector),
+        );
+        self.snapshot
+            .borrow_mut()
+            .selectors
+            .insert(selector.id(), resolved);
+    }
+
+    fn fixed(&self, fixed_query: &E::FixedQuery) -> Self::Output {
+        let resolved = record(
+            self.resolvers
+                .query_resolver()
+                .resolve_fixed_query(fixed_query),
+        );
+        self.snapshot.borrow_mut().fixed.insert(
+            (fixed_query.column_index(), fixed_query.rotation()),
+            resolved,
+        );
+    }
+
+    fn advice(&self, advice_query: &E::AdviceQuery) -> Self::Output {
+        let resolved = record(
+            self.resolvers
+                .query_resolver()
+                .resolve_advice_query(advice_query),
+        );
+        self.snapshot.borrow_mut().advice.insert(
+            (advice_query.column_index(), advice_query.rotation()),
+            resolved,
+        );
+    }
+
+    fn instance(&self, instance_query: &E::InstanceQuery) -> Self::Output {
+        let resolved = record(
+            self.resolvers
+                .query_resolver()
+                .resolve_instance_query(instance_query),
+        );
+        self.snapshot.borrow_mut().instance.insert(
+            (instance_query.column_index(), instance_query.rotation()),
+            resolved,
+        );
+    }
+
+    fn challenge(&self, challenge: &E::Challenge) -> Self::Output {
+        let resolved = record(
+            self.resolvers
+                .challenge_resolver()
+                .resolve_challenge(challenge),
+        );
+        self.snapshot
+            .borrow_mut()
+            .challenges
+            .insert((challenge.index(), challenge.phase()), resolved);
+    }
+
+    fn negated(&self, _: Self::Output) -> Self::Output {}
+
+    fn sum(&self, _: Self::Output, _: Self::Output) -> Self::Output {}
+
+    fn product(&self, _: Self::Output, _: Self::Output) -> Self::Output {}
+
+    fn scaled(&self, _: Self::Output, _: &F) -> Self::Output {}
 }
 
 impl<F, E> std::fmt::Debug for ScopedExpression<'_, '_, F, E>
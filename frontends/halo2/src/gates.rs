@@ -20,6 +20,7 @@ use halo2_frontend_core::{
 /// Information about a gate in the constraint system.
 ///
 /// Is parameterized by the expression type used to represent polynomials.
+#[derive(Clone)]
 pub(crate) struct Gate<E> {
     name: String,
     polynomials: Vec<E>,
@@ -150,6 +151,20 @@ impl<'syn, 'io, F: Field, E> GateScope<'syn, 'io, F, E> {
         self.gate.polynomials()
     }
 
+    /// Returns the maximum degree of the polynomials of the gate.
+    ///
+    /// If the gate has no polynomials returns 0.
+    pub fn max_degree(&self) -> usize
+    where
+        E: EvaluableExpr<F>,
+    {
+        self.polynomials()
+            .iter()
+            .map(EvaluableExpr::degree)
+            .max()
+            .unwrap_or_default()
+    }
+
     /// Returns the list of polynomials once per row. The polynomials per row are constant-folded
     /// first.
     pub fn polynomials_per_row(&self) -> anyhow::Result<Vec<(&'syn E, FoldedExpressions<E>)>>
@@ -216,6 +231,41 @@ impl<'syn, 'io, F: Field, E> GateScope<'syn, 'io, F, E> {
     pub fn rows(&self) -> Range<usize> {
         (self.row_bounds.0)..(self.row_bounds.1)
     }
+
+    /// Returns true if the selector was enabled in the given row of the region during synthesis.
+    pub fn selector_is_active(&self, sel: &E::Selector, row: usize) -> bool
+    where
+        E: ExpressionTypes,
+    {
+        self.region
+            .selectors_enabled_for_row(row)
+            .contains(sel.id())
+    }
+
+    /// Returns the selectors used by the gate's polynomials that are enabled in every row of the
+    /// scope.
+    ///
+    /// If the scope does not have any rows returns an empty list.
+    pub fn always_active_selectors(&self) -> Vec<E::Selector>
+    where
+        E: EvaluableExpr<F>,
+    {
+        if self.rows().is_empty() {
+            return vec![];
+        }
+        self.polynomials()
+            .iter()
+            .flat_map(collect_selectors::<F, E>)
+            .fold(Vec::<E::Selector>::new(), |mut acc, sel| {
+                if acc.iter().all(|s| s.id() != sel.id()) {
+                    acc.push(sel);
+                }
+                acc
+            })
+            .into_iter()
+            .filter(|sel| self.rows().all(|row| self.selector_is_active(sel, row)))
+            .collect()
+    }
 }
 
 impl<F: Field, E> Copy for GateScope<'_, '_, F, E> {}
@@ -253,6 +303,11 @@ pub type RewriteOutput<'syn, E> = IRStmt<(usize, Cow<'syn, E>)>;
 ///
 /// The rewrites performed by these patterns should be semantics preserving.
 pub trait GateRewritePattern<F, E> {
+    /// Returns a name that describes the pattern. Defaults to the name of the implementing type.
+    fn pattern_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
     /// Checks if the gate matches the pattern.
     ///
     /// Returns Ok(()) if the pattern matched.
@@ -303,6 +358,16 @@ pub trait GateCallbacks<F, E> {
     fn patterns(&self) -> Vec<Box<dyn GateRewritePattern<F, E>>>
     where
         F: Field;
+
+    /// Called when a gate did not match any of the patterns.
+    ///
+    /// Defaults to `Ok(())`, which skips the gate without emitting IR for it and reports it with a
+    /// warning. Implementations can override it to log, panic or return an error, in which case
+    /// the lowering fails with it.
+    #[allow(unused_variables)]
+    fn on_unmatched_gate(&self, gate_name: &str, region_name: &str) -> anyhow::Result<()> {
+        Ok(())
+    }
 }
 
 /// Default gate callbacks.
@@ -318,7 +383,7 @@ impl<F, E> GateCallbacks<F, E> for DefaultGateCallbacks {
 }
 
 /// A set of rewrite patterns.
-pub(crate) struct RewritePatternSet<F, E>(Vec<Box<dyn GateRewritePattern<F, E>>>);
+pub struct RewritePatternSet<F, E>(Vec<Box<dyn GateRewritePattern<F, E>>>);
 
 impl<F, E> RewritePatternSet<F, E> {
     /// Adds a pattern to the set.
@@ -327,6 +392,36 @@ impl<F, E> RewritePatternSet<F, E> {
     }
 }
 
+impl<F, E> RewritePatternSet<F, E> {
+    /// Returns a description of each pattern in the set, in the order they are checked.
+    pub fn describe(&self) -> Vec<PatternDescription> {
+        self.0
+            .iter()
+            .enumerate()
+            .map(|(priority, p)| PatternDescription {
+                name: p.pattern_name(),
+                priority,
+            })
+            .collect()
+    }
+}
+
+/// Describes a pattern registered in a [`RewritePatternSet`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PatternDescription {
+    /// The name of the pattern, as returned by [`GateRewritePattern::pattern_name`]. Defaults to
+    /// the [`std::any::type_name`] of the pattern.
+    pub name: &'static str,
+    /// The position of the pattern in the set. Patterns with lower values are checked first.
+    pub priority: usize,
+}
+
+impl<F, E> std::fmt::Debug for RewritePatternSet<F, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.describe()).finish()
+    }
+}
+
 impl<F, E> Default for RewritePatternSet<F, E> {
     fn default() -> Self {
         Self(Default::default())
@@ -388,6 +483,32 @@ impl<F, E> GateRewritePattern<F, E> for RewritePatternSet<F, E> {
 
 pub(crate) type SelectorSet = bit_set::BitSet;
 
+/// Returns the selectors that appear in the polynomial, in order of appearance.
+fn collect_selectors<F, E: EvaluableExpr<F>>(poly: &E) -> Vec<E::Selector> {
+    struct Eval<S>(RefCell<Vec<S>>);
+
+    impl<F, E: ExpressionTypes> EvalExpression<F, E> for Eval<E::Selector> {
+        type Output = ();
+
+        fn selector(&self, selector: &E::Selector) -> Self::Output {
+            self.0.borrow_mut().push(*selector);
+        }
+
+        fn constant(&self, _: &F) -> Self::Output {}
+        fn fixed(&self, _: &E::FixedQuery) -> Self::Output {}
+        fn advice(&self, _: &E::AdviceQuery) -> Self::Output {}
+        fn instance(&self, _: &E::InstanceQuery) -> Self::Output {}
+        fn challenge(&self, _: &E::Challenge) -> Self::Output {}
+        fn negated(&self, _: Self::Output) -> Self::Output {}
+        fn sum(&self, _: Self::Output, _: Self::Output) -> Self::Output {}
+        fn product(&self, _: Self::Output, _: Self::Output) -> Self::Output {}
+        fn scaled(&self, _: Self::Output, _: &F) -> Self::Output {}
+    }
+    let e = Eval(Default::default());
+    poly.evaluate(&e);
+    e.0.take()
+}
+
 pub(crate) fn find_selectors<F: Field, E: EvaluableExpr<F>>(poly: &E) -> SelectorSet {
     struct Eval(RefCell<SelectorSet>);
 
@@ -412,3 +533,63 @@ pub(crate) fn find_selectors<F: Field, E: EvaluableExpr<F>>(poly: &E) -> Selecto
     poly.evaluate(&e);
     e.0.take()
 }
+
+#[cfg(test)]
+mod tests {
+    use halo2curves::bn256::Fr;
+
+    use super::*;
+
+    struct RejectUnmatched;
+
+    impl GateCallbacks<Fr, ()> for RejectUnmatched {
+        fn patterns(&self) -> Vec<Box<dyn GateRewritePattern<Fr, ()>>> {
+            vec![]
+        }
+
+        fn on_unmatched_gate(&self, gate_name: &str, _: &str) -> anyhow::Result<()> {
+            Err(anyhow::anyhow!("Gate '{gate_name}' is not supported"))
+        }
+    }
+
+    struct First;
+
+    impl GateRewritePattern<Fr, ()> for First {}
+
+    struct Second;
+
+    impl GateRewritePattern<Fr, ()> for Second {}
+
+    #[test]
+    fn describe_lists_patterns_in_order() {
+        let mut patterns = RewritePatternSet::<Fr, ()>::default();
+        patterns.add(First);
+        patterns.add(Second);
+        assert_eq!(
+            patterns.describe(),
+            vec![
+                PatternDescription {
+                    name: std::any::type_name::<First>(),
+                    priority: 0,
+                },
+                PatternDescription {
+                    name: std::any::type_name::<Second>(),
+                    priority: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn unmatched_gates_are_skipped_by_default() {
+        let cb: &dyn GateCallbacks<Fr, ()> = &DefaultGateCallbacks;
+        assert!(cb.on_unmatched_gate("gate", "region").is_ok());
+    }
+
+    #[test]
+    fn rejecting_unmatched_gates_is_opt_in() {
+        let cb: &dyn GateCallbacks<Fr, ()> = &RejectUnmatched;
+        let err = cb.on_unmatched_gate("gate", "region").unwrap_err();
+        assert_eq!(err.to_string(), "Gate 'gate' is not supported");
+    }
+}
@@ -61,6 +61,18 @@ impl<C: ColumnType> CircuitIO<C> {
         self.outputs.len()
     }
 
+    /// Returns true if there are no inputs nor outputs.
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty() && self.outputs.is_empty()
+    }
+
+    /// Appends the inputs and outputs of `other` to the ones in `self`.
+    pub fn union(mut self, other: Self) -> Self {
+        self.inputs.extend(other.inputs);
+        self.outputs.extend(other.outputs);
+        self
+    }
+
     fn map<I>(m: &[(I, &[usize])]) -> Vec<IOCell<C>>
     where
         I: Into<Column<C>> + Copy,
@@ -117,3 +129,21 @@ impl<C: ColumnType + Hash> CircuitIO<C> {
         self.outputs.iter().collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_appends_the_cells() {
+        let col = |index| Column::new(index, Instance);
+        let lhs = InstanceIO::new_from_iocells([(col(0), 0)], [(col(0), 1)]);
+        let rhs = InstanceIO::new_from_iocells([(col(1), 0)], []);
+        assert!(!lhs.is_empty());
+
+        let io = lhs.union(rhs).union(InstanceIO::empty());
+        assert_eq!(io.inputs(), [(col(0), 0), (col(1), 0)]);
+        assert_eq!(io.outputs(), [(col(0), 1)]);
+        assert!(InstanceIO::empty().union(InstanceIO::empty()).is_empty());
+    }
+}
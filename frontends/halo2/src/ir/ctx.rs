@@ -1,24 +1,31 @@
 use std::collections::{HashMap, HashSet};
 use std::ops::Range;
 
+use anyhow::{Result, bail};
 use ff::Field;
 
 use crate::CircuitIO;
 use crate::io::IOCell;
 use crate::ir::generate::region_data;
-use crate::synthesis::SynthesizedCircuit;
 use crate::synthesis::groups::GroupCell;
 use crate::synthesis::regions::RegionData;
+use crate::synthesis::{CellAnnotations, SynthesizedCircuit};
 use halo2_frontend_core::query::{Advice, Instance};
 use halo2_frontend_core::table::{Any, Column, RegionIndex};
 
 /// Contains information related to the IR of a circuit. Is used by the driver to lower the
 /// circuit.
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "json-export",
+    derive(serde::Deserialize),
+    serde(from = "json::IRCtxRepr")
+)]
 pub struct IRCtx {
     groups_advice_io: HashMap<usize, crate::io::AdviceIO>,
     groups_instance_io: HashMap<usize, crate::io::InstanceIO>,
     advice_cells: HashMap<RegionIndex, AdviceCells>,
+    cell_annotations: CellAnnotations,
 }
 
 impl IRCtx {
@@ -42,9 +49,59 @@ impl IRCtx {
                 .into_iter()
                 .map(|(k, r)| (k, AdviceCells::new(r)))
                 .collect(),
+            cell_annotations: syn.cell_annotations().clone(),
         }
     }
 
+    /// Appends the information of another context, shifting the indices of its groups by
+    /// `offset`.
+    ///
+    /// The regions of `other` are renumbered to start after the last region of this context.
+    /// Returns the merged context together with the offset added to the region indices of
+    /// `other`, or an error if a shifted group index is already defined in this context or if
+    /// both contexts annotate the same cell differently.
+    pub fn merge(mut self, other: IRCtx, offset: usize) -> Result<(IRCtx, usize)> {
+        for (idx, advice_io) in other.groups_advice_io {
+            let idx = idx + offset;
+            if self.groups_advice_io.contains_key(&idx) {
+                bail!("Group {idx} is defined in both contexts");
+            }
+            self.groups_advice_io.insert(idx, advice_io);
+        }
+        for (idx, instance_io) in other.groups_instance_io {
+            let idx = idx + offset;
+            if self.groups_instance_io.contains_key(&idx) {
+                bail!("Group {idx} is defined in both contexts");
+            }
+            self.groups_instance_io.insert(idx, instance_io);
+        }
+
+        let region_offset = self
+            .advice_cells
+            .keys()
+            .map(|idx| **idx + 1)
+            .max()
+            .unwrap_or_default();
+        self.advice_cells.extend(
+            other
+                .advice_cells
+                .into_iter()
+                .map(|(idx, cells)| (RegionIndex::from(*idx + region_offset), cells)),
+        );
+        for ((col, row), annotation) in other.cell_annotations {
+            match self.cell_annotations.get(&(col, row)) {
+                Some(existing) if *existing != annotation => bail!(
+                    "Cell {col:?} at row {row} is annotated as {existing:?} and {annotation:?} in the two contexts"
+                ),
+                Some(_) => {}
+                None => {
+                    self.cell_annotations.insert((col, row), annotation);
+                }
+            }
+        }
+        Ok((self, region_offset))
+    }
+
     pub(crate) fn advice_io_of_group(&self, idx: usize) -> &crate::io::AdviceIO {
         &self.groups_advice_io[&idx]
     }
@@ -56,6 +113,11 @@ impl IRCtx {
     pub(crate) fn advice_cells(&self) -> &HashMap<RegionIndex, AdviceCells> {
         &self.advice_cells
     }
+
+    /// Returns the annotation attached to the cell during synthesis, if any.
+    pub(crate) fn cell_annotation(&self, col: Column<Any>, row: usize) -> Option<&str> {
+        self.cell_annotations.get(&(col, row)).map(String::as_str)
+    }
 }
 
 /// Contains information about the advice cells in a region.
@@ -144,3 +206,301 @@ fn mk_instance_io(
         outputs.iter().filter_map(filter_fn),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(groups: &[usize], regions: &[usize]) -> IRCtx {
+        IRCtx {
+            groups_advice_io: groups.iter().map(|g| (*g, CircuitIO::empty())).collect(),
+            groups_instance_io: groups.iter().map(|g| (*g, CircuitIO::empty())).collect(),
+            advice_cells: regions
+                .iter()
+                .map(|r| {
+                    let cells = AdviceCells {
+                        columns: Default::default(),
+                        rows: 0..1,
+                        start: Some(*r),
+                    };
+                    (RegionIndex::from(*r), cells)
+                })
+                .collect(),
+            cell_annotations: Default::default(),
+        }
+    }
+
+    #[test]
+    fn merge_shifts_groups_and_regions() {
+        let (merged, region_offset) = ctx(&[0, 1], &[0, 1, 2]).merge(ctx(&[0], &[0]), 2).unwrap();
+        assert_eq!(region_offset, 3);
+        let mut groups = merged.groups_advice_io.keys().copied().collect::<Vec<_>>();
+        groups.sort();
+        assert_eq!(groups, vec![0, 1, 2]);
+        assert_eq!(
+            merged.advice_cells()[&RegionIndex::from(3)].start(),
+            Some(0)
+        );
+        assert_eq!(merged.advice_cells().len(), 4);
+    }
+
+    #[test]
+    fn merge_rejects_duplicated_groups() {
+        assert!(ctx(&[0, 1], &[]).merge(ctx(&[0], &[]), 1).is_err());
+    }
+
+    #[test]
+    fn merge_rejects_duplicated_instance_io() {
+        let mut this = ctx(&[0], &[]);
+        this.groups_instance_io.insert(1, CircuitIO::empty());
+        let mut other = ctx(&[], &[]);
+        other.groups_instance_io.insert(0, CircuitIO::empty());
+        assert!(this.merge(other, 1).is_err());
+    }
+
+    #[test]
+    fn merge_rejects_conflicting_cell_annotations() {
+        let annotated = |annotation: &str| {
+            let mut ctx = ctx(&[], &[]);
+            ctx.cell_annotations
+                .insert((Column::new(0, Any::Advice), 3), annotation.to_string());
+            ctx
+        };
+        let (merged, _) = annotated("a").merge(annotated("a"), 0).unwrap();
+        assert_eq!(
+            merged.cell_annotation(Column::new(0, Any::Advice), 3),
+            Some("a")
+        );
+        assert!(annotated("a").merge(annotated("b"), 0).is_err());
+    }
+
+    #[test]
+    fn merge_into_empty_context_keeps_region_indices() {
+        let (merged, region_offset) = ctx(&[], &[]).merge(ctx(&[0], &[0, 1]), 0).unwrap();
+        assert_eq!(region_offset, 0);
+        assert_eq!(
+            merged.advice_cells()[&RegionIndex::from(1)].start(),
+            Some(1)
+        );
+    }
+
+    #[cfg(feature = "json-export")]
+    #[test]
+    fn serialization_requires_the_io_of_every_group() {
+        let complete = ctx(&[0, 1], &[0]);
+        let json = serde_json::to_string(&complete).unwrap();
+        let parsed: IRCtx = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+
+        let mut missing_instance = ctx(&[0, 1], &[0]);
+        missing_instance.groups_instance_io.remove(&1);
+        assert!(serde_json::to_string(&missing_instance).is_err());
+
+        let mut missing_advice = ctx(&[0, 1], &[0]);
+        missing_advice.groups_advice_io.remove(&0);
+        assert!(serde_json::to_string(&missing_advice).is_err());
+    }
+}
+
+#[cfg(feature = "json-export")]
+mod json {
+    //! Serializable representation of [`IRCtx`].
+
+    use std::ops::Range;
+
+    use anyhow::bail;
+    use halo2_frontend_core::query::{Advice, Instance};
+    use halo2_frontend_core::table::{Any, Column, ColumnType, RegionIndex};
+    use serde::{Deserialize, Serialize, Serializer};
+
+    use super::{AdviceCells, IRCtx};
+    use crate::CircuitIO;
+
+    #[derive(Serialize, Deserialize)]
+    struct IORepr {
+        inputs: Vec<(usize, usize)>,
+        outputs: Vec<(usize, usize)>,
+    }
+
+    impl IORepr {
+        fn new<C: ColumnType>(io: &CircuitIO<C>) -> Self {
+            let cells = |cells: &[(Column<C>, usize)]| -> Vec<_> {
+                cells.iter().map(|(col, row)| (col.index(), *row)).collect()
+            };
+            Self {
+                inputs: cells(io.inputs()),
+                outputs: cells(io.outputs()),
+            }
+        }
+
+        fn into_io<C: ColumnType>(self, column_type: C) -> CircuitIO<C> {
+            let cells = |cells: Vec<(usize, usize)>| {
+                cells
+                    .into_iter()
+                    .map(move |(col, row)| (Column::new(col, column_type), row))
+            };
+            CircuitIO::new_from_iocells(cells(self.inputs), cells(self.outputs))
+        }
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct GroupRepr {
+        id: usize,
+        advice: IORepr,
+        instance: IORepr,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct RegionRepr {
+        index: usize,
+        advice_columns: Vec<usize>,
+        rows: Range<usize>,
+        start: Option<usize>,
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+    enum ColumnKind {
+        Advice,
+        Fixed,
+        Instance,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct AnnotationRepr {
+        kind: ColumnKind,
+        column: usize,
+        row: usize,
+        annotation: String,
+    }
+
+    /// Stores the maps of the context as lists sorted by key to get a deterministic output.
+    #[derive(Serialize, Deserialize)]
+    pub(super) struct IRCtxRepr {
+        groups: Vec<GroupRepr>,
+        regions: Vec<RegionRepr>,
+        #[serde(default)]
+        annotations: Vec<AnnotationRepr>,
+    }
+
+    /// Serialized through [`IRCtxRepr`], failing if the IO of a group is incomplete.
+    impl Serialize for IRCtx {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            IRCtxRepr::try_from(self)
+                .map_err(serde::ser::Error::custom)?
+                .serialize(serializer)
+        }
+    }
+
+    impl TryFrom<&IRCtx> for IRCtxRepr {
+        type Error = anyhow::Error;
+
+        fn try_from(ctx: &IRCtx) -> anyhow::Result<Self> {
+            if let Some(id) = ctx
+                .groups_instance_io
+                .keys()
+                .find(|id| !ctx.groups_advice_io.contains_key(id))
+            {
+                bail!("Group {id} has instance IO but no advice IO");
+            }
+            let mut groups = Vec::with_capacity(ctx.groups_advice_io.len());
+            for (id, advice) in &ctx.groups_advice_io {
+                let Some(instance) = ctx.groups_instance_io.get(id) else {
+                    bail!("Group {id} has advice IO but no instance IO");
+                };
+                groups.push(GroupRepr {
+                    id: *id,
+                    advice: IORepr::new(advice),
+                    instance: IORepr::new(instance),
+                });
+            }
+            groups.sort_by_key(|g| g.id);
+
+            let mut regions = ctx
+                .advice_cells
+                .iter()
+                .map(|(index, cells)| {
+                    let mut advice_columns =
+                        cells.columns.iter().map(|c| c.index()).collect::<Vec<_>>();
+                    advice_columns.sort();
+                    RegionRepr {
+                        index: **index,
+                        advice_columns,
+                        rows: cells.rows.clone(),
+                        start: cells.start,
+                    }
+                })
+                .collect::<Vec<_>>();
+            regions.sort_by_key(|r| r.index);
+
+            let mut annotations = ctx
+                .cell_annotations
+                .iter()
+                .map(|((col, row), annotation)| AnnotationRepr {
+                    kind: match col.column_type() {
+                        Any::Advice => ColumnKind::Advice,
+                        Any::Fixed => ColumnKind::Fixed,
+                        Any::Instance => ColumnKind::Instance,
+                    },
+                    column: col.index(),
+                    row: *row,
+                    annotation: annotation.clone(),
+                })
+                .collect::<Vec<_>>();
+            annotations.sort_by(|a, b| (&a.kind, a.column, a.row).cmp(&(&b.kind, b.column, b.row)));
+
+            Ok(Self {
+                groups,
+                regions,
+                annotations,
+            })
+        }
+    }
+
+    impl From<IRCtxRepr> for IRCtx {
+        fn from(repr: IRCtxRepr) -> Self {
+            let mut groups_advice_io = std::collections::HashMap::new();
+            let mut groups_instance_io = std::collections::HashMap::new();
+            for group in repr.groups {
+                groups_advice_io.insert(group.id, group.advice.into_io(Advice));
+                groups_instance_io.insert(group.id, group.instance.into_io(Instance));
+            }
+            let advice_cells = repr
+                .regions
+                .into_iter()
+                .map(|region| {
+                    let cells = AdviceCells {
+                        columns: region
+                            .advice_columns
+                            .into_iter()
+                            .map(|col| Column::new(col, Any::Advice))
+                            .collect(),
+                        rows: region.rows,
+                        start: region.start,
+                    };
+                    (RegionIndex::from(region.index), cells)
+                })
+                .collect();
+            let cell_annotations = repr
+                .annotations
+                .into_iter()
+                .map(|a| {
+                    let kind = match a.kind {
+                        ColumnKind::Advice => Any::Advice,
+                        ColumnKind::Fixed => Any::Fixed,
+                        ColumnKind::Instance => Any::Instance,
+                    };
+                    ((Column::new(a.column, kind), a.row), a.annotation)
+                })
+                .collect();
+            Self {
+                groups_advice_io,
+                groups_instance_io,
+                advice_cells,
+                cell_annotations,
+            }
+        }
+    }
+}
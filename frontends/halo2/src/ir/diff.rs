@@ -0,0 +1,279 @@
+//! Structural comparison of circuits.
+
+use std::fmt;
+
+use crate::ir::{
+    CmpOp, ResolvedIRCircuit, expr::IRAexpr, groups::GroupBody, printer::IRPrinter, stmt::IRStmt,
+};
+
+/// Differences between the statements of two versions of a group.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GroupDiff {
+    name: String,
+    io: Option<((usize, usize), (usize, usize))>,
+    removed: Vec<String>,
+    added: Vec<String>,
+    changed: Vec<(String, String)>,
+}
+
+impl GroupDiff {
+    fn new(a: &GroupBody<IRAexpr>, b: &GroupBody<IRAexpr>) -> Self {
+        let io_a = (a.input_count(), a.output_count());
+        let io_b = (b.input_count(), b.output_count());
+        let mut diff = Self {
+            name: a.name().to_owned(),
+            io: (io_a != io_b).then_some((io_a, io_b)),
+            ..Default::default()
+        };
+        diff.compare(&group_statements(a), &group_statements(b));
+        diff
+    }
+
+    /// Compares the lists of statements after removing the common prefix and suffix. The
+    /// remaining statements are paired by position as changes and the rest are additions or
+    /// removals.
+    fn compare(&mut self, a: &[IRStmt<IRAexpr>], b: &[IRStmt<IRAexpr>]) {
+        let prefix = std::iter::zip(a, b).take_while(|(a, b)| a == b).count();
+        let (a, b) = (&a[prefix..], &b[prefix..]);
+        let suffix = std::iter::zip(a.iter().rev(), b.iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let (a, b) = (&a[..a.len() - suffix], &b[..b.len() - suffix]);
+
+        let paired = a.len().min(b.len());
+        self.changed = std::iter::zip(&a[..paired], &b[..paired])
+            .map(|(a, b)| (render(a), render(b)))
+            .collect();
+        self.removed = a[paired..].iter().map(render).collect();
+        self.added = b[paired..].iter().map(render).collect();
+    }
+
+    /// Returns the name of the group.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the number of inputs and outputs before and after if they changed.
+    pub fn io(&self) -> Option<((usize, usize), (usize, usize))> {
+        self.io
+    }
+
+    /// Returns the statements that are only in the first circuit.
+    pub fn removed(&self) -> &[String] {
+        &self.removed
+    }
+
+    /// Returns the statements that are only in the second circuit.
+    pub fn added(&self) -> &[String] {
+        &self.added
+    }
+
+    /// Returns the pairs of statements that occupy the same position but are different.
+    pub fn changed(&self) -> &[(String, String)] {
+        &self.changed
+    }
+
+    /// Returns true if both versions of the group are structurally equal.
+    pub fn is_empty(&self) -> bool {
+        self.io.is_none()
+            && self.removed.is_empty()
+            && self.added.is_empty()
+            && self.changed.is_empty()
+    }
+}
+
+impl fmt::Display for GroupDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "@@ group {:?} @@", self.name)?;
+        if let Some(((ins_a, outs_a), (ins_b, outs_b))) = self.io {
+            writeln!(f, "-inputs: {ins_a}, outputs: {outs_a}")?;
+            writeln!(f, "+inputs: {ins_b}, outputs: {outs_b}")?;
+        }
+        for (a, b) in &self.changed {
+            write_lines(f, '-', a)?;
+            write_lines(f, '+', b)?;
+        }
+        for stmt in &self.removed {
+            write_lines(f, '-', stmt)?;
+        }
+        for stmt in &self.added {
+            write_lines(f, '+', stmt)?;
+        }
+        Ok(())
+    }
+}
+
+/// Structural differences between two circuits.
+///
+/// Groups are matched by name and their statements are compared with structural equality,
+/// ignoring comments.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CircuitDiff {
+    removed_groups: Vec<String>,
+    added_groups: Vec<String>,
+    changed_groups: Vec<GroupDiff>,
+}
+
+impl CircuitDiff {
+    pub(super) fn new(a: &ResolvedIRCircuit, b: &ResolvedIRCircuit) -> Self {
+        let mut diff = Self::default();
+        for group_a in a.groups() {
+            match b.groups().iter().find(|g| g.name() == group_a.name()) {
+                Some(group_b) => {
+                    let group_diff = GroupDiff::new(group_a, group_b);
+                    if !group_diff.is_empty() {
+                        diff.changed_groups.push(group_diff);
+                    }
+                }
+                None => diff.removed_groups.push(group_a.name().to_owned()),
+            }
+        }
+        diff.added_groups = b
+            .groups()
+            .iter()
+            .filter(|g| !a.groups().iter().any(|group_a| group_a.name() == g.name()))
+            .map(|g| g.name().to_owned())
+            .collect();
+        diff
+    }
+
+    /// Returns the names of the groups that are only in the first circuit.
+    pub fn removed_groups(&self) -> &[String] {
+        &self.removed_groups
+    }
+
+    /// Returns the names of the groups that are only in the second circuit.
+    pub fn added_groups(&self) -> &[String] {
+        &self.added_groups
+    }
+
+    /// Returns the differences of the groups that are in both circuits but are not equal.
+    pub fn changed_groups(&self) -> &[GroupDiff] {
+        &self.changed_groups
+    }
+
+    /// Returns true if the circuits are structurally equal.
+    pub fn is_empty(&self) -> bool {
+        self.removed_groups.is_empty()
+            && self.added_groups.is_empty()
+            && self.changed_groups.is_empty()
+    }
+}
+
+impl fmt::Display for CircuitDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "--- a")?;
+        writeln!(f, "+++ b")?;
+        for name in &self.removed_groups {
+            writeln!(f, "-group {name:?}")?;
+        }
+        for name in &self.added_groups {
+            writeln!(f, "+group {name:?}")?;
+        }
+        for group in &self.changed_groups {
+            write!(f, "{group}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders the statement with the IR printer.
+fn render(stmt: &IRStmt<IRAexpr>) -> String {
+    IRPrinter::from_stmt(stmt).to_string()
+}
+
+/// Writes each line of the text preceded by the marker.
+fn write_lines(f: &mut fmt::Formatter<'_>, marker: char, text: &str) -> fmt::Result {
+    for line in text.lines() {
+        writeln!(f, "{marker}{line}")?;
+    }
+    Ok(())
+}
+
+/// Returns the statements of the group, including the calls to other groups, without comments.
+///
+/// Sequences are flattened so that the comments inside them are removed as well.
+fn group_statements(group: &GroupBody<IRAexpr>) -> Vec<IRStmt<IRAexpr>> {
+    group
+        .callsites()
+        .iter()
+        .flat_map(|callsite| {
+            let call = IRStmt::call(
+                callsite.name(),
+                callsite.inputs().iter().cloned(),
+                callsite.output_vars().iter().copied(),
+            );
+            let outputs =
+                std::iter::zip(callsite.outputs(), callsite.output_vars()).map(|(output, var)| {
+                    IRStmt::constraint(CmpOp::Eq, output.clone(), IRAexpr::IO(*var))
+                });
+            std::iter::once(call).chain(outputs)
+        })
+        .chain(
+            group
+                .statements()
+                .flat_map(IRStmt::iter)
+                .filter(|stmt| !matches!(stmt, IRStmt::Comment(_)))
+                .cloned(),
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{backend::func::FuncIO, ir::expr::Felt};
+
+    fn constraint(col: usize, value: usize) -> IRStmt<IRAexpr> {
+        IRStmt::constraint(
+            CmpOp::Eq,
+            IRAexpr::IO(FuncIO::advice_abs(col, 0)),
+            IRAexpr::Constant(Felt::from(value)),
+        )
+    }
+
+    #[test]
+    fn equal_statements_have_no_diff() {
+        let stmts = [constraint(0, 1), constraint(1, 2)];
+        let mut diff = GroupDiff::default();
+        diff.compare(&stmts, &stmts);
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn changed_and_added_statements() {
+        let a = [constraint(0, 1), constraint(1, 2), constraint(3, 3)];
+        let b = [
+            constraint(0, 1),
+            constraint(1, 5),
+            constraint(2, 4),
+            constraint(3, 3),
+        ];
+        let mut diff = GroupDiff::default();
+        diff.compare(&a, &b);
+        assert_eq!(diff.changed().len(), 1);
+        assert!(diff.removed().is_empty());
+        assert_eq!(diff.added(), &[render(&constraint(2, 4))]);
+        assert!(!diff.is_empty());
+    }
+
+    #[test]
+    fn statements_are_rendered_with_the_printer() {
+        let a = [constraint(0, 1)];
+        let b = [constraint(0, 2)];
+        let mut diff = GroupDiff::default();
+        diff.compare(&a, &b);
+        let (before, after) = &diff.changed()[0];
+        assert_eq!(before, &IRPrinter::from_stmt(&a[0]).to_string());
+        assert_eq!(after, &IRPrinter::from_stmt(&b[0]).to_string());
+        assert!(before.starts_with("(assert/"));
+
+        let output = diff.to_string();
+        assert!(
+            output
+                .lines()
+                .skip(1)
+                .all(|line| line.starts_with(['-', '+']))
+        );
+    }
+}
@@ -49,6 +49,32 @@ impl Felt {
     pub fn new_from<I: Into<BigUint>>(i: I) -> Self {
         Self(Intern::new(i.into()))
     }
+
+    /// Returns the additive inverse of the felt modulo the given prime.
+    ///
+    /// Panics if the prime is zero.
+    pub fn neg_mod(self, prime: Felt) -> Felt {
+        let value = self % prime;
+        if value == 0usize {
+            return value;
+        }
+        (prime - value).expect("value is reduced modulo the prime")
+    }
+
+    /// Returns the multiplicative inverse of the felt modulo the given prime, or `None` if the
+    /// felt is zero modulo the prime or the prime is smaller than 2.
+    pub fn inv_mod(self, prime: Felt) -> Option<Felt> {
+        if prime < Felt::from(2usize) {
+            return None;
+        }
+        let value = self % prime;
+        if value == 0usize {
+            return None;
+        }
+        // By Fermat's little theorem a^(p-2) is the inverse of a.
+        let exponent = prime.as_ref() - 2usize;
+        Some(value.modpow(&exponent, prime.as_ref()).into())
+    }
 }
 
 impl std::fmt::Debug for Felt {
@@ -146,8 +172,34 @@ impl std::fmt::Display for Felt {
     }
 }
 
+/// Serialized as an hexadecimal string prefixed with `0x`.
+#[cfg(feature = "json-export")]
+impl serde::Serialize for Felt {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&format!("0x{:x}", self.as_ref()))
+    }
+}
+
+#[cfg(feature = "json-export")]
+impl<'de> serde::Deserialize<'de> for Felt {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.strip_prefix("0x")
+            .and_then(|digits| BigUint::parse_bytes(digits.as_bytes(), 16))
+            .map(Felt::from)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid field element {s:?}")))
+    }
+}
+
 /// Represents an arithmetic expression.
 #[derive(PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "json-export", derive(serde::Serialize, serde::Deserialize))]
 pub enum IRAexpr {
     /// Constant value.
     Constant(Felt),
@@ -183,6 +235,26 @@ impl IRAexpr {
         }
     }
 
+    /// Returns the depth of the expression tree. Leaves have depth 1.
+    pub fn depth(&self) -> usize {
+        match self {
+            IRAexpr::Constant(_) | IRAexpr::IO(_) => 1,
+            IRAexpr::Negated(expr) => 1 + expr.depth(),
+            IRAexpr::Sum(lhs, rhs) | IRAexpr::Product(lhs, rhs) => {
+                1 + std::cmp::max(lhs.depth(), rhs.depth())
+            }
+        }
+    }
+
+    /// Returns the number of nodes in the expression tree.
+    pub fn size(&self) -> usize {
+        match self {
+            IRAexpr::Constant(_) | IRAexpr::IO(_) => 1,
+            IRAexpr::Negated(expr) => 1 + expr.size(),
+            IRAexpr::Sum(lhs, rhs) | IRAexpr::Product(lhs, rhs) => 1 + lhs.size() + rhs.size(),
+        }
+    }
+
     /// Folds the expression if the values are constant.
     pub(crate) fn constant_fold(&mut self, prime: Felt) {
         match self {
@@ -504,6 +576,36 @@ mod folding_tests {
         sum.constant_fold(seven);
         assert_eq!(sum, lhs);
     }
+
+    #[rstest]
+    fn negation_modulo_prime(seven: Felt) {
+        assert_eq!(Felt::from(0usize).neg_mod(seven), 0usize);
+        assert_eq!(Felt::from(1usize).neg_mod(seven), 6usize);
+        assert_eq!(Felt::from(6usize).neg_mod(seven), 1usize);
+        assert_eq!(seven.neg_mod(seven), 0usize);
+        assert_eq!(Felt::from(9usize).neg_mod(seven), 5usize);
+    }
+
+    #[rstest]
+    fn inverse_modulo_prime(seven: Felt) {
+        assert_eq!(Felt::from(0usize).inv_mod(seven), None);
+        assert_eq!(seven.inv_mod(seven), None);
+        assert_eq!(Felt::from(1usize).inv_mod(seven), Some(1usize.into()));
+        assert_eq!(Felt::from(6usize).inv_mod(seven), Some(6usize.into()));
+        assert_eq!(Felt::from(3usize).inv_mod(seven), Some(5usize.into()));
+        assert_eq!(Felt::from(10usize).inv_mod(seven), Some(5usize.into()));
+    }
+
+    #[test]
+    fn inverse_modulo_small_primes() {
+        assert_eq!(Felt::from(1usize).inv_mod(0usize.into()), None);
+        assert_eq!(Felt::from(1usize).inv_mod(1usize.into()), None);
+        assert_eq!(
+            Felt::from(1usize).inv_mod(2usize.into()),
+            Some(1usize.into())
+        );
+        assert_eq!(Felt::from(2usize).inv_mod(2usize.into()), None);
+    }
 }
 
 #[cfg(test)]
@@ -607,6 +709,27 @@ mod lowering_tests {
         }
     }
 
+    #[rstest]
+    fn owned_expressions_lower_equally(mul_gate: MulCfg) {
+        let regions = two_regions(&mul_gate);
+        let region = regions.regions()[1];
+        let advice_io = CircuitIO::<Advice>::empty();
+        let instance_io = CircuitIO::<Instance>::empty();
+        let zero = ZeroResolver {};
+
+        for poly in &mul_gate.gates {
+            for row in region.rows() {
+                let scoped = || {
+                    let rr = RegionRow::new(region, row, &advice_io, &instance_io, &zero);
+                    ScopedExpression::from_ref(poly, rr)
+                };
+                let expected: super::IRAexpr = scoped().try_into().unwrap();
+                let owned: super::IRAexpr = scoped().into_owned().try_into().unwrap();
+                assert_eq!(expected, owned);
+            }
+        }
+    }
+
     /// Dummy resolver that always resolves to zero.
     struct ZeroResolver {}
 
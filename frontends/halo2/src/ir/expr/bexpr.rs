@@ -15,6 +15,7 @@ use std::{
 };
 
 /// Represents boolean expressions over some arithmetic expression type A.
+#[cfg_attr(feature = "json-export", derive(serde::Serialize, serde::Deserialize))]
 pub enum IRBexpr<A> {
     /// Literal value for true.
     True,
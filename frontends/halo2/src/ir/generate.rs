@@ -19,26 +19,131 @@ use halo2_frontend_core::{
 
 mod patterns;
 
+pub use patterns::{ConstantZero, FallbackGateRewriter, ZeroExpr};
+
+/// Per-gate overrides of the settings in [`IRGenParams`].
+///
+/// Settings that are not set fall back to the value configured in the params.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GateConfig {
+    constant_folding: Option<bool>,
+    canonicalization: Option<bool>,
+    max_expr_depth: Option<usize>,
+}
+
+impl GateConfig {
+    /// Creates a configuration that does not override any setting.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overrides whether the statements of the gate are constant folded.
+    pub fn with_constant_folding(mut self, enabled: bool) -> Self {
+        self.constant_folding = Some(enabled);
+        self
+    }
+
+    /// Overrides whether the statements of the gate are canonicalized.
+    pub fn with_canonicalization(mut self, enabled: bool) -> Self {
+        self.canonicalization = Some(enabled);
+        self
+    }
+
+    /// Overrides the maximum depth the expressions of the gate can have.
+    pub fn with_max_expr_depth(mut self, depth: usize) -> Self {
+        self.max_expr_depth = Some(depth);
+        self
+    }
+}
+
+/// Settings of a gate after applying its overrides.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct GateSettings {
+    pub gate: String,
+    pub constant_folding: bool,
+    pub canonicalization: bool,
+    pub max_expr_depth: Option<usize>,
+}
+
 /// Configuration parameters for IR generation.
 pub struct IRGenParams<'lc, 'gc, F: Field, E> {
     debug_comments: bool,
     lookup_cb: Option<&'lc dyn LookupCallbacks<F, E>>,
     gate_cb: Option<&'gc dyn GateCallbacks<F, E>>,
+    constant_folding: bool,
+    canonicalization: bool,
+    max_expr_depth: Option<usize>,
+    gate_configs: HashMap<String, GateConfig>,
+    region_filter: Option<Box<RegionFilter>>,
 }
 
+/// Predicate over the regions of the circuit.
+type RegionFilter = dyn Fn(&RegionData) -> bool;
+
 impl<'lc, 'gc, F: Field, E> IRGenParams<'lc, 'gc, F, E> {
     fn new() -> Self {
         Self {
             debug_comments: false,
             lookup_cb: None,
             gate_cb: None,
+            constant_folding: false,
+            canonicalization: false,
+            max_expr_depth: None,
+            gate_configs: Default::default(),
+            region_filter: None,
         }
     }
 
+    /// Creates a builder for the params.
+    pub fn builder() -> IRGenParamsBuilder<'lc, 'gc, F, E> {
+        IRGenParamsBuilder::new()
+    }
+
     /// Returns wether debug comments are enabled or not.
     pub fn debug_comments(&self) -> bool {
         self.debug_comments
     }
+
+    /// Returns whether the statements of the gates are constant folded.
+    pub fn constant_folding(&self) -> bool {
+        self.constant_folding
+    }
+
+    /// Returns whether the statements of the gates are canonicalized.
+    pub fn canonicalization(&self) -> bool {
+        self.canonicalization
+    }
+
+    /// Returns the maximum depth the expressions of the gates can have, if any.
+    pub fn max_expr_depth(&self) -> Option<usize> {
+        self.max_expr_depth
+    }
+
+    /// Returns the overrides configured for the gate.
+    pub fn gate_config(&self, gate: &str) -> Option<&GateConfig> {
+        self.gate_configs.get(gate)
+    }
+
+    /// Sets a filter on the regions whose IR will be generated.
+    ///
+    /// The predicate receives the name and index of the region and works like the filter of
+    /// [`Driver::generate_ir_filtered`](crate::driver::Driver::generate_ir_filtered). If both are
+    /// given, a region must pass both.
+    pub fn with_region_filter(mut self, f: impl Fn(&str, usize) -> bool + 'static) -> Self {
+        self.region_filter = Some(region_filter(f));
+        self
+    }
+
+    /// Returns the settings of the gate with its overrides applied.
+    pub(crate) fn gate_settings(&self, gate: &str) -> GateSettings {
+        let config = self.gate_config(gate).copied().unwrap_or_default();
+        GateSettings {
+            gate: gate.to_owned(),
+            constant_folding: config.constant_folding.unwrap_or(self.constant_folding),
+            canonicalization: config.canonicalization.unwrap_or(self.canonicalization),
+            max_expr_depth: config.max_expr_depth.or(self.max_expr_depth),
+        }
+    }
 }
 
 impl<F: Field, E> Default for IRGenParams<'_, '_, F, E> {
@@ -67,6 +172,18 @@ impl<F: Field, E> std::fmt::Debug for IRGenParams<'_, '_, F, E> {
                     &"default"
                 },
             )
+            .field("constant_folding", &self.constant_folding)
+            .field("canonicalization", &self.canonicalization)
+            .field("max_expr_depth", &self.max_expr_depth)
+            .field("gate_configs", &self.gate_configs)
+            .field(
+                "region_filter",
+                if self.region_filter.is_some() {
+                    &"set"
+                } else {
+                    &"none"
+                },
+            )
             .finish()
     }
 }
@@ -117,17 +234,61 @@ impl<'lc, 'gc, F: Field, E> IRGenParamsBuilder<'lc, 'gc, F, E> {
         self
     }
 
+    /// Sets whether the statements of the gates are constant folded.
+    pub fn with_constant_folding(&mut self, enabled: bool) -> &mut Self {
+        self.0.constant_folding = enabled;
+        self
+    }
+
+    /// Sets whether the statements of the gates are canonicalized.
+    pub fn with_canonicalization(&mut self, enabled: bool) -> &mut Self {
+        self.0.canonicalization = enabled;
+        self
+    }
+
+    /// Sets the maximum depth the expressions of the gates can have. Resolving the IR fails if
+    /// an expression is deeper.
+    pub fn with_max_expr_depth(&mut self, depth: usize) -> &mut Self {
+        self.0.max_expr_depth = Some(depth);
+        self
+    }
+
+    /// Overrides the settings for the gate with the given name.
+    pub fn with_gate_config(&mut self, gate_name: &str, config: GateConfig) -> &mut Self {
+        self.0.gate_configs.insert(gate_name.to_owned(), config);
+        self
+    }
+
+    /// Sets a filter on the regions whose IR will be generated.
+    ///
+    /// See [`IRGenParams::with_region_filter`].
+    pub fn with_region_filter(&mut self, f: impl Fn(&str, usize) -> bool + 'static) -> &mut Self {
+        self.0.region_filter = Some(region_filter(f));
+        self
+    }
+
     /// Creates the params.
     pub fn build(&mut self) -> IRGenParams<'lc, 'gc, F, E> {
         std::mem::take(&mut self.0)
     }
 }
 
+/// Adapts a predicate over the name and index of a region to a [`RegionFilter`]. Regions without
+/// an index don't pass the filter.
+fn region_filter(f: impl Fn(&str, usize) -> bool + 'static) -> Box<RegionFilter> {
+    Box::new(move |region| region.index().is_some_and(|index| f(region.name(), *index)))
+}
+
 /// Generates an intermediate representation of the circuit from its synthesis.
+///
+/// If a region filter is given, either as argument or in the params, only the gates and lookups
+/// of the regions that pass the filter are lowered. The groups still include the rest of the
+/// regions so their indices remain consistent with the full circuit.
 pub(crate) fn generate_ir<'syn, 'ctx, 'cb, 'sco, F, E>(
     syn: &'syn SynthesizedCircuit<F, E>,
-    params: IRGenParams<'cb, '_, F, E>,
+    mut params: IRGenParams<'cb, '_, F, E>,
     ir_ctx: &'ctx IRCtx,
+    region_filter: Option<&dyn Fn(&RegionData) -> bool>,
 ) -> anyhow::Result<Vec<GroupBody<ExprOrTemp<ScopedExpression<'syn, 'sco, F, E>>>>>
 where
     F: PrimeField,
@@ -139,11 +300,22 @@ where
     log::debug!("Circuit synthesis has {} gates", syn.gates().len());
     let patterns = load_patterns(params.gate_cb.unwrap_or(&DefaultGateCallbacks));
     let regions_by_index = region_data(syn);
+    let params_filter = params.region_filter.take();
+    let both_filters = |region: &RegionData| {
+        region_filter.is_none_or(|f| f(region)) && params_filter.as_ref().is_none_or(|f| f(region))
+    };
+    let filter: Option<&dyn Fn(&RegionData) -> bool> = match (region_filter, &params_filter) {
+        (None, None) => None,
+        (Some(f), None) => Some(f),
+        (None, Some(f)) => Some(f.as_ref()),
+        (Some(_), Some(_)) => Some(&both_filters),
+    };
     let ctx = GroupIRCtx {
         regions_by_index,
         syn,
         patterns,
         params,
+        region_filter: filter,
     };
 
     log::debug!("Generating IR of region groups");
@@ -190,14 +362,15 @@ pub(super) fn region_data<'s, F: Field, E>(syn: &'s SynthesizedCircuit<F, E>) ->
 pub(super) type RegionByIndex<'s> = HashMap<RegionIndex, RegionData<'s>>;
 
 /// Support data for creating group body IR structs
-pub(super) struct GroupIRCtx<'lc, 'gc, 'syn, F: Field, E> {
+pub(super) struct GroupIRCtx<'lc, 'gc, 'syn, 'rf, F: Field, E> {
     regions_by_index: RegionByIndex<'syn>,
     syn: &'syn SynthesizedCircuit<F, E>,
     patterns: RewritePatternSet<F, E>,
     params: IRGenParams<'lc, 'gc, F, E>,
+    region_filter: Option<&'rf dyn Fn(&RegionData) -> bool>,
 }
 
-impl<'lc, 'gc, 'syn, F: Field, E> GroupIRCtx<'lc, 'gc, 'syn, F, E> {
+impl<'lc, 'gc, 'syn, F: Field, E> GroupIRCtx<'lc, 'gc, 'syn, '_, F, E> {
     pub(super) fn groups(&self) -> &'syn [Group] {
         self.syn.groups()
     }
@@ -214,6 +387,10 @@ impl<'lc, 'gc, 'syn, F: Field, E> GroupIRCtx<'lc, 'gc, 'syn, F, E> {
         &self.patterns
     }
 
+    pub(super) fn gate_cb(&self) -> &'gc dyn GateCallbacks<F, E> {
+        self.params.gate_cb.unwrap_or(&DefaultGateCallbacks)
+    }
+
     pub(super) fn lookup_cb(&self) -> &'lc dyn LookupCallbacks<F, E>
     where
         E: Clone,
@@ -224,4 +401,47 @@ impl<'lc, 'gc, 'syn, F: Field, E> GroupIRCtx<'lc, 'gc, 'syn, F, E> {
     pub(super) fn generate_debug_comments(&self) -> bool {
         self.params.debug_comments
     }
+
+    pub(super) fn gate_settings(&self, gate: &str) -> GateSettings {
+        self.params.gate_settings(gate)
+    }
+
+    /// Returns the regions of the group whose IR has to be generated.
+    pub(super) fn selected_regions(&self, group: &'syn Group) -> Vec<RegionData<'syn>> {
+        group
+            .regions()
+            .into_iter()
+            .filter(|r| self.region_filter.is_none_or(|f| f(r)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2curves::bn256::Fr;
+
+    #[test]
+    fn gate_config_overrides_global_settings() {
+        let params = IRGenParams::<Fr, ()>::builder()
+            .with_constant_folding(true)
+            .with_max_expr_depth(4)
+            .with_gate_config(
+                "mul",
+                GateConfig::new()
+                    .with_constant_folding(false)
+                    .with_canonicalization(true),
+            )
+            .build();
+
+        let mul = params.gate_settings("mul");
+        assert!(!mul.constant_folding);
+        assert!(mul.canonicalization);
+        assert_eq!(mul.max_expr_depth, Some(4));
+
+        let add = params.gate_settings("add");
+        assert!(add.constant_folding);
+        assert!(!add.canonicalization);
+        assert_eq!(add.max_expr_depth, Some(4));
+    }
 }
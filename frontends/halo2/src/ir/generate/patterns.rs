@@ -10,23 +10,68 @@ use crate::{
 use halo2_frontend_core::expressions::{EvaluableExpr, ExprBuilder};
 use std::{borrow::Cow, result::Result as StdResult};
 
+/// Factory of the expression the fallback rewriter compares the polynomials against.
+pub trait ZeroExpr<F, E> {
+    /// Creates the zero expression.
+    fn zero(&self) -> E;
+}
+
+/// Creates the zero as a constant expression.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ConstantZero;
+
+impl<F: Field, E: ExprBuilder<F>> ZeroExpr<F, E> for ConstantZero {
+    fn zero(&self) -> E {
+        E::constant(F::ZERO)
+    }
+}
+
+impl<F, E, Z: Fn() -> E> ZeroExpr<F, E> for Z {
+    fn zero(&self) -> E {
+        self()
+    }
+}
+
 /// Default gate pattern that transforms each polynomial in a gate into an equality statement for
 /// each row in the region.
-struct FallbackGateRewriter {
+///
+/// Is loaded after the user patterns. Users that need a different zero expression can return a
+/// rewriter configured with [`FallbackGateRewriter::with_zero_expr`] from
+/// [`GateCallbacks::patterns`].
+#[derive(Debug, Clone, Copy)]
+pub struct FallbackGateRewriter<Z = ConstantZero> {
     ignore_disabled_gates: bool,
+    zero: Z,
 }
 
 impl FallbackGateRewriter {
+    /// Creates the rewriter.
     pub fn new(ignore_disabled_gates: bool) -> Self {
         Self {
             ignore_disabled_gates,
+            zero: ConstantZero,
+        }
+    }
+}
+
+impl<Z> FallbackGateRewriter<Z> {
+    /// Uses the given factory to create the right hand side of the emitted constraints instead of
+    /// a constant zero.
+    pub fn with_zero_expr<E, Z2>(self, zero: Z2) -> FallbackGateRewriter<Z2>
+    where
+        Z2: Fn() -> E + 'static,
+    {
+        FallbackGateRewriter {
+            ignore_disabled_gates: self.ignore_disabled_gates,
+            zero,
         }
     }
 }
 
-impl<F, E> GateRewritePattern<F, E> for FallbackGateRewriter
+impl<F, E, Z> GateRewritePattern<F, E> for FallbackGateRewriter<Z>
 where
     E: std::fmt::Debug + EvaluableExpr<F> + ExprBuilder<F>,
+    Z: ZeroExpr<F, E>,
 {
     fn match_gate<'syn>(&self, _gate: GateScope<'syn, '_, F, E>) -> StdResult<(), RewriteError>
     where
@@ -67,7 +112,9 @@ where
                         true
                     })
                     .map(Cow::Borrowed)
-                    .map(|lhs| IRStmt::constraint(CmpOp::Eq, lhs, Cow::Owned(E::constant(F::ZERO))))
+                    .map(move |lhs| {
+                        IRStmt::constraint(CmpOp::Eq, lhs, Cow::Owned(self.zero.zero()))
+                    })
                     .map(move |s| s.map(&|e: Cow<'syn, _>| (row.row_number(), e)))
                 //.collect()
             })
@@ -9,16 +9,20 @@ use crate::{
         lowering::{Lowering, lowerable::LowerableStmt},
     },
     expressions::{ExpressionInRow, ScopedExpression},
-    gates::{Gate, GateRewritePattern as _, GateScope, RewriteError, RewritePatternSet},
+    gates::{
+        Gate, GateCallbacks, GateRewritePattern as _, GateScope, RewriteError, RewriteOutput,
+        RewritePatternSet,
+    },
     ir::{
         CmpOp, IRCtx,
         ctx::AdviceCells,
         equivalency::{EqvRelation, SymbolicEqv},
         expr::{Felt, IRAexpr},
-        generate::{GroupIRCtx, RegionByIndex},
+        generate::{GateSettings, GroupIRCtx, RegionByIndex},
         groups::{
             bounds::{Bound, EqConstraintCheck, GroupBounds},
             callsite::CallSite,
+            validation::ValidationError,
         },
         stmt::IRStmt,
     },
@@ -42,9 +46,11 @@ use halo2_frontend_core::{
 
 pub(crate) mod bounds;
 pub mod callsite;
+pub mod validation;
 
 /// Group's IR
 #[derive(Debug)]
+#[cfg_attr(feature = "json-export", derive(serde::Serialize, serde::Deserialize))]
 pub struct GroupBody<E> {
     name: String,
     /// Index in the original groups array.
@@ -58,6 +64,9 @@ pub struct GroupBody<E> {
     lookups: IRStmt<E>,
     injected: Vec<IRStmt<E>>,
     generate_debug_comments: bool,
+    /// Settings of each statement in `gates`.
+    #[cfg_attr(feature = "json-export", serde(skip))]
+    gate_settings: Vec<GateSettings>,
 }
 
 impl<'cb, 'syn, 'ctx, 'sco, F, E> GroupBody<ExprOrTemp<ScopedExpression<'syn, 'sco, F, E>>>
@@ -71,7 +80,7 @@ where
     pub(super) fn new(
         group: &'syn Group,
         id: usize,
-        ctx: &GroupIRCtx<'cb, '_, 'syn, F, E>,
+        ctx: &GroupIRCtx<'cb, '_, 'syn, '_, F, E>,
         advice_io: &'ctx crate::io::AdviceIO,
         instance_io: &'ctx crate::io::InstanceIO,
     ) -> anyhow::Result<Self>
@@ -90,20 +99,22 @@ where
                 .collect::<Result<Vec<_>, _>>()?
         };
 
+        let regions = ctx.selected_regions(group);
         log::debug!("Lowering gates for group {:?}", group.name());
-        let gates = IRStmt::seq(
-            lower_gates(
-                ctx.syn().gates(),
-                &group.regions(),
-                ctx.patterns(),
-                advice_io,
-                instance_io,
-                ctx.syn().fixed_query_resolver(),
-                ctx.generate_debug_comments(),
-            )?
-            .into_iter()
-            .map(|stmt| stmt.map(&ExprOrTemp::Expr)),
-        );
+        let (gates, gate_settings): (Vec<_>, Vec<_>) = lower_gates(
+            ctx.syn().gates(),
+            &regions,
+            ctx.patterns(),
+            ctx.gate_cb(),
+            advice_io,
+            instance_io,
+            ctx.syn().fixed_query_resolver(),
+            ctx.generate_debug_comments(),
+        )?
+        .into_iter()
+        .map(|(stmt, gate)| (stmt, ctx.gate_settings(gate)))
+        .unzip();
+        let gates = IRStmt::seq(gates.into_iter().map(|stmt| stmt.map(&ExprOrTemp::Expr)));
 
         log::debug!(
             "Lowering inter region equality constraints for group {:?}",
@@ -132,9 +143,17 @@ where
         );
 
         log::debug!("Lowering lookups for group {:?}", group.name());
+        let fqr = ctx.syn().fixed_query_resolver();
+        let region_rows = regions
+            .into_iter()
+            .flat_map(|r| {
+                r.rows()
+                    .map(move |row| RegionRow::new(r, row, advice_io, instance_io, fqr))
+            })
+            .collect::<Vec<_>>();
         let lookups = IRStmt::seq(codegen_lookup_invocations(
             ctx.syn(),
-            &group.region_rows(advice_io, instance_io, ctx.syn().fixed_query_resolver()),
+            &region_rows,
             ctx.lookup_cb(),
             ctx.generate_debug_comments(),
         )?);
@@ -151,6 +170,7 @@ where
             lookups,
             injected: vec![],
             generate_debug_comments: ctx.generate_debug_comments(),
+            gate_settings,
         })
     }
 
@@ -202,6 +222,36 @@ impl GroupBody<IRAexpr> {
             .try_for_each(|s| s.constant_fold(prime))
     }
 
+    /// Applies to the statements of each gate the settings configured in
+    /// [`crate::ir::generate::IRGenParams`].
+    pub(crate) fn apply_gate_settings(&mut self, prime: Felt) -> Result<()> {
+        let IRStmt::Seq(gates) = &mut self.gates else {
+            return Ok(());
+        };
+        for (stmt, settings) in gates.iter_mut().zip(&self.gate_settings) {
+            if settings.constant_folding {
+                stmt.constant_fold(prime)?;
+            }
+            if settings.canonicalization {
+                stmt.canonicalize();
+            }
+            if let Some(max_depth) = settings.max_expr_depth {
+                stmt.try_map_inplace(&|expr: &mut IRAexpr| {
+                    let depth = expr.depth();
+                    if depth > max_depth {
+                        anyhow::bail!(
+                            "Expression of depth {depth} in gate \"{}\" of group \"{}\" exceeds the maximum depth of {max_depth}",
+                            settings.gate,
+                            self.name
+                        );
+                    }
+                    Ok(())
+                })?;
+            }
+        }
+        Ok(())
+    }
+
     /// Matches the statements against a series of known patterns and applies rewrites if able to.
     pub fn canonicalize(&mut self) {
         self.gates.canonicalize();
@@ -323,63 +373,70 @@ impl<E> GroupBody<E> {
                 .map(|i| i.try_map(f))
                 .collect::<Result<Vec<_>, _>>()?,
             generate_debug_comments: self.generate_debug_comments,
+            gate_settings: self.gate_settings,
         })
     }
 
-    fn validate_callsite(&self, callsite: &CallSite<E>, groups: &[GroupBody<E>]) -> Result<()> {
+    /// Validates a callsite, adding to the list any errors found.
+    fn validate_callsite(
+        &self,
+        call_no: usize,
+        callsite: &CallSite<E>,
+        groups: &[GroupBody<E>],
+        errors: &mut Vec<ValidationError>,
+    ) {
+        if callsite.outputs().len() != callsite.output_vars().len() {
+            errors.push(ValidationError::OutputVarsMismatch {
+                call_no,
+                callee: callsite.name().to_owned(),
+                callee_id: callsite.callee_id(),
+                outputs: callsite.outputs().len(),
+                output_vars: callsite.output_vars().len(),
+            });
+        }
         let callee_id = callsite.callee_id();
-        let callee = groups
-            .get(callee_id)
-            .ok_or_else(|| anyhow::anyhow!("Callee with id {callee_id} was not found"))?;
+        let Some(callee) = groups.get(callee_id) else {
+            errors.push(ValidationError::UnresolvedCallee { call_no, callee_id });
+            return;
+        };
         if callee.id() != callsite.callee_id() {
-            anyhow::bail!(
-                "Callsite points to \"{}\" ({}) but callee was \"{}\" ({})",
-                callsite.name(),
-                callsite.callee_id(),
-                callee.name(),
-                callee.id()
-            );
+            errors.push(ValidationError::CalleeMismatch {
+                call_no,
+                expected_name: callsite.name().to_owned(),
+                expected_id: callsite.callee_id(),
+                found_name: callee.name().to_owned(),
+                found_id: callee.id(),
+            });
         }
         if callee.input_count != callsite.inputs().len() {
-            anyhow::bail!(
-                "Callee \"{}\" ({}) was expecting {} inputs but callsite has {}",
-                callee.name(),
-                callee.id(),
-                callee.input_count,
-                callsite.inputs().len()
-            );
+            errors.push(ValidationError::InputCountMismatch {
+                call_no,
+                callee: callee.name().to_owned(),
+                callee_id: callee.id(),
+                expected: callee.input_count,
+                found: callsite.inputs().len(),
+            });
         }
         if callee.output_count != callsite.outputs().len() {
-            anyhow::bail!(
-                "Callee \"{}\" ({}) was expecting {} outputs but callsite has {}",
-                callee.name(),
-                callee.id(),
-                callee.output_count,
-                callsite.outputs().len()
-            );
+            errors.push(ValidationError::OutputCountMismatch {
+                call_no,
+                callee: callee.name().to_owned(),
+                callee_id: callee.id(),
+                expected: callee.output_count,
+                found: callsite.outputs().len(),
+            });
         }
-        if callsite.outputs().len() != callsite.output_vars().len() {
-            anyhow::bail!(
-                "Call to \"{}\" ({}) has {} outputs but declared {} output variables",
-                callsite.name(),
-                callsite.callee_id(),
-                callsite.outputs().len(),
-                callsite.output_vars().len()
-            );
-        }
-
-        Ok(())
     }
 
     /// Validates the IR in the group.
-    pub fn validate(&self, groups: &[GroupBody<E>]) -> (Result<()>, Vec<String>) {
+    ///
+    /// Collects every error found instead of stopping at the first one.
+    pub fn validate(&self, groups: &[GroupBody<E>]) -> (Result<()>, Vec<ValidationError>) {
         let mut errors = vec![];
 
         // Check 1. Consistency of callsites arity.
         for (call_no, callsite) in self.callsites().iter().enumerate() {
-            if let Err(err) = self.validate_callsite(callsite, groups) {
-                errors.push(format!("On callsite {call_no}: {err}"));
-            }
+            self.validate_callsite(call_no, callsite, groups, &mut errors);
         }
 
         // Return errors if any.
@@ -490,6 +547,7 @@ impl<E: Clone> Clone for GroupBody<E> {
             lookups: self.lookups.clone(),
             injected: self.injected.clone(),
             generate_debug_comments: self.generate_debug_comments,
+            gate_settings: self.gate_settings.clone(),
         }
     }
 }
@@ -497,7 +555,7 @@ impl<E: Clone> Clone for GroupBody<E> {
 /// Select the equality constraints that concern this group.
 fn select_equality_constraints<F: Field, E>(
     group: &Group,
-    ctx: &GroupIRCtx<'_, '_, '_, F, E>,
+    ctx: &GroupIRCtx<'_, '_, '_, '_, F, E>,
 ) -> Vec<EqConstraint<F>> {
     let bounds = GroupBounds::new(group, ctx.groups(), ctx.regions_by_index());
 
@@ -655,11 +713,12 @@ fn lower_gates<'sco, 'syn, 'io, F, E>(
     gates: &'syn [Gate<E>],
     regions: &[RegionData<'syn>],
     patterns: &RewritePatternSet<F, E>,
+    gate_cb: &dyn GateCallbacks<F, E>,
     advice_io: &'io crate::io::AdviceIO,
     instance_io: &'io crate::io::InstanceIO,
     fqr: &'syn dyn FixedQueryResolver<F>,
     generate_debug_comments: bool,
-) -> Result<Vec<IRStmt<ScopedExpression<'syn, 'sco, F, E>>>>
+) -> Result<Vec<(IRStmt<ScopedExpression<'syn, 'sco, F, E>>, &'syn str)>>
 where
     'syn: 'sco,
     'io: 'sco + 'syn,
@@ -667,6 +726,7 @@ where
     E: Clone,
 {
     log::debug!("Got {} gates and {} regions", gates.len(), regions.len());
+    log::debug!("Registered patterns: {:?}", patterns.describe());
     utils::product(regions, gates)
         .map(|(r, g)| {
             log::debug!("Lowering gate {} in region {}", g.name(), r.name());
@@ -675,7 +735,7 @@ where
 
             patterns
                 .match_and_rewrite(scope)
-                .map_err(|e| make_error(e, scope))
+                .or_else(|e| make_error(e, scope, gate_cb))
                 .and_then(|stmt| {
                     stmt.try_map(&|(row, expr)| {
                         let rr = scope.region_row(row)?;
@@ -695,6 +755,7 @@ where
                         generate_debug_comments,
                     )
                 })
+                .map(move |stmt| (stmt, g.name()))
         })
         .collect()
 }
@@ -777,25 +838,41 @@ fn prepend_comment<E>(
     [comment, stmt].into_iter().collect()
 }
 
-/// If the rewrite error is [`RewriteError::NoMatch`] returns an error
-/// that the gate in scope did not match any pattern. If it is [`RewriteError::Err`]
-/// forwards the inner error.
+/// If the rewrite error is [`RewriteError::NoMatch`] asks the callbacks what to do with the gate.
+/// If the callbacks accept it the gate is skipped, otherwise returns an error that the gate in
+/// scope did not match any pattern. If it is [`RewriteError::Err`] forwards the inner error.
 #[inline]
-fn make_error<F, E>(e: RewriteError, scope: GateScope<F, E>) -> anyhow::Error
+fn make_error<'syn, F, E>(
+    e: RewriteError,
+    scope: GateScope<'syn, '_, F, E>,
+    gate_cb: &dyn GateCallbacks<F, E>,
+) -> Result<RewriteOutput<'syn, E>>
 where
     F: Field,
 {
     match e {
-        RewriteError::NoMatch => anyhow::anyhow!(
-            "Gate '{}' on region {} '{}' did not match any pattern",
-            scope.gate_name(),
-            scope
-                .region_index()
-                .as_deref()
-                .map(ToString::to_string)
-                .unwrap_or("unk".to_string()),
-            scope.region_name()
-        ),
-        RewriteError::Err(error) => anyhow::anyhow!(error),
+        RewriteError::NoMatch => {
+            gate_cb
+                .on_unmatched_gate(scope.gate_name(), scope.region_name())
+                .map_err(|error| {
+                    error.context(format!(
+                        "Gate '{}' on region {} '{}' did not match any pattern",
+                        scope.gate_name(),
+                        scope
+                            .region_index()
+                            .as_deref()
+                            .map(ToString::to_string)
+                            .unwrap_or("unk".to_string()),
+                        scope.region_name()
+                    ))
+                })?;
+            log::warn!(
+                "Skipping gate '{}' on region '{}' since it did not match any pattern",
+                scope.gate_name(),
+                scope.region_name()
+            );
+            Ok(IRStmt::empty())
+        }
+        RewriteError::Err(error) => Err(anyhow::anyhow!(error)),
     }
 }
@@ -27,6 +27,7 @@ use halo2_frontend_core::expressions::ExprBuilder;
 
 /// Data related to a single callsite
 #[derive(Debug)]
+#[cfg_attr(feature = "json-export", derive(serde::Serialize, serde::Deserialize))]
 pub struct CallSite<E> {
     name: String,
     callee: GroupKey,
@@ -39,7 +40,7 @@ pub struct CallSite<E> {
 
 fn cells_to_exprs<'e, 's, 'syn, 'cb, 'io, F, E>(
     cells: &[GroupCell],
-    ctx: &super::GroupIRCtx<'cb, '_, 'syn, F, E>,
+    ctx: &super::GroupIRCtx<'cb, '_, 'syn, '_, F, E>,
     advice_io: &'io crate::io::AdviceIO,
     instance_io: &'io crate::io::InstanceIO,
 ) -> anyhow::Result<Vec<ExprOrTemp<ScopedExpression<'e, 's, F, E>>>>
@@ -124,7 +125,7 @@ where
     pub(super) fn new(
         callee: &Group,
         callee_id: usize,
-        ctx: &super::GroupIRCtx<'_, '_, 'syn, F, E>,
+        ctx: &super::GroupIRCtx<'_, '_, 'syn, '_, F, E>,
         call_no: usize,
         advice_io: &'ctx crate::io::AdviceIO,
         instance_io: &'ctx crate::io::InstanceIO,
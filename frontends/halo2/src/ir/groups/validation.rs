@@ -0,0 +1,131 @@
+//! Problems found while validating the IR of a group.
+
+use std::fmt;
+
+/// A problem found while validating a group.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// The callsite points to a group that does not exist.
+    UnresolvedCallee {
+        /// Index of the callsite in the group.
+        call_no: usize,
+        /// Id of the called group.
+        callee_id: usize,
+    },
+    /// The callsite points to a group different from the one found at its id.
+    CalleeMismatch {
+        /// Index of the callsite in the group.
+        call_no: usize,
+        /// Name of the group the callsite expects.
+        expected_name: String,
+        /// Id of the group the callsite expects.
+        expected_id: usize,
+        /// Name of the group found.
+        found_name: String,
+        /// Id of the group found.
+        found_id: usize,
+    },
+    /// The callsite does not pass as many inputs as the callee expects.
+    InputCountMismatch {
+        /// Index of the callsite in the group.
+        call_no: usize,
+        /// Name of the called group.
+        callee: String,
+        /// Id of the called group.
+        callee_id: usize,
+        /// Number of inputs of the callee.
+        expected: usize,
+        /// Number of inputs of the callsite.
+        found: usize,
+    },
+    /// The callsite does not receive as many outputs as the callee returns.
+    OutputCountMismatch {
+        /// Index of the callsite in the group.
+        call_no: usize,
+        /// Name of the called group.
+        callee: String,
+        /// Id of the called group.
+        callee_id: usize,
+        /// Number of outputs of the callee.
+        expected: usize,
+        /// Number of outputs of the callsite.
+        found: usize,
+    },
+    /// The callsite's outputs and output variables have different lengths.
+    OutputVarsMismatch {
+        /// Index of the callsite in the group.
+        call_no: usize,
+        /// Name of the called group.
+        callee: String,
+        /// Id of the called group.
+        callee_id: usize,
+        /// Number of outputs of the callsite.
+        outputs: usize,
+        /// Number of output variables of the callsite.
+        output_vars: usize,
+    },
+}
+
+impl ValidationError {
+    /// Returns the index of the callsite the error refers to.
+    pub fn call_no(&self) -> usize {
+        match self {
+            ValidationError::UnresolvedCallee { call_no, .. }
+            | ValidationError::CalleeMismatch { call_no, .. }
+            | ValidationError::InputCountMismatch { call_no, .. }
+            | ValidationError::OutputCountMismatch { call_no, .. }
+            | ValidationError::OutputVarsMismatch { call_no, .. } => *call_no,
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "On callsite {}: ", self.call_no())?;
+        match self {
+            ValidationError::UnresolvedCallee { callee_id, .. } => {
+                write!(f, "Callee with id {callee_id} was not found")
+            }
+            ValidationError::CalleeMismatch {
+                expected_name,
+                expected_id,
+                found_name,
+                found_id,
+                ..
+            } => write!(
+                f,
+                "Callsite points to \"{expected_name}\" ({expected_id}) but callee was \"{found_name}\" ({found_id})"
+            ),
+            ValidationError::InputCountMismatch {
+                callee,
+                callee_id,
+                expected,
+                found,
+                ..
+            } => write!(
+                f,
+                "Callee \"{callee}\" ({callee_id}) was expecting {expected} inputs but callsite has {found}"
+            ),
+            ValidationError::OutputCountMismatch {
+                callee,
+                callee_id,
+                expected,
+                found,
+                ..
+            } => write!(
+                f,
+                "Callee \"{callee}\" ({callee_id}) was expecting {expected} outputs but callsite has {found}"
+            ),
+            ValidationError::OutputVarsMismatch {
+                callee,
+                callee_id,
+                outputs,
+                output_vars,
+                ..
+            } => write!(
+                f,
+                "Call to \"{callee}\" ({callee_id}) has {outputs} outputs but declared {output_vars} output variables"
+            ),
+        }
+    }
+}
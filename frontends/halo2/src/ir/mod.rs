@@ -19,6 +19,7 @@ use stmt::IRStmt;
 
 /// Comparison operators between arithmetic expressions.
 #[derive(Copy, Clone, PartialEq, Eq, Debug, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "json-export", derive(serde::Serialize, serde::Deserialize))]
 pub enum CmpOp {
     /// Equality
     Eq,
@@ -34,6 +35,36 @@ pub enum CmpOp {
     Ne,
 }
 
+impl CmpOp {
+    /// Returns the operator that negates the comparison.
+    pub const fn inverse(self) -> Self {
+        match self {
+            CmpOp::Eq => CmpOp::Ne,
+            CmpOp::Lt => CmpOp::Ge,
+            CmpOp::Le => CmpOp::Gt,
+            CmpOp::Gt => CmpOp::Le,
+            CmpOp::Ge => CmpOp::Lt,
+            CmpOp::Ne => CmpOp::Eq,
+        }
+    }
+
+    /// Returns the operator that yields the same comparison when the operands are swapped.
+    pub const fn flip(self) -> Self {
+        match self {
+            CmpOp::Lt => CmpOp::Gt,
+            CmpOp::Le => CmpOp::Ge,
+            CmpOp::Gt => CmpOp::Lt,
+            CmpOp::Ge => CmpOp::Le,
+            op @ (CmpOp::Eq | CmpOp::Ne) => op,
+        }
+    }
+
+    /// Returns `true` if swapping the operands does not change the comparison.
+    pub const fn is_symmetric(self) -> bool {
+        matches!(self, CmpOp::Eq | CmpOp::Ne)
+    }
+}
+
 impl std::fmt::Display for CmpOp {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -53,6 +84,7 @@ impl std::fmt::Display for CmpOp {
 
 mod canon;
 mod ctx;
+pub mod diff;
 pub mod equivalency;
 pub mod expr;
 pub mod generate;
@@ -108,8 +140,14 @@ where
         let regions = region_data(syn);
         for (index, stmt) in ir {
             let index = index.into();
+            let Some(group_idx) = self.regions_to_groups.get(*index).copied() else {
+                anyhow::bail!(
+                    "Region index {} out of bounds (max {})",
+                    *index,
+                    self.regions_to_groups.len()
+                );
+            };
             let region = regions[&index];
-            let group_idx = self.regions_to_groups[*index];
             self.groups[group_idx].inject_ir(
                 region,
                 stmt,
@@ -133,6 +171,7 @@ where
             .collect::<Result<Vec<_>, _>>()?;
         for group in &mut groups {
             group.relativize_eq_constraints(self.ctx)?;
+            group.apply_gate_settings(Felt::prime::<F>())?;
         }
         Ok(ResolvedIRCircuit {
             prime: Felt::prime::<F>(),
@@ -171,6 +210,7 @@ where
 /// Circuit that has resolved its expressions and is no longer tied to the lifetime of the
 /// synthesis and is not parametrized on a prime field.
 #[derive(Debug)]
+#[cfg_attr(feature = "json-export", derive(serde::Serialize, serde::Deserialize))]
 pub struct ResolvedIRCircuit {
     prime: Felt,
     ctx: IRCtx,
@@ -193,6 +233,34 @@ impl ResolvedIRCircuit {
         IRPrinter::from_circuit(self)
     }
 
+    /// Returns a GraphViz DOT graph of the circuit generated with the default parameters of the
+    /// DOT backend.
+    #[cfg(feature = "dot-backend")]
+    pub fn to_dot(&self) -> Result<String> {
+        use crate::backend::dot::{DotBackend, DotParams};
+
+        DotBackend::initialize(DotParams::default()).codegen(self, self.ctx())
+    }
+
+    /// Returns the structural differences between two circuits.
+    pub fn diff(a: &ResolvedIRCircuit, b: &ResolvedIRCircuit) -> diff::CircuitDiff {
+        diff::CircuitDiff::new(a, b)
+    }
+
+    /// Serializes the circuit into JSON.
+    ///
+    /// Field elements are encoded as hexadecimal strings.
+    #[cfg(feature = "json-export")]
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Deserializes a circuit from the JSON generated by [`ResolvedIRCircuit::to_json`].
+    #[cfg(feature = "json-export")]
+    pub fn from_json(s: &str) -> Result<Self> {
+        Ok(serde_json::from_str(s)?)
+    }
+
     /// Returns the main group.
     ///
     /// Panics if there isn't a main group.
@@ -253,3 +321,35 @@ impl ResolvedIRCircuit {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::CmpOp;
+
+    const OPS: [CmpOp; 6] = [
+        CmpOp::Eq,
+        CmpOp::Lt,
+        CmpOp::Le,
+        CmpOp::Gt,
+        CmpOp::Ge,
+        CmpOp::Ne,
+    ];
+
+    #[test]
+    fn inverse_and_flip_are_involutions() {
+        for op in OPS {
+            assert_eq!(op.inverse().inverse(), op);
+            assert_ne!(op.inverse(), op);
+            assert_eq!(op.flip().flip(), op);
+            assert_eq!(op.flip() == op, op.is_symmetric());
+        }
+        assert_eq!(CmpOp::Lt.inverse(), CmpOp::Ge);
+        assert_eq!(CmpOp::Le.flip(), CmpOp::Ge);
+    }
+
+    #[test]
+    fn only_equalities_are_symmetric() {
+        let symmetric: Vec<_> = OPS.into_iter().filter(|op| op.is_symmetric()).collect();
+        assert_eq!(symmetric, vec![CmpOp::Eq, CmpOp::Ne]);
+    }
+}
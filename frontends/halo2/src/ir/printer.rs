@@ -2,10 +2,12 @@
 
 use std::fmt::{Display, Formatter, Result as FmtResult, Write};
 
+use halo2_frontend_core::table::{Any, Column};
+
 use crate::{
-    backend::func::FuncIO,
+    backend::func::{CellRef, FuncIO},
     ir::{
-        ResolvedIRCircuit,
+        IRCtx, ResolvedIRCircuit,
         expr::{IRAexpr, IRBexpr},
         groups::GroupBody,
         stmt::IRStmt,
@@ -27,32 +29,71 @@ enum IRPrinterCapture<'a> {
 /// relied upon as it may change unexpectedly. The purpose of the printer is to be a debugging aid
 /// for inspecting the shape of the IR and not a serialization/deserialization format.
 #[derive(Debug, Copy, Clone)]
-pub struct IRPrinter<'a>(IRPrinterCapture<'a>);
+pub struct IRPrinter<'a>(IRPrinterCapture<'a>, Option<&'a IRCtx>);
 
 impl<'a> IRPrinter<'a> {
     /// Creates a printer from a [`ResolvedIRCircuit`].
     pub fn from_circuit(c: &'a ResolvedIRCircuit) -> Self {
-        Self(IRPrinterCapture::Circuit(c))
+        Self(IRPrinterCapture::Circuit(c), None)
     }
 
     /// Creates a printer from a [`GroupBody`].
     pub fn from_group(c: &'a GroupBody<IRAexpr>) -> Self {
-        Self(IRPrinterCapture::Group(c))
+        Self(IRPrinterCapture::Group(c), None)
     }
 
     /// Creates a printer from a [`IRStmt`].
     pub fn from_stmt(c: &'a IRStmt<IRAexpr>) -> Self {
-        Self(IRPrinterCapture::Stmt(c))
+        Self(IRPrinterCapture::Stmt(c), None)
     }
 
     /// Creates a printer from a [`IRBexpr`].
     pub fn from_bexpr(c: &'a IRBexpr<IRAexpr>) -> Self {
-        Self(IRPrinterCapture::Bexpr(c))
+        Self(IRPrinterCapture::Bexpr(c), None)
     }
 
     /// Creates a printer from a [`IRAexpr`].
     pub fn from_aexpr(c: &'a IRAexpr) -> Self {
-        Self(IRPrinterCapture::Aexpr(c))
+        Self(IRPrinterCapture::Aexpr(c), None)
+    }
+
+    /// Prints the annotations attached to the cells during synthesis as comments after each
+    /// statement that references an annotated cell.
+    ///
+    /// Only has effect when printing a whole circuit since the annotations are stored in its
+    /// context.
+    pub fn with_cell_annotations(mut self, enabled: bool) -> Self {
+        self.1 = match self.0 {
+            IRPrinterCapture::Circuit(circuit) if enabled => Some(circuit.ctx()),
+            _ => None,
+        };
+        self
+    }
+
+    /// Records the annotation of the cell, if any, to be printed after the current statement.
+    fn add_cell_annotation(&self, cell_ref: &CellRef, column_type: Any, ctx: &mut IRPrinterCtx) {
+        let Some(annotation) = self.1.and_then(|ir_ctx| {
+            ir_ctx.cell_annotation(Column::new(cell_ref.col(), column_type), cell_ref.row())
+        }) else {
+            return;
+        };
+        let atom = match column_type {
+            Any::Advice => "advice",
+            Any::Fixed => "fixed",
+            Any::Instance => "instance",
+        };
+        let annotation = format!("({atom} {cell_ref}) {annotation}");
+        if !ctx.annotations.contains(&annotation) {
+            ctx.annotations.push(annotation);
+        }
+    }
+
+    /// Prints the annotations recorded while printing the last statement as comments.
+    fn fmt_cell_annotations(&self, ctx: &mut IRPrinterCtx) -> FmtResult {
+        for annotation in std::mem::take(&mut ctx.annotations) {
+            writeln!(ctx, "; {annotation}")?;
+        }
+        Ok(())
     }
 
     fn fmt_circuit(&self, circuit: &ResolvedIRCircuit, ctx: &mut IRPrinterCtx) -> FmtResult {
@@ -86,11 +127,13 @@ impl<'a> IRPrinter<'a> {
                     ctx,
                 )?;
                 ctx.nl()?;
+                self.fmt_cell_annotations(ctx)?;
             }
 
             for stmt in group.statements() {
                 self.fmt_stmt(stmt, ctx)?;
                 ctx.nl()?;
+                self.fmt_cell_annotations(ctx)?;
             }
 
             Ok(())
@@ -137,9 +180,13 @@ impl<'a> IRPrinter<'a> {
             FuncIO::Arg(arg_no) => write!(ctx, "(input {arg_no})"),
             FuncIO::Field(field_id) => write!(ctx, "(output {field_id})"),
             FuncIO::Advice(cell_ref) => {
+                self.add_cell_annotation(cell_ref, Any::Advice, ctx);
                 write!(ctx, "(advice {cell_ref})")
             }
-            FuncIO::Fixed(cell_ref) => write!(ctx, "(fixed {cell_ref})"),
+            FuncIO::Fixed(cell_ref) => {
+                self.add_cell_annotation(cell_ref, Any::Fixed, ctx);
+                write!(ctx, "(fixed {cell_ref})")
+            }
             FuncIO::TableLookup(id, col, row, idx, region) => {
                 write!(ctx, "(lookup {id} {col} {row} {idx} {region})")
             }
@@ -384,6 +431,7 @@ struct IRPrinterCtx<'a, 'f> {
     f: &'a mut Formatter<'f>,
     indent: Vec<usize>,
     indent_pending: bool,
+    annotations: Vec<String>,
 }
 
 impl<'a, 'f> IRPrinterCtx<'a, 'f> {
@@ -392,6 +440,7 @@ impl<'a, 'f> IRPrinterCtx<'a, 'f> {
             f,
             indent: vec![],
             indent_pending: true,
+            annotations: vec![],
         }
     }
 
@@ -21,6 +21,7 @@ mod comment;
 mod constraint;
 mod post_cond;
 mod seq;
+mod visitor;
 
 use assert::Assert;
 use assume_determ::AssumeDeterministic;
@@ -29,8 +30,10 @@ use comment::Comment;
 use constraint::Constraint;
 use post_cond::PostCond;
 use seq::Seq;
+pub use visitor::{IrStmtRewriter, IrStmtVisitor};
 
 /// IR for operations that occur in the main circuit.
+#[cfg_attr(feature = "json-export", derive(serde::Serialize, serde::Deserialize))]
 pub enum IRStmt<T> {
     /// A call to another module.
     ConstraintCall(Call<T>),
@@ -56,16 +59,28 @@ impl<T: PartialEq> PartialEq for IRStmt<T> {
     ///     Seq([a, Seq([b, c])]) == Seq([a, b, c])
     ///     a == Seq([a])
     fn eq(&self, other: &Self) -> bool {
-        std::iter::zip(self.iter(), other.iter()).all(|(lhs, rhs)| match (lhs, rhs) {
-            (IRStmt::ConstraintCall(lhs), IRStmt::ConstraintCall(rhs)) => lhs.eq(rhs),
-            (IRStmt::Constraint(lhs), IRStmt::Constraint(rhs)) => lhs.eq(rhs),
-            (IRStmt::Comment(lhs), IRStmt::Comment(rhs)) => lhs.eq(rhs),
-            (IRStmt::AssumeDeterministic(lhs), IRStmt::AssumeDeterministic(rhs)) => lhs.eq(rhs),
-            (IRStmt::Assert(lhs), IRStmt::Assert(rhs)) => lhs.eq(rhs),
-            (IRStmt::PostCond(lhs), IRStmt::PostCond(rhs)) => lhs.eq(rhs),
-            (IRStmt::Seq(_), _) | (_, IRStmt::Seq(_)) => unreachable!(),
-            _ => false,
-        })
+        let (mut lhs, mut rhs) = (self.iter(), other.iter());
+        loop {
+            let eq = match (lhs.next(), rhs.next()) {
+                (None, None) => return true,
+                (Some(IRStmt::ConstraintCall(lhs)), Some(IRStmt::ConstraintCall(rhs))) => {
+                    lhs.eq(rhs)
+                }
+                (Some(IRStmt::Constraint(lhs)), Some(IRStmt::Constraint(rhs))) => lhs.eq(rhs),
+                (Some(IRStmt::Comment(lhs)), Some(IRStmt::Comment(rhs))) => lhs.eq(rhs),
+                (
+                    Some(IRStmt::AssumeDeterministic(lhs)),
+                    Some(IRStmt::AssumeDeterministic(rhs)),
+                ) => lhs.eq(rhs),
+                (Some(IRStmt::Assert(lhs)), Some(IRStmt::Assert(rhs))) => lhs.eq(rhs),
+                (Some(IRStmt::PostCond(lhs)), Some(IRStmt::PostCond(rhs))) => lhs.eq(rhs),
+                (Some(IRStmt::Seq(_)), _) | (_, Some(IRStmt::Seq(_))) => unreachable!(),
+                _ => false,
+            };
+            if !eq {
+                return false;
+            }
+        }
     }
 }
 
@@ -219,6 +234,40 @@ impl<T> IRStmt<T> {
         }
     }
 
+    /// Calls the visitor on each statement in the tree.
+    pub fn visit<V: IrStmtVisitor<T>>(&self, visitor: &mut V) {
+        for stmt in self.iter() {
+            match stmt {
+                IRStmt::ConstraintCall(_) => visitor.visit_call(stmt),
+                IRStmt::Constraint(_) => visitor.visit_constraint(stmt),
+                IRStmt::Comment(_) => visitor.visit_comment(stmt),
+                IRStmt::AssumeDeterministic(_) => visitor.visit_assume_deterministic(stmt),
+                IRStmt::Assert(_) => visitor.visit_assert(stmt),
+                IRStmt::PostCond(_) => visitor.visit_post_cond(stmt),
+                IRStmt::Seq(_) => unreachable!(),
+            }
+        }
+    }
+
+    /// Transforms the statement tree using the rewriter.
+    pub fn rewrite<R: IrStmtRewriter<T>>(self, rewriter: &mut R) -> IRStmt<T> {
+        match self {
+            IRStmt::ConstraintCall(_) => rewriter.rewrite_call(self),
+            IRStmt::Constraint(_) => rewriter.rewrite_constraint(self),
+            IRStmt::Comment(_) => rewriter.rewrite_comment(self),
+            IRStmt::AssumeDeterministic(_) => rewriter.rewrite_assume_deterministic(self),
+            IRStmt::Assert(_) => rewriter.rewrite_assert(self),
+            IRStmt::PostCond(_) => rewriter.rewrite_post_cond(self),
+            IRStmt::Seq(seq) => {
+                let seq = seq
+                    .into_iter()
+                    .map(|s| s.rewrite(rewriter))
+                    .collect::<Seq<_>>();
+                rewriter.rewrite_seq(seq.into())
+            }
+        }
+    }
+
     /// Returns an iterator of references to the statements.
     pub fn iter<'a>(&'a self) -> IRStmtRefIter<'a, T> {
         IRStmtRefIter { stack: vec![self] }
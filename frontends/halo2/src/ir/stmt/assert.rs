@@ -12,6 +12,7 @@ use crate::{
     },
 };
 
+#[cfg_attr(feature = "json-export", derive(serde::Serialize, serde::Deserialize))]
 pub struct Assert<T>(IRBexpr<T>);
 
 impl<T> Assert<T> {
@@ -8,6 +8,7 @@ use crate::{
     ir::equivalency::EqvRelation,
 };
 
+#[cfg_attr(feature = "json-export", derive(serde::Serialize, serde::Deserialize))]
 pub struct AssumeDeterministic(FuncIO);
 
 impl AssumeDeterministic {
@@ -14,6 +14,7 @@ use crate::{
     },
 };
 
+#[cfg_attr(feature = "json-export", derive(serde::Serialize, serde::Deserialize))]
 pub struct Call<I> {
     callee: String,
     inputs: Vec<I>,
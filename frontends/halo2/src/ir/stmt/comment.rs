@@ -2,6 +2,7 @@ use anyhow::Result;
 
 use crate::backend::lowering::{Lowering, lowerable::LowerableStmt};
 
+#[cfg_attr(feature = "json-export", derive(serde::Serialize, serde::Deserialize))]
 pub struct Comment(String);
 
 impl Comment {
@@ -14,6 +14,7 @@ use crate::{
     },
 };
 
+#[cfg_attr(feature = "json-export", derive(serde::Serialize, serde::Deserialize))]
 pub struct Constraint<T> {
     op: CmpOp,
     lhs: T,
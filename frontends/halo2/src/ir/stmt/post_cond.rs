@@ -12,6 +12,7 @@ use crate::{
     },
 };
 
+#[cfg_attr(feature = "json-export", derive(serde::Serialize, serde::Deserialize))]
 pub struct PostCond<T>(IRBexpr<T>);
 
 impl<T> PostCond<T> {
@@ -10,6 +10,7 @@ use crate::{
 
 use super::IRStmt;
 
+#[cfg_attr(feature = "json-export", derive(serde::Serialize, serde::Deserialize))]
 pub struct Seq<T>(Vec<IRStmt<T>>);
 
 impl<T> Seq<T> {
@@ -86,6 +87,12 @@ impl<T> IntoIterator for Seq<T> {
     }
 }
 
+impl<T> FromIterator<IRStmt<T>> for Seq<T> {
+    fn from_iter<I: IntoIterator<Item = IRStmt<T>>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
 impl<T: PartialEq> PartialEq for Seq<T> {
     fn eq(&self, other: &Self) -> bool {
         self.0 == other.0
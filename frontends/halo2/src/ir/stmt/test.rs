@@ -20,6 +20,95 @@ fn iterator_nested_seqs() {
     assert_eq!(expected, output);
 }
 
+#[test]
+fn visitor_counts_constraints() {
+    #[derive(Default)]
+    struct Counter(usize);
+
+    impl IrStmtVisitor<()> for Counter {
+        fn visit_constraint(&mut self, constraint: &S) {
+            assert!(matches!(constraint, IRStmt::Constraint(_)));
+            self.0 += 1;
+        }
+    }
+
+    let stmt = S::seq([
+        S::constraint(CmpOp::Eq, (), ()),
+        S::comment("nested"),
+        S::seq([S::constraint(CmpOp::Lt, (), ())]),
+    ]);
+    let mut counter = Counter::default();
+    stmt.visit(&mut counter);
+    assert_eq!(counter.0, 2);
+}
+
+#[test]
+fn rewriter_removes_comments() {
+    struct RemoveComments;
+
+    impl IrStmtRewriter<()> for RemoveComments {
+        fn rewrite_comment(&mut self, _: S) -> S {
+            S::empty()
+        }
+    }
+
+    let stmt = S::seq([
+        S::comment("first"),
+        S::constraint(CmpOp::Eq, (), ()),
+        S::seq([S::comment("second"), S::constraint(CmpOp::Lt, (), ())]),
+    ]);
+    let expected = S::seq([
+        S::constraint(CmpOp::Eq, (), ()),
+        S::constraint(CmpOp::Lt, (), ()),
+    ]);
+    let rewritten = stmt.rewrite(&mut RemoveComments);
+    assert_eq!(rewritten, expected);
+}
+
+#[test]
+fn sequences_of_different_length_are_not_equal() {
+    let a = || S::constraint(CmpOp::Eq, (), ());
+    let b = || S::comment("b");
+    assert_ne!(S::seq([a()]), S::seq([a(), b()]));
+    assert_ne!(S::seq([a(), b()]), S::seq([a()]));
+    assert_ne!(S::empty(), a());
+    assert_eq!(S::seq([a(), S::seq([b()])]), S::seq([a(), b()]));
+    assert_eq!(a(), S::seq([a()]));
+}
+
+#[cfg(feature = "json-export")]
+#[test]
+fn json_round_trip() {
+    let cell = || IRAexpr::IO(FuncIO::advice_abs(0, 1));
+    let stmt: IRStmt<IRAexpr> = IRStmt::seq([
+        IRStmt::constraint(
+            CmpOp::Eq,
+            IRAexpr::Product(Box::new(cell()), Box::new(cell())),
+            IRAexpr::Constant(Felt::from(255usize)),
+        ),
+        IRStmt::comment("squared"),
+        IRStmt::assert(IRBexpr::Cmp(
+            CmpOp::Lt,
+            cell(),
+            IRAexpr::Negated(Box::new(IRAexpr::IO(FuncIO::Arg(0.into())))),
+        )),
+        IRStmt::call("f", [cell()], [FuncIO::CallOutput(0, 0)]),
+    ]);
+    let json = serde_json::to_string(&stmt).unwrap();
+    assert!(json.contains("\"0xff\""));
+    let parsed: IRStmt<IRAexpr> = serde_json::from_str(&json).unwrap();
+    assert_eq!(stmt, parsed);
+}
+
+#[cfg(feature = "json-export")]
+#[test]
+fn json_field_elements_are_prefixed() {
+    let felt: Felt = serde_json::from_str("\"0xff\"").unwrap();
+    assert_eq!(felt, Felt::from(255usize));
+    assert!(serde_json::from_str::<Felt>("\"ff\"").is_err());
+    assert!(serde_json::from_str::<Felt>("\"0x\"").is_err());
+}
+
 pub struct TestHelper<T, O> {
     factory: Box<dyn ConstraintFactory<Inner = T, Out = O>>,
 }
@@ -0,0 +1,72 @@
+//! Traits for traversing and transforming the statement tree.
+
+use super::IRStmt;
+
+/// Visits the statements of an [`IRStmt`] tree.
+///
+/// Sequences are traversed in order, so the methods are only called on the leaf statements. Each
+/// method receives the statement of the variant it is named after. By default each method does
+/// nothing.
+#[allow(unused_variables)]
+pub trait IrStmtVisitor<T> {
+    /// Called on each [`IRStmt::ConstraintCall`].
+    fn visit_call(&mut self, call: &IRStmt<T>) {}
+
+    /// Called on each [`IRStmt::Constraint`].
+    fn visit_constraint(&mut self, constraint: &IRStmt<T>) {}
+
+    /// Called on each [`IRStmt::Comment`].
+    fn visit_comment(&mut self, comment: &IRStmt<T>) {}
+
+    /// Called on each [`IRStmt::AssumeDeterministic`].
+    fn visit_assume_deterministic(&mut self, assume_deterministic: &IRStmt<T>) {}
+
+    /// Called on each [`IRStmt::Assert`].
+    fn visit_assert(&mut self, assert: &IRStmt<T>) {}
+
+    /// Called on each [`IRStmt::PostCond`].
+    fn visit_post_cond(&mut self, post_cond: &IRStmt<T>) {}
+}
+
+/// Rewrites the statements of an [`IRStmt`] tree.
+///
+/// Each method receives by value a statement of the variant it is named after and returns the
+/// statement that replaces it. Returning [`IRStmt::empty`] removes the statement and returning a
+/// sequence splices its statements in place. The statements inside a sequence are rewritten
+/// before the sequence itself. By default each method returns the statement unchanged.
+pub trait IrStmtRewriter<T> {
+    /// Rewrites an [`IRStmt::ConstraintCall`].
+    fn rewrite_call(&mut self, call: IRStmt<T>) -> IRStmt<T> {
+        call
+    }
+
+    /// Rewrites an [`IRStmt::Constraint`].
+    fn rewrite_constraint(&mut self, constraint: IRStmt<T>) -> IRStmt<T> {
+        constraint
+    }
+
+    /// Rewrites an [`IRStmt::Comment`].
+    fn rewrite_comment(&mut self, comment: IRStmt<T>) -> IRStmt<T> {
+        comment
+    }
+
+    /// Rewrites an [`IRStmt::AssumeDeterministic`].
+    fn rewrite_assume_deterministic(&mut self, assume_deterministic: IRStmt<T>) -> IRStmt<T> {
+        assume_deterministic
+    }
+
+    /// Rewrites an [`IRStmt::Assert`].
+    fn rewrite_assert(&mut self, assert: IRStmt<T>) -> IRStmt<T> {
+        assert
+    }
+
+    /// Rewrites an [`IRStmt::PostCond`].
+    fn rewrite_post_cond(&mut self, post_cond: IRStmt<T>) -> IRStmt<T> {
+        post_cond
+    }
+
+    /// Rewrites an [`IRStmt::Seq`] whose statements have already been rewritten.
+    fn rewrite_seq(&mut self, seq: IRStmt<T>) -> IRStmt<T> {
+        seq
+    }
+}
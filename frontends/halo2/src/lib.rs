@@ -17,6 +17,11 @@ pub mod temps;
 mod utils;
 
 pub use crate::io::{AdviceIO, InstanceIO};
+#[cfg(feature = "dot-backend")]
+pub use backend::dot::{
+    DotOutput,
+    params::{DotParams, DotParamsBuilder},
+};
 #[cfg(feature = "llzk-backend")]
 pub use backend::llzk::{
     LlzkOutput,
@@ -24,14 +29,24 @@ pub use backend::llzk::{
 };
 #[cfg(feature = "picus-backend")]
 pub use backend::picus::{
-    PicusOutput,
-    params::{PicusParams, PicusParamsBuilder},
+    OptimizationDelta, PicusOutput, PicusOutputStats, optimization_delta,
+    params::{PicusParams, PicusParamsBuilder, PicusParamsError},
+};
+#[cfg(feature = "smtlib2-backend")]
+pub use backend::smtlib2::{
+    SmtLib2Output,
+    params::{SmtLib2Params, SmtLib2ParamsBuilder},
 };
+pub use backend::{codegen::CodegenProgress, estimate::BackendEstimate};
 use ff::Field;
+pub use gates::PatternDescription;
 use halo2_frontend_core::info_traits::ConstraintSystemInfo;
 pub use io::CircuitIO;
 pub use lookups::callbacks::LookupCallbacks;
-pub use synthesis::Synthesizer;
+pub use synthesis::{
+    SynthesizedCircuit, Synthesizer,
+    errors::{RegionOverlap, SynthesisError, SynthesisErrors},
+};
 
 /// Implementations of this trait define how a circuit is synthesized.
 ///
@@ -48,7 +63,10 @@ pub trait CircuitSynthesis<F: Field> {
     /// The type of the circuit.
     type Circuit;
     /// Should be the same type as the circuit config.
-    type Config;
+    ///
+    /// Must be [`Clone`] since the driver keeps a copy of it for [`CircuitSynthesis::validate`]
+    /// after the synthesis consumes it.
+    type Config: Clone;
     /// Type of the constraint system.
     type CS: ConstraintSystemInfo<F> + Default + 'static;
     /// Error type for synthesis.
@@ -77,4 +95,16 @@ pub trait CircuitSynthesis<F: Field> {
         synthesizer: &mut Synthesizer<F>,
         cs: &Self::CS,
     ) -> Result<(), Self::Error>;
+
+    /// Validates the result of the synthesis of the circuit.
+    ///
+    /// Called by the [`driver::Driver`] after the synthesis has completed and allows
+    /// implementations to check invariants about the circuit. Returning an error aborts the
+    /// synthesis. By default does nothing.
+    fn validate(
+        _config: &Self::Config,
+        _synthesis: &SynthesizedCircuit<F, <Self::CS as ConstraintSystemInfo<F>>::Polynomial>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
 }
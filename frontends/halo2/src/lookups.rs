@@ -11,12 +11,13 @@ pub mod callbacks;
 pub mod table;
 
 /// Defines a lookup as a list of pairs of expressions.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Lookup<E> {
     name: String,
     idx: usize,
     inputs: Vec<E>,
     table: Vec<E>,
+    multiplicity: Option<E>,
 }
 
 impl<E> Lookup<E> {
@@ -28,11 +29,11 @@ impl<E> Lookup<E> {
         cs.lookups()
             .iter()
             .enumerate()
-            .map(|(idx, a)| Self::new(idx, a.name, a.arguments, a.table))
+            .map(|(idx, a)| Self::new(idx, a.name, a.arguments, a.table, a.multiplicity))
             .collect()
     }
 
-    fn new(idx: usize, name: &str, inputs: &[E], table: &[E]) -> Self
+    fn new(idx: usize, name: &str, inputs: &[E], table: &[E], multiplicity: Option<&E>) -> Self
     where
         E: Clone,
     {
@@ -41,6 +42,7 @@ impl<E> Lookup<E> {
             name: name.to_string(),
             inputs: inputs.to_vec(),
             table: table.to_vec(),
+            multiplicity: multiplicity.cloned(),
         }
     }
 
@@ -64,6 +66,13 @@ impl<E> Lookup<E> {
         &self.inputs
     }
 
+    /// Returns the expression of the multiplicity polynomial.
+    ///
+    /// Returns `None` for Plookup-style lookups that don't track multiplicities.
+    pub fn multiplicity_expression(&self) -> Option<&E> {
+        self.multiplicity.as_ref()
+    }
+
     /// Returns the queries to the lookup table.
     pub fn table_queries(&self) -> Result<Vec<E::FixedQuery>>
     where
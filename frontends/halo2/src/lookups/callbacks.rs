@@ -50,6 +50,9 @@ pub trait LookupCallbacks<F: Field, E> {
     }
 
     /// Called on each lookup the circuit defines.
+    ///
+    /// By default the lookup is ignored and only a comment is emitted in its place.
+    #[allow(unused_variables)]
     fn on_lookup<'syn>(
         &self,
         lookup: &'syn Lookup<E>,
@@ -57,9 +60,25 @@ pub trait LookupCallbacks<F: Field, E> {
         temps: &mut Temps,
     ) -> Result<IRStmt<ExprOrTemp<Cow<'syn, E>>>>
     where
-        E: Clone;
+        E: Clone,
+    {
+        Ok(IRStmt::comment("Lookup ignored"))
+    }
 }
 
+impl<F: Field, E> dyn LookupCallbacks<F, E> {
+    /// Returns callbacks that ignore every lookup in the circuit.
+    pub fn ignore() -> IgnoreLookups {
+        IgnoreLookups
+    }
+}
+
+/// Callbacks that ignore the lookups, emitting only a comment for each one.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct IgnoreLookups;
+
+impl<F: Field, E> LookupCallbacks<F, E> for IgnoreLookups {}
+
 pub(crate) struct DefaultLookupCallbacks;
 
 impl<F: Field, E: Clone> LookupCallbacks<F, E> for DefaultLookupCallbacks {
@@ -1,8 +1,13 @@
 //! Types related to the tables read by lookups.
 
 use anyhow::{Error, Result};
-use ff::Field;
-use std::{cell::LazyCell, ops::Index};
+use ff::{Field, PrimeField};
+use std::{
+    cell::LazyCell,
+    collections::HashSet,
+    hash::{Hash, Hasher},
+    ops::Index,
+};
 
 use halo2_frontend_core::{info_traits::QueryInfo, query::Fixed};
 
@@ -51,7 +56,7 @@ impl<F, FN: FnOnce() -> LookupTableBox<F>> std::fmt::Debug for LazyLookupTableGe
 
 /// Represents a row in the lookup table that can be indexed by the columns participating in the
 /// lookup.
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct LookupTableRow<F> {
     // Maps the n-th index of the slice to the n-th column
     columns: Vec<usize>,
@@ -67,6 +72,15 @@ impl<F: Copy> LookupTableRow<F> {
     }
 }
 
+impl<F: PrimeField> Hash for LookupTableRow<F> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.columns.hash(state);
+        for value in &self.table {
+            value.to_repr().as_ref().hash(state);
+        }
+    }
+}
+
 impl<F> LookupTableRow<F> {
     fn col_to_index(&self, col: usize) -> Option<usize> {
         self.columns.iter().find(|c| **c == col).copied()
@@ -86,3 +100,48 @@ impl<F, Q: QueryInfo<Kind = Fixed>> Index<Q> for LookupTableRow<F> {
         &self.table[index]
     }
 }
+
+/// Removes the tables that are structurally identical to a previous one, keeping the first
+/// occurrence of each.
+pub fn deduplicate_tables<F: PrimeField>(
+    tables: Vec<Vec<LookupTableRow<F>>>,
+) -> Vec<Vec<LookupTableRow<F>>> {
+    let mut seen = HashSet::new();
+    let first_seen = tables.iter().map(|t| seen.insert(t)).collect::<Vec<_>>();
+    tables
+        .into_iter()
+        .zip(first_seen)
+        .filter_map(|(table, first)| first.then_some(table))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2curves::bn256::Fr;
+
+    fn table(columns: &[usize], rows: &[[u64; 2]]) -> Vec<LookupTableRow<Fr>> {
+        rows.iter()
+            .map(|row| LookupTableRow::new(columns, row.map(Fr::from).to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn duplicated_tables_are_removed() {
+        let tables = vec![
+            table(&[0, 1], &[[0, 1], [2, 3]]),
+            table(&[0, 2], &[[0, 1], [2, 3]]),
+            table(&[0, 1], &[[0, 1], [2, 3]]),
+            table(&[0, 1], &[[0, 1], [2, 4]]),
+        ];
+        let unique = deduplicate_tables(tables);
+        assert_eq!(
+            unique,
+            vec![
+                table(&[0, 1], &[[0, 1], [2, 3]]),
+                table(&[0, 2], &[[0, 1], [2, 3]]),
+                table(&[0, 1], &[[0, 1], [2, 4]]),
+            ]
+        );
+    }
+}
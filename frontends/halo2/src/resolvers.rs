@@ -59,7 +59,7 @@ where
 }
 
 /// Represents the value of selector.
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone)]
 pub struct Bool(bool);
 
 impl From<bool> for Bool {
@@ -78,7 +78,7 @@ impl Bool {
 }
 
 /// Possible values when resolving a selector.
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone)]
 pub enum ResolvedSelector {
     // When the selector is used as argument.
     Const(Bool),
@@ -6,6 +6,9 @@ use halo2_frontend_core::{
 };
 use std::collections::BTreeSet;
 
+#[cfg(feature = "z3-export")]
+mod to_z3;
+
 /// Possible nodes in the graph.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum EqConstraintArg<F: Field> {
@@ -117,7 +120,7 @@ impl<F: Field> From<(EqConstraintArg<F>, EqConstraintArg<F>)> for EqConstraint<F
 }
 
 /// Graph of equality constraints between cells and finite field values.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct EqConstraintGraph<F> {
     edges: BTreeSet<(EqConstraintArgSto, EqConstraintArgSto)>,
     vertices: BTreeSet<EqConstraintArgSto>,
@@ -180,6 +183,23 @@ impl<F: Field> EqConstraintGraph<F> {
             .map(|v| v.load_from_sto(&self.ff_storage))
             .collect()
     }
+
+    /// Returns the rows of the column that appear in a constraint paired with the other end of
+    /// each constraint.
+    pub fn constraints_for_column(&self, col: Column<Any>) -> Vec<(usize, EqConstraintArg<F>)> {
+        let row_in_column = |v: &EqConstraintArgSto| match *v {
+            EqConstraintArgSto::Any(c, row) if c == col => Some(row),
+            EqConstraintArgSto::Fixed(c, row) if Column::<Any>::from(c) == col => Some(row),
+            _ => None,
+        };
+        self.edges
+            .iter()
+            .flat_map(|(from, to)| [(from, to), (to, from)])
+            .filter_map(|(this, other)| {
+                Some((row_in_column(this)?, other.load_from_sto(&self.ff_storage)))
+            })
+            .collect()
+    }
 }
 
 impl<F: Field> Default for EqConstraintGraph<F> {
@@ -191,3 +211,39 @@ impl<F: Field> Default for EqConstraintGraph<F> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_frontend_core::query::{Advice, Instance};
+    use halo2curves::bn256::Fr;
+
+    #[test]
+    fn constraints_for_column() {
+        let instance: Column<Any> = Column::new(0, Instance).into();
+        let advice: Column<Any> = Column::new(1, Advice).into();
+        let fixed = Column::new(2, Fixed);
+        let mut graph = EqConstraintGraph::<Fr>::default();
+        graph.add(EqConstraint::AnyToAny(advice, 3, instance, 0));
+        graph.add(EqConstraint::AnyToAny(instance, 1, advice, 5));
+        graph.add(EqConstraint::AnyToAny(advice, 5, fixed.into(), 0));
+        graph.add(EqConstraint::FixedToConst(fixed, 0, Fr::from(7)));
+
+        let mut instance_constraints = graph.constraints_for_column(instance);
+        instance_constraints.sort_by_key(|(row, _)| *row);
+        assert_eq!(
+            instance_constraints,
+            vec![
+                (0, EqConstraintArg::Any(advice, 3)),
+                (1, EqConstraintArg::Any(advice, 5)),
+            ]
+        );
+        assert_eq!(
+            graph.constraints_for_column(fixed.into()),
+            vec![
+                (0, EqConstraintArg::Any(advice, 5)),
+                (0, EqConstraintArg::Const(Fr::from(7))),
+            ]
+        );
+    }
+}
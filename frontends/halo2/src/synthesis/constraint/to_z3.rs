@@ -0,0 +1,66 @@
+//! Export of the equality constraints as Z3 Python code.
+
+use ff::Field;
+
+use super::{EqConstraintArg, EqConstraintGraph};
+
+impl<F: Field> EqConstraintGraph<F> {
+    /// Returns Z3 Python code that adds the equality constraints to an existing `solver`.
+    ///
+    /// The cells in the graph are declared as `z3.Int` variables named with `var_name`. The
+    /// function is also called for the constant values in the graph, and in that case must return
+    /// a valid Python expression since constants are not declared.
+    pub fn to_z3_python(&self, var_name: impl Fn(&EqConstraintArg<F>) -> String) -> String {
+        let mut lines = vec![];
+        for vertex in self.vertices() {
+            if !matches!(vertex, EqConstraintArg::Const(_)) {
+                let name = var_name(&vertex);
+                lines.push(format!("{name} = z3.Int({name:?})"));
+            }
+        }
+        for edge in self.edges() {
+            let (from, to) = edge.vertices();
+            lines.push(format!(
+                "solver.add({} == {})",
+                var_name(&from),
+                var_name(&to)
+            ));
+        }
+        lines.join("\n") + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::synthesis::constraint::EqConstraint;
+    use halo2_frontend_core::{
+        query::{Advice, Fixed},
+        table::{Any, Column},
+    };
+    use halo2curves::bn256::Fr;
+
+    #[test]
+    fn z3_python() {
+        let a0: Column<Any> = Column::new(0, Advice).into();
+        let a1: Column<Any> = Column::new(1, Advice).into();
+        let f0 = Column::new(0, Fixed);
+        let mut graph = EqConstraintGraph::<Fr>::default();
+        graph.add(EqConstraint::AnyToAny(a0, 3, a1, 5));
+        graph.add(EqConstraint::FixedToConst(f0, 0, Fr::from(1)));
+
+        let output = graph.to_z3_python(|arg| match arg {
+            EqConstraintArg::Const(_) => "1".to_owned(),
+            EqConstraintArg::Any(col, row) => format!("advice_{}_{row}", col.index()),
+            EqConstraintArg::Fixed(col, row) => format!("fixed_{}_{row}", col.index()),
+        });
+        similar_asserts::assert_eq!(
+            output,
+            "advice_0_3 = z3.Int(\"advice_0_3\")\n\
+             advice_1_5 = z3.Int(\"advice_1_5\")\n\
+             fixed_0_0 = z3.Int(\"fixed_0_0\")\n\
+             solver.add(advice_0_3 == advice_1_5)\n\
+             solver.add(fixed_0_0 == 1)\n"
+        );
+    }
+}
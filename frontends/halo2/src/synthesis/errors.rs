@@ -0,0 +1,167 @@
+//! Diagnostics reported by the synthesis process.
+
+use std::{fmt, ops::Range};
+
+use halo2_frontend_core::table::{Any, Column};
+
+/// A problem found while synthesizing a circuit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SynthesisError {
+    /// A region was entered but never exited.
+    UnclosedRegion {
+        /// Name of the region.
+        name: String,
+    },
+    /// A fixed cell was assigned twice with different values. Only reported in strict mode.
+    FixedCellConflict {
+        /// Index of the fixed column.
+        column: usize,
+        /// Row of the cell.
+        row: usize,
+        /// Value assigned first.
+        old_value: String,
+        /// Value assigned last.
+        new_value: String,
+    },
+    /// An advice cell was assigned twice inside the same region.
+    AdviceCellConflict {
+        /// Name of the region.
+        region: String,
+        /// Index of the advice column.
+        column: usize,
+        /// Row of the cell.
+        row: usize,
+    },
+    /// Two regions use the same cells.
+    RegionOverlap {
+        /// Name of the first region.
+        region_a: String,
+        /// Name of the second region.
+        region_b: String,
+        /// Column used by both regions.
+        column: Column<Any>,
+        /// Rows of the column used by both regions.
+        rows: Range<usize>,
+    },
+}
+
+impl fmt::Display for SynthesisError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SynthesisError::UnclosedRegion { name } => {
+                write!(f, "Region {name:?} was entered but never exited")
+            }
+            SynthesisError::FixedCellConflict {
+                column,
+                row,
+                old_value,
+                new_value,
+            } => write!(
+                f,
+                "Fixed cell ({column}, {row}) was assigned {old_value} and then {new_value}"
+            ),
+            SynthesisError::AdviceCellConflict {
+                region,
+                column,
+                row,
+            } => write!(
+                f,
+                "Advice cell ({column}, {row}) was assigned twice in region {region:?}"
+            ),
+            SynthesisError::RegionOverlap {
+                region_a,
+                region_b,
+                column,
+                rows,
+            } => write!(
+                f,
+                "Regions {region_a:?} and {region_b:?} overlap on column {column:?} in rows {rows:?}"
+            ),
+        }
+    }
+}
+
+/// Cells of a column used by two regions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegionOverlap {
+    region_a: String,
+    region_b: String,
+    column: Column<Any>,
+    rows: Range<usize>,
+}
+
+impl RegionOverlap {
+    pub(crate) fn new(
+        region_a: String,
+        region_b: String,
+        column: Column<Any>,
+        rows: Range<usize>,
+    ) -> Self {
+        Self {
+            region_a,
+            region_b,
+            column,
+            rows,
+        }
+    }
+
+    /// Returns the name of the region that starts first.
+    pub fn region_a(&self) -> &str {
+        &self.region_a
+    }
+
+    /// Returns the name of the region that starts last.
+    pub fn region_b(&self) -> &str {
+        &self.region_b
+    }
+
+    /// Returns the column used by both regions.
+    pub fn column(&self) -> Column<Any> {
+        self.column
+    }
+
+    /// Returns the rows of the column used by both regions.
+    pub fn rows(&self) -> Range<usize> {
+        self.rows.clone()
+    }
+}
+
+impl From<RegionOverlap> for SynthesisError {
+    fn from(overlap: RegionOverlap) -> Self {
+        SynthesisError::RegionOverlap {
+            region_a: overlap.region_a,
+            region_b: overlap.region_b,
+            column: overlap.column,
+            rows: overlap.rows,
+        }
+    }
+}
+
+/// Error returned by the synthesis process containing all the problems that were found.
+#[derive(Debug, Clone)]
+pub struct SynthesisErrors {
+    errors: Vec<SynthesisError>,
+}
+
+impl SynthesisErrors {
+    pub(crate) fn new(errors: Vec<SynthesisError>) -> Self {
+        Self { errors }
+    }
+
+    /// Returns the list of problems found during synthesis.
+    pub fn errors(&self) -> &[SynthesisError] {
+        &self.errors
+    }
+}
+
+impl fmt::Display for SynthesisErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Synthesis failed:")?;
+        for error in &self.errors {
+            writeln!(f, "  - {error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for SynthesisErrors {}
@@ -2,17 +2,14 @@ use std::{borrow::Borrow, collections::HashMap, ops::Deref};
 
 use ff::Field;
 
-use crate::{
-    io::{AdviceIO, CircuitIO, IOCell, InstanceIO},
-    resolvers::FixedQueryResolver,
-};
+use crate::io::{CircuitIO, IOCell};
 use halo2_frontend_core::{
     expressions::ExprBuilder,
     query::{Advice, Instance},
     table::{Any, Cell, ColumnType, RegionIndex, Rotation, RotationExt},
 };
 
-use super::regions::{RegionData, RegionRow, Regions};
+use super::regions::{RegionData, Regions};
 
 pub type GroupKey = u64;
 
@@ -89,7 +86,7 @@ impl From<IOCell<Advice>> for GroupCell {
 /// A flat read-only representation of a group.
 ///
 /// The parent-children relation is represented by indices on a vector instead.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct Group {
     kind: GroupKind,
     name: Option<String>,
@@ -134,22 +131,6 @@ impl Group {
         self.regions.regions()
     }
 
-    /// Returns the regions' rows
-    pub fn region_rows<'a, 'io, 'fq, F: Field>(
-        &'a self,
-        advice_io: &'io AdviceIO,
-        instance_io: &'io InstanceIO,
-        fqr: &'fq dyn FixedQueryResolver<F>,
-    ) -> Vec<RegionRow<'a, 'io, 'fq, F>> {
-        self.regions()
-            .into_iter()
-            .flat_map(move |r| {
-                r.rows()
-                    .map(move |row| RegionRow::new(r, row, advice_io, instance_io, fqr))
-            })
-            .collect()
-    }
-
     pub fn inputs(&self) -> &[GroupCell] {
         &self.inputs
     }
@@ -189,7 +170,7 @@ impl Group {
 /// A collection of groups.
 ///
 /// It is represented with a newtype to be able to add methods to this type.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct Groups(Vec<Group>);
 
 impl Groups {
@@ -238,7 +219,7 @@ impl Deref for Groups {
 /// Can have children blocks that represent subpieces of the logic.
 /// The boundary between parent and children is determined by the groups
 /// the circuit declares during synthesis.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct GroupTree {
     kind: GroupKind,
     name: Option<String>,
@@ -309,7 +290,7 @@ impl GroupTree {
 /// Once completed they get added to the list of children of the next group in the stack.
 ///
 /// The root group owned by the builder is always a top-level block.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct GroupBuilder {
     root: GroupTree,
     stack: Vec<GroupTree>,
@@ -348,10 +329,36 @@ impl GroupBuilder {
 
     /// Returns the root and consumes the builder.
     ///
-    /// Panics if there are pending groups on the stack.
-    pub fn into_root(self) -> GroupTree {
-        assert!(self.stack.is_empty(), "Builder has pending groups");
-        self.root
+    /// Fails if [`GroupBuilder::check_for_cycles`] fails.
+    pub fn into_root(self) -> anyhow::Result<GroupTree> {
+        self.check_for_cycles()?;
+        Ok(self.root)
+    }
+
+    /// Checks that the group tree is consistent.
+    ///
+    /// Fails if there are pending groups on the stack, which happens when the calls to
+    /// [`GroupBuilder::push`] and [`GroupBuilder::pop`] don't match, or if the top-level group
+    /// appears as a descendant of another group.
+    pub fn check_for_cycles(&self) -> anyhow::Result<()> {
+        if let Some(pending) = self.stack.last() {
+            anyhow::bail!(
+                "Builder has {} pending groups, the innermost being \"{}\"",
+                self.stack.len(),
+                pending.name()
+            );
+        }
+        let mut worklist: Vec<_> = self.root.children.iter().map(|c| (c, &self.root)).collect();
+        while let Some((group, parent)) = worklist.pop() {
+            if matches!(group.kind, GroupKind::TopLevel) {
+                anyhow::bail!(
+                    "The top-level group appears as a descendant of group \"{}\"",
+                    parent.name()
+                );
+            }
+            worklist.extend(group.children.iter().map(|c| (c, group)));
+        }
+        Ok(())
     }
 
     /// Pushes a new group group into the stack.
@@ -401,6 +408,11 @@ impl GroupBuilder {
             .extend(io.outputs().iter().copied().map(Into::into));
     }
 
+    /// Returns a reference to the regions in the current group.
+    pub fn regions(&self) -> &Regions {
+        &self.current().regions
+    }
+
     /// Returns a mutable reference to the regions in the current group.
     pub fn regions_mut(&mut self) -> &mut Regions {
         &mut self.current_mut().regions
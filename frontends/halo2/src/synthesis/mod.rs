@@ -1,9 +1,15 @@
 //! Defines types for handling the result of synthesizing a circuit.
 
-use std::{collections::HashSet, convert::identity};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::identity,
+    iter::Map,
+    ops::RangeFrom,
+};
 
 use anyhow::{Result, anyhow};
 use constraint::{EqConstraint, EqConstraintArg, EqConstraintGraph};
+use errors::{RegionOverlap, SynthesisError, SynthesisErrors};
 use ff::Field;
 use groups::{GroupBuilder, GroupKey, Groups};
 use regions::{FixedData, TableData};
@@ -24,11 +30,12 @@ use halo2_frontend_core::{
 };
 
 pub(crate) mod constraint;
+pub(crate) mod errors;
 pub(crate) mod groups;
 pub(crate) mod regions;
 
 /// Result of synthesizing a circuit.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SynthesizedCircuit<F, E>
 where
     F: Field,
@@ -40,8 +47,12 @@ where
     fixed: FixedData<F>,
     tables: Vec<TableData<F>>,
     groups: Groups,
+    cell_annotations: CellAnnotations,
 }
 
+/// Annotations attached to cells, keyed by column and absolute row.
+pub(crate) type CellAnnotations = HashMap<(Column<Any>, usize), String>;
+
 impl<F, E> SynthesizedCircuit<F, E>
 where
     F: Field,
@@ -51,8 +62,13 @@ where
         &self.gates
     }
 
+    /// Returns the names of the gates in the constraint system.
+    pub fn gate_names(&self) -> impl Iterator<Item = &str> {
+        self.gates.iter().map(Gate::name)
+    }
+
     /// Returns the lookups declared during synthesis.
-    pub(crate) fn lookups(&self) -> &[Lookup<E>] {
+    pub fn lookups(&self) -> &[Lookup<E>] {
         &self.lookups
     }
 
@@ -108,6 +124,22 @@ where
         })
     }
 
+    /// Returns the annotation attached to the cell during synthesis, if any.
+    pub fn cell_annotation(&self, col: Column<Any>, row: usize) -> Option<&str> {
+        self.cell_annotations.get(&(col, row)).map(String::as_str)
+    }
+
+    /// Returns the annotations attached to cells during synthesis.
+    pub(crate) fn cell_annotations(&self) -> &CellAnnotations {
+        &self.cell_annotations
+    }
+
+    /// Returns the cells that are used by more than one region.
+    pub fn check_region_overlaps(&self) -> Vec<RegionOverlap> {
+        let regions: Vec<_> = self.groups.iter().flat_map(|g| g.regions()).collect();
+        regions::find_overlaps(&regions)
+    }
+
     /// Returns the groups in the circuit.
     pub(crate) fn groups(&self) -> &Groups {
         &self.groups
@@ -165,7 +197,39 @@ pub struct Synthesizer<F: Field> {
     // This iterator yields indices for the regions inside the circuit. Each region has an unique
     // index. Regions marked as tables discard their index, that is reused for the next
     // region.
-    next_index: Box<dyn Iterator<Item = RegionIndex>>,
+    next_index: RegionIndices,
+    // Number of regions that have been exited, including the ones marked as tables.
+    region_count: usize,
+    // Advice cells assigned inside the current region. Used for detecting cells that get
+    // assigned more than once.
+    assigned_advice: HashSet<(usize, usize)>,
+    // If true, assigning an advice cell twice in the same region is reported as an error
+    // instead of a warning.
+    strict_mode: bool,
+    // Problems found during synthesis. Reported all together when building the synthesized
+    // circuit.
+    errors: Vec<SynthesisError>,
+    // Human-readable annotations attached to individual cells.
+    cell_annotations: CellAnnotations,
+    // State recorded before synthesizing the circuit. Restored if the synthesis fails and dropped
+    // if it succeeds.
+    snapshot: Option<Box<SynthesizerSnapshot<F>>>,
+}
+
+/// Iterator that yields the indices of the regions.
+type RegionIndices = Map<RangeFrom<usize>, fn(usize) -> RegionIndex>;
+
+/// State of a [`Synthesizer`] recorded by [`SynthesizerLike::snapshot`].
+struct SynthesizerSnapshot<F: Field> {
+    groups: GroupBuilder,
+    fixed: FixedData<F>,
+    eq_constraints: EqConstraintGraph<F>,
+    tables: Vec<HashSet<Column<Fixed>>>,
+    next_index: RegionIndices,
+    region_count: usize,
+    assigned_advice: HashSet<(usize, usize)>,
+    errors: Vec<SynthesisError>,
+    cell_annotations: CellAnnotations,
 }
 
 impl<F: Field> Synthesizer<F> {
@@ -176,21 +240,65 @@ impl<F: Field> Synthesizer<F> {
             fixed: Default::default(),
             eq_constraints: Default::default(),
             tables: Default::default(),
-            next_index: Box::new((0..).map(RegionIndex::from)),
+            next_index: (0..).map(RegionIndex::from as fn(usize) -> RegionIndex),
+            region_count: 0,
+            assigned_advice: Default::default(),
+            strict_mode: false,
+            errors: Default::default(),
+            cell_annotations: Default::default(),
+            snapshot: None,
         }
     }
 
+    /// Sets whether conflicting assignments are reported as errors.
+    pub(crate) fn set_strict_mode(&mut self, strict_mode: bool) {
+        self.strict_mode = strict_mode;
+    }
+
     /// Configures the IO of the circuit.
     pub(crate) fn configure_io(&mut self, advice_io: AdviceIO, instance_io: InstanceIO) {
         self.groups.add_root_io(&advice_io);
         self.groups.add_root_io(&instance_io);
     }
 
+    /// Attaches a human-readable annotation to the cell at the given column and absolute row.
+    ///
+    /// Annotating the same cell again replaces the previous annotation.
+    pub fn annotate_cell(&mut self, column: Column<Any>, row: usize, annotation: String) {
+        self.cell_annotations.insert((column, row), annotation);
+    }
+
+    /// Returns the number of regions that have been entered and exited so far.
+    ///
+    /// Regions marked as tables are included in the count.
+    pub fn region_count(&self) -> usize {
+        self.region_count
+    }
+
+    /// Returns the name of the region that has been entered but not exited yet, if any.
+    pub fn active_region_name(&self) -> Option<&str> {
+        self.groups.regions().current_name()
+    }
+
+    /// Returns true if a region has been entered but not exited yet.
+    pub fn is_in_region(&self) -> bool {
+        self.active_region_name().is_some()
+    }
+
     /// Builds a [`SynthesizedCircuit`] with the information recollected about the circuit.
+    ///
+    /// If problems were found during synthesis returns a [`SynthesisErrors`] with all of them.
     pub(crate) fn build<CS>(mut self, cs: CS) -> Result<SynthesizedCircuit<F, CS::Polynomial>>
     where
         CS: ConstraintSystemInfo<F> + 'static,
     {
+        if let Some(name) = self.groups.regions_mut().current_name() {
+            let name = name.to_owned();
+            self.errors.push(SynthesisError::UnclosedRegion { name });
+        }
+        if !self.errors.is_empty() {
+            return Err(SynthesisErrors::new(self.errors).into());
+        }
         add_fixed_to_const_constraints(&mut self.eq_constraints, &self.fixed)?;
 
         Ok(SynthesizedCircuit {
@@ -200,7 +308,8 @@ impl<F: Field> Synthesizer<F> {
             eq_constraints: self.eq_constraints,
             tables: fill_tables(self.tables, &self.fixed)?,
             fixed: self.fixed,
-            groups: self.groups.into_root().flatten(),
+            groups: self.groups.into_root()?.flatten(),
+            cell_annotations: self.cell_annotations,
         })
     }
 }
@@ -210,6 +319,7 @@ impl<F: Field> SynthesizerLike<F> for Synthesizer<F> {
     ///
     /// Panics if the synthesizer entered a region already and didn't exit.
     fn enter_region(&mut self, region_name: String) {
+        self.assigned_advice.clear();
         self.groups
             .regions_mut()
             .push(|| region_name, &mut self.next_index, &mut self.tables);
@@ -220,6 +330,7 @@ impl<F: Field> SynthesizerLike<F> for Synthesizer<F> {
     /// Panics if the synthesizer didn't entered a region prior.
     fn exit_region(&mut self) {
         self.groups.regions_mut().commit();
+        self.region_count += 1;
     }
 
     /// Marks the given selector as enabled for the table row.
@@ -230,14 +341,37 @@ impl<F: Field> SynthesizerLike<F> for Synthesizer<F> {
     }
 
     /// Process that inside the entered region the circuit assigned a value to an advice cell.
+    ///
+    /// If the cell was already assigned inside the same region logs a warning, or records an
+    /// error if the synthesizer is in strict mode.
     fn on_advice_assigned(&mut self, advice: impl Into<Column<Advice>>, row: usize) {
         let advice = advice.into();
-        self.groups.regions_mut().edit(|region| {
+        let Some(region) = self.groups.regions_mut().edit(|region| {
             region.update_extent(advice.into(), row);
-        });
+            region.name().to_owned()
+        }) else {
+            return;
+        };
+        if self.assigned_advice.insert((advice.index(), row)) {
+            return;
+        }
+        log::warn!(
+            "Advice cell ({}, {row}) was assigned twice in region {region:?}. Only the last assignment is kept",
+            advice.index()
+        );
+        if self.strict_mode {
+            self.errors.push(SynthesisError::AdviceCellConflict {
+                region,
+                column: advice.index(),
+                row,
+            });
+        }
     }
 
     /// Process that inside the entered region the circuit assigned a value to a fixed cell.
+    ///
+    /// If the cell was already assigned a different value logs a warning, or records an error if
+    /// the synthesizer is in strict mode.
     fn on_fixed_assigned(&mut self, fixed: impl Into<Column<Fixed>>, row: usize, value: F) {
         let fixed = fixed.into();
         // Assignments to fixed cells can happen outside a region so we write those on the last
@@ -245,7 +379,22 @@ impl<F: Field> SynthesizerLike<F> for Synthesizer<F> {
         self.groups.regions_mut().edit(|region| {
             region.update_extent(fixed.into(), row);
         });
-        self.fixed.assign_fixed(fixed, row, value);
+        let previous = self.fixed.assign_fixed(fixed, row, value);
+        if let Some(old_value) = previous.filter(|old| *old != value) {
+            log::warn!(
+                "Fixed cell ({}, {row}) was assigned {value:?} after being assigned {old_value:?}. Only the last assignment is kept",
+                fixed.index()
+            );
+            if !self.strict_mode {
+                return;
+            }
+            self.errors.push(SynthesisError::FixedCellConflict {
+                column: fixed.index(),
+                row,
+                old_value: format!("{old_value:?}"),
+                new_value: format!("{value:?}"),
+            });
+        }
     }
 
     /// Annotates that the two given cells have a copy constraint between them.
@@ -315,6 +464,40 @@ impl<F: Field> SynthesizerLike<F> for Synthesizer<F> {
         log::debug!("Exiting group '{}'", self.groups.current().name());
         self.groups.pop();
     }
+
+    fn snapshot(&mut self) {
+        self.snapshot = Some(Box::new(SynthesizerSnapshot {
+            groups: self.groups.clone(),
+            fixed: self.fixed.clone(),
+            eq_constraints: self.eq_constraints.clone(),
+            tables: self.tables.clone(),
+            next_index: self.next_index.clone(),
+            region_count: self.region_count,
+            assigned_advice: self.assigned_advice.clone(),
+            errors: self.errors.clone(),
+            cell_annotations: self.cell_annotations.clone(),
+        }));
+    }
+
+    fn rollback(&mut self) {
+        let Some(snapshot) = self.snapshot.take() else {
+            return;
+        };
+        log::debug!("Rolling back synthesizer {}", self.id);
+        self.groups = snapshot.groups;
+        self.fixed = snapshot.fixed;
+        self.eq_constraints = snapshot.eq_constraints;
+        self.tables = snapshot.tables;
+        self.next_index = snapshot.next_index;
+        self.region_count = snapshot.region_count;
+        self.assigned_advice = snapshot.assigned_advice;
+        self.errors = snapshot.errors;
+        self.cell_annotations = snapshot.cell_annotations;
+    }
+
+    fn discard_snapshot(&mut self) {
+        self.snapshot = None;
+    }
 }
 
 impl<F: Field> std::fmt::Debug for Synthesizer<F> {
@@ -325,6 +508,9 @@ impl<F: Field> std::fmt::Debug for Synthesizer<F> {
             .field("fixed", &self.fixed)
             .field("eq_constraints", &self.eq_constraints)
             .field("tables", &self.tables)
+            .field("region_count", &self.region_count)
+            .field("errors", &self.errors)
+            .field("cell_annotations", &self.cell_annotations)
             .finish()
     }
 }
@@ -362,3 +548,132 @@ fn add_fixed_to_const_constraints<F: Field>(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2curves::bn256::Fr;
+
+    #[test]
+    fn advice_conflict_in_strict_mode() {
+        let mut syn = Synthesizer::<Fr>::new(0);
+        syn.set_strict_mode(true);
+        syn.enter_region("region".to_owned());
+        syn.on_advice_assigned(Column::new(0, Advice), 1);
+        syn.on_advice_assigned(Column::new(0, Advice), 2);
+        syn.on_advice_assigned(Column::new(0, Advice), 1);
+        syn.exit_region();
+        // Assigning the same cell in a different region is not a conflict.
+        syn.enter_region("other".to_owned());
+        syn.on_advice_assigned(Column::new(0, Advice), 1);
+        syn.exit_region();
+        assert_eq!(
+            syn.errors,
+            vec![SynthesisError::AdviceCellConflict {
+                region: "region".to_owned(),
+                column: 0,
+                row: 1
+            }]
+        );
+    }
+
+    #[test]
+    fn fixed_conflict_in_strict_mode() {
+        let assign_twice = |strict_mode| {
+            let mut syn = Synthesizer::<Fr>::new(0);
+            syn.set_strict_mode(strict_mode);
+            syn.on_fixed_assigned(Column::new(0, Fixed), 1, Fr::ONE);
+            // Assigning the same value again is not a conflict.
+            syn.on_fixed_assigned(Column::new(0, Fixed), 1, Fr::ONE);
+            syn.on_fixed_assigned(Column::new(0, Fixed), 1, Fr::ZERO);
+            syn.errors
+        };
+        assert!(assign_twice(false).is_empty());
+        assert_eq!(
+            assign_twice(true),
+            vec![SynthesisError::FixedCellConflict {
+                column: 0,
+                row: 1,
+                old_value: format!("{:?}", Fr::ONE),
+                new_value: format!("{:?}", Fr::ZERO),
+            }]
+        );
+    }
+
+    #[test]
+    fn rollback_restores_the_snapshot() {
+        let mut syn = Synthesizer::<Fr>::new(0);
+        syn.enter_region("before".to_owned());
+        syn.exit_region();
+
+        syn.snapshot();
+        syn.enter_region("failing".to_owned());
+        syn.on_fixed_assigned(Column::new(0, Fixed), 0, Fr::ONE);
+        syn.annotate_cell(Column::new(0, Any::Fixed), 0, "partial".to_owned());
+        syn.rollback();
+
+        assert!(!syn.is_in_region());
+        assert_eq!(syn.region_count(), 1);
+        assert!(syn.cell_annotations.is_empty());
+        assert!(syn.snapshot.is_none());
+        // The synthesizer can still be used after the rollback.
+        syn.enter_region("after".to_owned());
+        assert_eq!(syn.active_region_name(), Some("after"));
+    }
+
+    #[test]
+    fn successful_synthesis_discards_the_snapshot() {
+        let mut syn = Synthesizer::<Fr>::new(0);
+        syn.snapshot();
+        syn.enter_region("region".to_owned());
+        syn.exit_region();
+        syn.discard_snapshot();
+        assert!(syn.snapshot.is_none());
+
+        // Without a snapshot rolling back keeps the current state.
+        syn.rollback();
+        assert_eq!(syn.region_count(), 1);
+    }
+
+    #[test]
+    fn unbalanced_groups() {
+        let mut builder = GroupBuilder::new();
+        builder.push("outer".to_owned(), 0);
+        builder.push("inner".to_owned(), 1);
+        builder.pop();
+        assert!(builder.check_for_cycles().is_err());
+        builder.pop();
+        assert!(builder.into_root().is_ok());
+    }
+
+    #[test]
+    fn cell_annotations() {
+        let mut syn = Synthesizer::<Fr>::new(0);
+        let col = Column::new(1, Any::Advice);
+        syn.annotate_cell(col, 3, "first".to_owned());
+        syn.annotate_cell(col, 3, "running sum".to_owned());
+        syn.annotate_cell(Column::new(1, Any::Fixed), 3, "fixed".to_owned());
+        assert_eq!(
+            syn.cell_annotations.get(&(col, 3)).map(String::as_str),
+            Some("running sum")
+        );
+        assert_eq!(syn.cell_annotations.len(), 2);
+    }
+
+    #[test]
+    fn region_queries() {
+        let mut syn = Synthesizer::<Fr>::new(0);
+        assert_eq!(syn.region_count(), 0);
+        assert!(!syn.is_in_region());
+
+        syn.enter_region("first".to_owned());
+        assert_eq!(syn.active_region_name(), Some("first"));
+        assert!(syn.is_in_region());
+        syn.exit_region();
+
+        syn.enter_region("second".to_owned());
+        syn.exit_region();
+        assert_eq!(syn.region_count(), 2);
+        assert_eq!(syn.active_region_name(), None);
+    }
+}
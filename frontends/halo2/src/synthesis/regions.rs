@@ -4,6 +4,8 @@ use halo2_frontend_core::{
     table::{Column, RegionIndex},
 };
 
+use super::errors::RegionOverlap;
+
 use std::{collections::HashSet, ops::Deref};
 
 pub(super) mod data;
@@ -38,8 +40,37 @@ impl From<usize> for RegionStart {
     }
 }
 
+/// Finds the cells that are used by more than one region.
+///
+/// Two regions overlap if their row ranges intersect and they use the same column.
+pub(crate) fn find_overlaps(regions: &[RegionData]) -> Vec<RegionOverlap> {
+    let mut regions: Vec<_> = regions.iter().filter(|r| !r.rows().is_empty()).collect();
+    regions.sort_by_key(|r| r.rows().start);
+    let mut overlaps = vec![];
+    for (n, a) in regions.iter().enumerate() {
+        let rows_a = a.rows();
+        for b in regions[n + 1..]
+            .iter()
+            .take_while(|b| b.rows().start < rows_a.end)
+        {
+            let rows = b.rows().start..rows_a.end.min(b.rows().end);
+            let mut columns: Vec<_> = a.columns().intersection(b.columns()).copied().collect();
+            columns.sort();
+            overlaps.extend(columns.into_iter().map(|column| {
+                RegionOverlap::new(
+                    a.name().to_owned(),
+                    b.name().to_owned(),
+                    column,
+                    rows.clone(),
+                )
+            }));
+        }
+    }
+    overlaps
+}
+
 /// A set of regions
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct Regions {
     regions: Vec<RegionDataImpl>,
     current: Option<RegionDataImpl>,
@@ -98,6 +129,11 @@ impl Regions {
         None
     }
 
+    /// Returns the name of the region that has been entered but not exited yet, if any.
+    pub fn current_name(&self) -> Option<&str> {
+        self.current.as_ref().map(|region| region.name())
+    }
+
     pub fn regions<'a>(&'a self) -> Vec<RegionData<'a>> {
         self.regions.iter().map(RegionData::new).collect()
     }
@@ -139,3 +175,46 @@ impl Regions {
         tables.push(table.columns());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_frontend_core::{query::Advice, table::Any};
+
+    fn regions(extents: &[(&'static str, usize, std::ops::Range<usize>)]) -> Regions {
+        let mut r = Regions::default();
+        let mut indices = (0..).map(RegionIndex::from);
+        let mut tables = vec![];
+        for (name, col, rows) in extents {
+            r.push(|| *name, &mut indices, &mut tables);
+            r.edit(|r| {
+                for row in rows.clone() {
+                    r.update_extent(Column::new(*col, Advice).into(), row);
+                }
+            });
+            r.commit();
+        }
+        r
+    }
+
+    #[test]
+    fn disjoint_regions_do_not_overlap() {
+        let r = regions(&[("a", 0, 0..2), ("b", 0, 2..4), ("c", 1, 0..4)]);
+        assert!(find_overlaps(&r.regions()).is_empty());
+    }
+
+    #[test]
+    fn overlapping_regions() {
+        let r = regions(&[("a", 0, 0..3), ("b", 1, 0..4), ("c", 0, 2..5)]);
+        let overlaps = find_overlaps(&r.regions());
+        assert_eq!(overlaps.len(), 1);
+        let overlap = &overlaps[0];
+        assert_eq!(overlap.region_a(), "a");
+        assert_eq!(overlap.region_b(), "c");
+        assert_eq!(
+            overlap.column(),
+            Column::<Any>::from(Column::new(0, Advice))
+        );
+        assert_eq!(overlap.rows(), 2..3);
+    }
+}
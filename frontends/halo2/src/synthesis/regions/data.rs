@@ -10,7 +10,7 @@ use std::{
     ops::Range,
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RegionDataImpl {
     /// The name of the region. Not required to be unique.
     name: String,
@@ -48,7 +48,9 @@ impl<F: Copy + std::fmt::Debug + Default> FixedData<F> {
             .push((row.., value));
     }
 
-    pub fn assign_fixed(&mut self, fixed: Column<Fixed>, row: usize, value: F)
+    /// Records the value of a fixed cell returning the previous value if the cell was already
+    /// assigned.
+    pub fn assign_fixed(&mut self, fixed: Column<Fixed>, row: usize, value: F) -> Option<F>
     where
         F: Field,
     {
@@ -60,7 +62,7 @@ impl<F: Copy + std::fmt::Debug + Default> FixedData<F> {
         self.fixed
             .entry(fixed.index())
             .or_default()
-            .insert(row, value);
+            .insert(row, value)
     }
 
     fn resolve_from_blanket_fills(&self, column: usize, row: usize) -> Option<F>
@@ -45,7 +45,7 @@ impl Ord for Fill {
 }
 
 /// Sparse representation of a table.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TableData<F: Copy> {
     values: HashMap<usize, BTreeMap<Fill, F>>,
 }
@@ -147,6 +147,28 @@ impl<F: Copy + Default + std::fmt::Debug> TableData<F> {
         Some(self.get_rows_impl(cols))
     }
 
+    /// Returns the number of columns in the table.
+    pub fn column_count(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns a new table with only the given columns.
+    ///
+    /// The columns are identified by the index of the fixed column. Fails if any of the columns
+    /// is not part of the table.
+    pub fn project(&self, columns: &[usize]) -> anyhow::Result<TableData<F>> {
+        let values = columns
+            .iter()
+            .map(|col| {
+                self.values
+                    .get(col)
+                    .map(|values| (*col, values.clone()))
+                    .ok_or_else(|| anyhow::anyhow!("Table does not have column {col}"))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self { values })
+    }
+
     fn collect_columns(&self) -> HashSet<usize> {
         self.values.keys().copied().collect()
     }
@@ -183,3 +205,25 @@ fn fill_table<F: Default + Copy>(
 
     Ok(dense)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_frontend_core::table::Column;
+    use halo2curves::bn256::Fr;
+
+    #[test]
+    fn project_columns() {
+        let mut fixed = FixedData::<Fr>::default();
+        for col in 0..3 {
+            fixed.blanket_fill(Column::new(col, Fixed), 0, Fr::from(col as u64));
+        }
+        let table = TableData::new(fixed);
+        assert_eq!(table.column_count(), 3);
+
+        let projected = table.project(&[0, 2]).unwrap();
+        assert_eq!(projected.column_count(), 2);
+        assert_eq!(projected.collect_columns(), HashSet::from([0, 2]));
+        assert!(table.project(&[3]).is_err());
+    }
+}
@@ -4,6 +4,7 @@ use std::ops::Deref;
 
 /// A temporary variable.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "json-export", derive(serde::Serialize, serde::Deserialize))]
 pub struct Temp(usize);
 
 impl Deref for Temp {
@@ -30,6 +31,40 @@ impl<E> ExprOrTemp<E> {
             ExprOrTemp::Expr(e) => ExprOrTemp::Expr(f(e)),
         }
     }
+
+    /// Returns true if the value is a temporary.
+    pub fn is_temp(&self) -> bool {
+        matches!(self, ExprOrTemp::Temp(_))
+    }
+
+    /// Returns true if the value is an expression.
+    pub fn is_expr(&self) -> bool {
+        matches!(self, ExprOrTemp::Expr(_))
+    }
+
+    /// Returns the temporary if the value is one.
+    pub fn as_temp(&self) -> Option<&Temp> {
+        match self {
+            ExprOrTemp::Temp(temp) => Some(temp),
+            ExprOrTemp::Expr(_) => None,
+        }
+    }
+
+    /// Returns a reference to the expression if the value is one.
+    pub fn as_expr(&self) -> Option<&E> {
+        match self {
+            ExprOrTemp::Temp(_) => None,
+            ExprOrTemp::Expr(e) => Some(e),
+        }
+    }
+
+    /// Returns the expression if the value is one. Otherwise returns the value back as an error.
+    pub fn into_expr(self) -> Result<E, Self> {
+        match self {
+            ExprOrTemp::Expr(e) => Ok(e),
+            temp => Err(temp),
+        }
+    }
 }
 
 impl<E> From<Temp> for ExprOrTemp<E> {
@@ -81,3 +116,26 @@ impl Iterator for Temps {
         Some(Temp(id))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn temporaries_and_expressions() {
+        let temp = Temps::new().nth(2).unwrap();
+        let t: ExprOrTemp<&str> = temp.into();
+        assert!(t.is_temp());
+        assert!(!t.is_expr());
+        assert_eq!(t.as_temp().map(|temp| **temp), Some(2));
+        assert_eq!(t.as_expr(), None);
+        assert!(matches!(t.into_expr(), Err(ExprOrTemp::Temp(t)) if t == temp));
+
+        let e = ExprOrTemp::Expr("x");
+        assert!(e.is_expr());
+        assert!(!e.is_temp());
+        assert_eq!(e.as_temp(), None);
+        assert_eq!(e.as_expr(), Some(&"x"));
+        assert_eq!(e.map(str::len).into_expr().unwrap(), 1);
+    }
+}
@@ -14,10 +14,11 @@ mod inner {
             .short_names()
             .no_optimize()
             .build()
+            .unwrap()
     }
 
     pub fn opt_picus_params() -> PicusParams {
-        PicusParamsBuilder::new().short_names().build()
+        PicusParamsBuilder::new().short_names().build().unwrap()
     }
 
     pub fn picus_test<F, C>(
@@ -0,0 +1,324 @@
+use std::cell::Cell;
+
+use group::ff::Field as _;
+use halo2_frontend_core::{
+    expressions::{
+        EvalExpression, EvaluableExpr, EvaluationCache, ExprBuilder, ExpressionInfo as _,
+        ExpressionOperandsInfo as _, ExpressionTypes,
+    },
+    info_traits::{CreateQuery, QueryInfo as _},
+};
+use halo2_midnight_integration::plonk::{_AdviceQuery, _Expression, _FixedQuery, _InstanceQuery};
+use halo2curves::bn256::Fr;
+
+type Expr = _Expression<Fr>;
+
+fn advice(index: usize) -> Expr {
+    <_AdviceQuery as CreateQuery<Expr>>::query_expr(index, 0)
+}
+
+/// Counts the number of nodes of the expression and how many times it was called.
+#[derive(Default)]
+struct NodeCounter {
+    calls: Cell<usize>,
+}
+
+impl NodeCounter {
+    fn leaf(&self) -> usize {
+        self.calls.set(self.calls.get() + 1);
+        1
+    }
+}
+
+impl<E: ExpressionTypes> EvalExpression<Fr, E> for NodeCounter {
+    type Output = usize;
+
+    fn constant(&self, _: &Fr) -> Self::Output {
+        self.leaf()
+    }
+
+    fn selector(&self, _: &E::Selector) -> Self::Output {
+        self.leaf()
+    }
+
+    fn fixed(&self, _: &E::FixedQuery) -> Self::Output {
+        self.leaf()
+    }
+
+    fn advice(&self, _: &E::AdviceQuery) -> Self::Output {
+        self.leaf()
+    }
+
+    fn instance(&self, _: &E::InstanceQuery) -> Self::Output {
+        self.leaf()
+    }
+
+    fn challenge(&self, _: &E::Challenge) -> Self::Output {
+        self.leaf()
+    }
+
+    fn negated(&self, expr: Self::Output) -> Self::Output {
+        self.leaf() + expr
+    }
+
+    fn sum(&self, lhs: Self::Output, rhs: Self::Output) -> Self::Output {
+        self.leaf() + lhs + rhs
+    }
+
+    fn product(&self, lhs: Self::Output, rhs: Self::Output) -> Self::Output {
+        self.leaf() + lhs + rhs
+    }
+
+    fn scaled(&self, lhs: Self::Output, _: &Fr) -> Self::Output {
+        self.leaf() + lhs
+    }
+}
+
+#[test]
+fn cached_evaluate_reuses_results() {
+    let x = advice(0);
+    let poly = Expr::sum(Expr::product(x.clone(), x.clone()), Expr::constant(Fr::ONE));
+    let other = Expr::negated(x);
+
+    let counter = NodeCounter::default();
+    let cache = EvaluationCache::new(&counter);
+    assert_eq!(poly.cached_evaluate(&cache), 5);
+    assert_eq!(counter.calls.get(), 5);
+    assert_eq!(poly.cached_evaluate(&cache), 5);
+    assert_eq!(counter.calls.get(), 5);
+
+    assert_eq!(other.cached_evaluate(&cache), 2);
+    assert_eq!(counter.calls.get(), 7);
+    assert_eq!(cache.len(), 2);
+
+    cache.clear();
+    assert!(cache.is_empty());
+    assert_eq!(poly.cached_evaluate(&cache), 5);
+    assert_eq!(counter.calls.get(), 12);
+}
+
+#[test]
+fn evaluation_caches_are_independent() {
+    let poly = Expr::product(advice(0), advice(1));
+
+    let first = NodeCounter::default();
+    let second = NodeCounter::default();
+    assert_eq!(poly.cached_evaluate(&EvaluationCache::new(&first)), 3);
+    assert_eq!(poly.cached_evaluate(&EvaluationCache::new(&second)), 3);
+    assert_eq!(first.calls.get(), 3);
+    assert_eq!(second.calls.get(), 3);
+}
+
+fn advice_index(expr: &Expr) -> Option<usize> {
+    expr.as_advice_query().map(|query| query.column_index())
+}
+
+#[test]
+fn operands_of_sums_and_products() {
+    let sum = Expr::sum(advice(0), advice(1));
+    let (lhs, rhs) = sum.as_sum().unwrap();
+    assert_eq!(advice_index(lhs), Some(0));
+    assert_eq!(advice_index(rhs), Some(1));
+    assert!(sum.as_product().is_none());
+
+    let product = Expr::product(advice(2), sum.clone());
+    let (lhs, rhs) = product.as_product().unwrap();
+    assert_eq!(advice_index(lhs), Some(2));
+    assert!(rhs.as_sum().is_some());
+    assert!(product.as_sum().is_none());
+}
+
+#[test]
+fn operands_of_constants_and_scaled() {
+    let two = Fr::ONE + Fr::ONE;
+    assert_eq!(Expr::constant(two).as_constant(), Some(&two));
+    assert!(advice(0).as_constant().is_none());
+
+    let scaled = Expr::scaled(advice(3), two);
+    let (inner, factor) = scaled.as_scaled().unwrap();
+    assert_eq!(advice_index(inner), Some(3));
+    assert_eq!(factor, &two);
+    assert!(scaled.as_constant().is_none());
+}
+
+#[test]
+fn leaves_have_no_operands() {
+    let x = advice(0);
+    assert!(x.as_sum().is_none());
+    assert!(x.as_product().is_none());
+    assert!(x.as_scaled().is_none());
+    assert!(Expr::negated(x).as_sum().is_none());
+}
+
+#[test]
+fn queries_by_column_type() {
+    let adv = advice(2);
+    assert_eq!(advice_index(&adv), Some(2));
+    assert!(adv.as_instance_query().is_none());
+    assert!(adv.as_fixed_query().is_none());
+    assert!(adv.as_challenge().is_none());
+
+    let inst = <_InstanceQuery as CreateQuery<Expr>>::query_expr(1, 0);
+    assert_eq!(
+        inst.as_instance_query().map(|query| query.column_index()),
+        Some(1)
+    );
+    assert!(inst.as_advice_query().is_none());
+    assert!(inst.as_challenge().is_none());
+
+    let fixed = <_FixedQuery as CreateQuery<Expr>>::query_expr(3, 0);
+    assert!(fixed.as_advice_query().is_none());
+    assert!(fixed.as_instance_query().is_none());
+
+    let sum = Expr::sum(adv, inst);
+    assert!(sum.as_advice_query().is_none());
+    assert!(sum.as_instance_query().is_none());
+    assert!(sum.as_challenge().is_none());
+}
+
+#[test]
+fn degree_of_mixed_expressions() {
+    let x = advice(0);
+    let y = advice(1);
+    assert_eq!(x.degree(), 1);
+    assert_eq!(Expr::constant(Fr::ONE).degree(), 1);
+
+    // -(x * y) + x * x * y
+    let xy = Expr::product(x.clone(), y.clone());
+    let xxy = Expr::product(Expr::product(x.clone(), x.clone()), y);
+    let poly = Expr::sum(Expr::negated(xy.clone()), xxy);
+    assert_eq!(Expr::negated(xy.clone()).degree(), 2);
+    assert_eq!(poly.degree(), 3);
+
+    // 3 * (x * y) * -x
+    let three = Fr::ONE + Fr::ONE + Fr::ONE;
+    let poly = Expr::product(Expr::scaled(xy, three), Expr::negated(x));
+    assert_eq!(poly.degree(), 3);
+}
+
+/// Evaluates the expression replacing every advice query with the same value.
+struct AtPoint(Fr);
+
+impl<E: ExpressionTypes> EvalExpression<Fr, E> for AtPoint {
+    type Output = Fr;
+
+    fn constant(&self, f: &Fr) -> Self::Output {
+        *f
+    }
+
+    fn selector(&self, _: &E::Selector) -> Self::Output {
+        unreachable!()
+    }
+
+    fn fixed(&self, _: &E::FixedQuery) -> Self::Output {
+        unreachable!()
+    }
+
+    fn advice(&self, _: &E::AdviceQuery) -> Self::Output {
+        self.0
+    }
+
+    fn instance(&self, _: &E::InstanceQuery) -> Self::Output {
+        unreachable!()
+    }
+
+    fn challenge(&self, _: &E::Challenge) -> Self::Output {
+        unreachable!()
+    }
+
+    fn negated(&self, expr: Self::Output) -> Self::Output {
+        -expr
+    }
+
+    fn sum(&self, lhs: Self::Output, rhs: Self::Output) -> Self::Output {
+        lhs + rhs
+    }
+
+    fn product(&self, lhs: Self::Output, rhs: Self::Output) -> Self::Output {
+        lhs * rhs
+    }
+
+    fn scaled(&self, lhs: Self::Output, rhs: &Fr) -> Self::Output {
+        lhs * rhs
+    }
+}
+
+/// Counts the products in the expression.
+struct Products;
+
+impl<E: ExpressionTypes> EvalExpression<Fr, E> for Products {
+    type Output = usize;
+
+    fn constant(&self, _: &Fr) -> Self::Output {
+        0
+    }
+
+    fn selector(&self, _: &E::Selector) -> Self::Output {
+        0
+    }
+
+    fn fixed(&self, _: &E::FixedQuery) -> Self::Output {
+        0
+    }
+
+    fn advice(&self, _: &E::AdviceQuery) -> Self::Output {
+        0
+    }
+
+    fn instance(&self, _: &E::InstanceQuery) -> Self::Output {
+        0
+    }
+
+    fn challenge(&self, _: &E::Challenge) -> Self::Output {
+        0
+    }
+
+    fn negated(&self, expr: Self::Output) -> Self::Output {
+        expr
+    }
+
+    fn sum(&self, lhs: Self::Output, rhs: Self::Output) -> Self::Output {
+        lhs + rhs
+    }
+
+    fn product(&self, lhs: Self::Output, rhs: Self::Output) -> Self::Output {
+        1 + lhs + rhs
+    }
+
+    fn scaled(&self, lhs: Self::Output, _: &Fr) -> Self::Output {
+        lhs
+    }
+}
+
+#[test]
+fn from_polynomial_evaluates_like_the_polynomial() {
+    let coefficients = [
+        (Fr::from(2u64), 1),
+        (Fr::ONE, 0),
+        (Fr::ONE, 3),
+        (Fr::from(4u64), 1),
+    ];
+    // x^3 + 6*x + 1
+    let poly = Expr::from_polynomial(&coefficients, advice(0));
+    for x in [0u64, 1, 2, 5, 11].map(Fr::from) {
+        let expected = x * x * x + Fr::from(6u64) * x + Fr::ONE;
+        assert_eq!(poly.evaluate(&AtPoint(x)), expected);
+    }
+    // Horner's method multiplies by the variable once per degree.
+    assert_eq!(poly.evaluate(&Products), 3);
+}
+
+#[test]
+fn from_polynomial_edge_cases() {
+    let zero = Expr::from_polynomial(&[], advice(0));
+    assert_eq!(zero.as_constant(), Some(&Fr::ZERO));
+
+    let five = Fr::from(5u64);
+    let constant = Expr::from_polynomial(&[(five, 0)], advice(0));
+    assert_eq!(constant.as_constant(), Some(&five));
+
+    // 3*x^2
+    let monomial = Expr::from_polynomial(&[(Fr::from(3u64), 2)], advice(0));
+    assert_eq!(monomial.evaluate(&AtPoint(Fr::from(4u64))), Fr::from(48u64));
+    assert_eq!(monomial.evaluate(&Products), 2);
+}
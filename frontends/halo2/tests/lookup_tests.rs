@@ -87,3 +87,69 @@ impl<F: Field> LookupCallbacks<F, _Expression<F>> for LookupCallbackHandler {
         Ok(IRStmt::comment("Ignored lookup"))
     }
 }
+
+// Ignoring the lookups produces the same constraints as the handler above, which also only emits
+// a comment.
+#[cfg(feature = "picus-backend")]
+#[test]
+fn ignored_lookups_are_replaced_by_a_comment() {
+    common::setup();
+    let ignore = <dyn LookupCallbacks<Fr, _Expression<Fr>>>::ignore();
+    let mut driver = halo2_llzk_frontend::driver::Driver::default();
+    let resolved = common::synthesize_and_generate_ir(
+        &mut driver,
+        LookupCircuitSynthesis::default(),
+        IRGenParamsBuilder::new().lookup_callbacks(&ignore).build(),
+    );
+    let comments = resolved
+        .groups()
+        .iter()
+        .flat_map(|g| g.statements())
+        .flat_map(IRStmt::iter)
+        .filter(|stmt| matches!(stmt, IRStmt::Comment(c) if c.value() == "Lookup ignored"))
+        .count();
+    assert!(comments > 0);
+
+    common::picus::check_picus(
+        &driver,
+        &resolved,
+        common::picus::picus_params(),
+        include_str!("expected/picus/lookup.picus"),
+    );
+}
+
+/// Records which lookups have a multiplicity expression.
+#[derive(Default)]
+struct MultiplicityRecorder(std::cell::RefCell<Vec<bool>>);
+
+impl<F: Field> LookupCallbacks<F, _Expression<F>> for MultiplicityRecorder {
+    fn on_lookup<'a>(
+        &self,
+        lookup: &'a Lookup<_Expression<F>>,
+        _table: &dyn LookupTableGenerator<F>,
+        _temps: &mut Temps,
+    ) -> anyhow::Result<IRStmt<ExprOrTemp<Cow<'a, _Expression<F>>>>> {
+        self.0
+            .borrow_mut()
+            .push(lookup.multiplicity_expression().is_some());
+        Ok(IRStmt::comment("Ignored lookup"))
+    }
+}
+
+// Midnight lookups are Plookup-style and don't have a multiplicity.
+#[test]
+fn midnight_lookups_have_no_multiplicity() {
+    common::setup();
+    let recorder = MultiplicityRecorder::default();
+    let mut driver = halo2_llzk_frontend::driver::Driver::default();
+    common::synthesize_and_generate_ir(
+        &mut driver,
+        Lookup2x3CircuitSynthesis::default(),
+        IRGenParamsBuilder::new()
+            .lookup_callbacks(&recorder)
+            .build(),
+    );
+    let recorded = recorder.0.take();
+    assert!(!recorded.is_empty());
+    assert!(recorded.iter().all(|has_multiplicity| !has_multiplicity));
+}
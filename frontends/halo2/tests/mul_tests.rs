@@ -145,6 +145,27 @@ mod mul_inject {
         resolved
     }
 
+    #[test]
+    fn inject_out_of_bounds() {
+        common::setup();
+        let mut driver = Driver::default();
+        let circuit = MulInjectCircuitSynthesis::default();
+        let syn = driver.synthesize(&circuit).unwrap();
+
+        let mut unresolved = driver
+            .generate_ir(&syn, IRGenParamsBuilder::new().build())
+            .unwrap();
+        let (_, stmt) = ir_to_inject().swap_remove(0);
+        let err = unresolved
+            .inject_ir([(RegionIndex::from(100), stmt)], &syn)
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Region index 100 out of bounds (max "),
+            "unexpected error: {err}"
+        );
+    }
+
     #[test]
     fn picus() {
         common::setup();
@@ -179,6 +200,523 @@ mod mul_inject {
     }
 }
 
+#[cfg(feature = "picus-backend")]
+mod group_selection {
+    use halo2_llzk_frontend::{PicusParamsBuilder, driver::Driver};
+
+    use super::*;
+
+    /// Returns the sorted names of the modules generated for the deep callstack circuit.
+    ///
+    /// Also checks that the estimated number of groups matches the number of modules.
+    fn modules(params: &mut PicusParamsBuilder) -> Vec<String> {
+        common::setup();
+        let mut driver = Driver::default();
+        let resolved = common::synthesize_and_generate_ir(
+            &mut driver,
+            DeepCallstackCircuitSynthesis::default(),
+            IRGenParamsBuilder::new().build(),
+        );
+        let params = params.short_names().no_optimize().build().unwrap();
+        let estimate = driver.picus_estimate(&resolved, params.clone());
+        let output = driver
+            .picus(&resolved, params)
+            .unwrap()
+            .display()
+            .to_string();
+        let mut names: Vec<_> = output
+            .lines()
+            .filter_map(|line| line.strip_prefix("(begin-module "))
+            .map(|name| name.trim_end_matches(')').to_owned())
+            .collect();
+        names.sort();
+        assert_eq!(estimate.group_count(), names.len());
+        names
+    }
+
+    #[test]
+    fn inline_everything() {
+        assert_eq!(modules(PicusParamsBuilder::new().inline()), ["Main"]);
+    }
+
+    // Main calls outer_group, which calls inner_group.
+    #[test]
+    fn max_depth_outlines_deep_groups() {
+        assert_eq!(
+            modules(PicusParamsBuilder::new().inline().inline_max_depth(1)),
+            ["Main", "inner_group"]
+        );
+    }
+
+    #[test]
+    fn no_inlining_emits_every_group() {
+        assert_eq!(
+            modules(PicusParamsBuilder::new().no_inline()),
+            [
+                "Main",
+                "inner_group",
+                "outer_group",
+                "test_group",
+                "test_group1"
+            ]
+        );
+    }
+
+    #[test]
+    fn only_group_skips_the_rest_of_the_groups() {
+        assert_eq!(
+            modules(
+                PicusParamsBuilder::new()
+                    .no_inline()
+                    .only_group("outer_group")
+            ),
+            ["Main", "inner_group", "outer_group"]
+        );
+    }
+
+    #[test]
+    fn group_filter_outlines_groups_and_their_callees() {
+        assert_eq!(
+            modules(
+                PicusParamsBuilder::new()
+                    .inline()
+                    .outline_group("outer_group")
+            ),
+            ["Main", "inner_group", "outer_group"]
+        );
+    }
+}
+
+#[cfg(feature = "picus-backend")]
+mod codegen_progress {
+    use std::cell::RefCell;
+
+    use halo2_llzk_frontend::{PicusParamsBuilder, driver::Driver};
+
+    use super::*;
+
+    #[test]
+    fn progress_is_reported_once_per_group() {
+        common::setup();
+        let mut driver = Driver::default();
+        let resolved = common::synthesize_and_generate_ir(
+            &mut driver,
+            DeepCallstackCircuitSynthesis::default(),
+            IRGenParamsBuilder::new().build(),
+        );
+        let params = PicusParamsBuilder::new()
+            .short_names()
+            .no_optimize()
+            .no_inline()
+            .build()
+            .unwrap();
+        let reports = RefCell::new(vec![]);
+        driver
+            .picus_with_progress(&resolved, params, |progress| {
+                reports.borrow_mut().push((
+                    progress.groups_done,
+                    progress.groups_total,
+                    progress.current_group_name.to_owned(),
+                ))
+            })
+            .unwrap();
+        let reports = reports.into_inner();
+
+        let done: Vec<_> = reports.iter().map(|(done, _, _)| *done).collect();
+        assert_eq!(done, [0, 1, 2, 3, 4]);
+        assert!(reports.iter().all(|(_, total, _)| *total == 5));
+        let mut names: Vec<_> = reports.into_iter().map(|(_, _, name)| name).collect();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "Main",
+                "inner_group",
+                "outer_group",
+                "test_group",
+                "test_group1"
+            ]
+        );
+    }
+}
+
+mod region_filters {
+    use crate::ensure_validation;
+    use halo2_llzk_frontend::{
+        driver::Driver,
+        ir::{ResolvedIRCircuit, stmt::IRStmt},
+    };
+
+    use super::*;
+
+    /// Counts the constraints of every group in the circuit.
+    fn constraints(resolved: &ResolvedIRCircuit) -> usize {
+        resolved
+            .groups()
+            .iter()
+            .flat_map(|g| g.statements())
+            .flat_map(IRStmt::iter)
+            .filter(|stmt| matches!(stmt, IRStmt::Constraint(_)))
+            .count()
+    }
+
+    // The circuit has a single region with the two constraints of the mul gate. The copy
+    // constraints to the IO are not part of the region.
+    #[test]
+    fn params_filter_skips_regions() {
+        common::setup();
+        let mut driver = Driver::default();
+        let circuit = MulCircuitSynthesis::default();
+        let all = common::synthesize_and_generate_ir(
+            &mut driver,
+            circuit,
+            IRGenParamsBuilder::new()
+                .with_region_filter(|_, _| true)
+                .build(),
+        );
+        assert_eq!(constraints(&all), 4);
+
+        let mut driver = Driver::default();
+        let circuit = MulCircuitSynthesis::default();
+        let filtered = common::synthesize_and_generate_ir(
+            &mut driver,
+            circuit,
+            IRGenParamsBuilder::new()
+                .with_region_filter(|name, index| name != "first row" && index != 0)
+                .build(),
+        );
+        assert_eq!(constraints(&filtered), 2);
+        assert_eq!(filtered.groups().len(), all.groups().len());
+    }
+
+    #[test]
+    fn driver_filter_skips_regions() {
+        common::setup();
+        let mut driver = Driver::default();
+        let circuit = MulCircuitSynthesis::default();
+        let syn = driver.synthesize(&circuit).unwrap();
+        let unresolved = driver
+            .generate_ir_filtered(&syn, IRGenParamsBuilder::new().build(), |region| {
+                region.name() != "first row"
+            })
+            .unwrap();
+        ensure_validation!(unresolved);
+        let resolved = unresolved.resolve().unwrap();
+        ensure_validation!(resolved);
+        assert_eq!(constraints(&resolved), 2);
+    }
+}
+
+mod batch_synthesis {
+    use halo2_llzk_frontend::driver::Driver;
+
+    use super::*;
+
+    #[test]
+    fn circuits_share_the_configuration() {
+        common::setup();
+        let mut driver = Driver::default();
+        let single = common::synthesize_and_generate_ir(
+            &mut driver,
+            MulCircuitSynthesis::default(),
+            IRGenParamsBuilder::new().build(),
+        );
+
+        let circuits = [
+            MulCircuitSynthesis::default(),
+            MulCircuitSynthesis::default(),
+        ];
+        let batch = driver.synthesize_batch(&circuits).unwrap();
+        assert_eq!(batch.len(), 2);
+        for syn in &batch {
+            assert_eq!(syn.gate_names().collect::<Vec<_>>(), ["mul"]);
+            let resolved = driver
+                .generate_ir(syn, IRGenParamsBuilder::new().build())
+                .unwrap()
+                .resolve()
+                .unwrap();
+            assert_eq!(resolved.groups().len(), single.groups().len());
+        }
+    }
+}
+
+mod group_validation {
+    use halo2_llzk_frontend::{driver::Driver, ir::groups::validation::ValidationError};
+
+    use super::*;
+
+    // The callees are looked up by their id, so validating against an empty list of groups
+    // leaves every callsite unresolved.
+    #[test]
+    fn every_callsite_is_reported() {
+        common::setup();
+        let mut driver = Driver::default();
+        let resolved = common::synthesize_and_generate_ir(
+            &mut driver,
+            DifferentBodiesCircuitSynthesis::default(),
+            IRGenParamsBuilder::new().build(),
+        );
+        let main = resolved.main();
+        assert!(main.callsites().len() > 1);
+        let (status, errors) = main.validate(resolved.groups());
+        assert!(status.is_ok());
+        assert!(errors.is_empty());
+
+        let (status, errors) = main.validate(&[]);
+        assert!(status.is_err());
+        let expected = main
+            .callsites()
+            .iter()
+            .enumerate()
+            .map(|(call_no, callsite)| ValidationError::UnresolvedCallee {
+                call_no,
+                callee_id: callsite.callee_id(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(errors, expected);
+        assert_eq!(
+            errors[1].to_string(),
+            format!(
+                "On callsite 1: Callee with id {} was not found",
+                main.callsites()[1].callee_id()
+            )
+        );
+    }
+}
+
+mod gate_scope {
+    use std::{cell::RefCell, rc::Rc};
+
+    use halo2_llzk_frontend::{
+        driver::Driver,
+        gates::{GateCallbacks, GateRewritePattern, GateScope, RewriteError},
+    };
+    use halo2_midnight_integration::plonk::_Expression;
+
+    use super::*;
+
+    /// Summary of a gate scope seen while lowering the gates.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct ScopeSummary {
+        region: String,
+        always_active: usize,
+        active_after_end: bool,
+        max_degree: usize,
+    }
+
+    /// Records the scopes of the gates and lets the default patterns lower them.
+    #[derive(Default, Clone)]
+    struct Recorder(Rc<RefCell<Vec<ScopeSummary>>>);
+
+    impl GateRewritePattern<Fr, _Expression<Fr>> for Recorder {
+        fn match_gate(&self, gate: GateScope<Fr, _Expression<Fr>>) -> Result<(), RewriteError> {
+            let always_active = gate.always_active_selectors();
+            let active_after_end = always_active
+                .iter()
+                .any(|sel| gate.selector_is_active(sel, gate.end_row() + 1));
+            self.0.borrow_mut().push(ScopeSummary {
+                region: gate.region_name().to_owned(),
+                always_active: always_active.len(),
+                active_after_end,
+                max_degree: gate.max_degree(),
+            });
+            Err(RewriteError::NoMatch)
+        }
+    }
+
+    impl GateCallbacks<Fr, _Expression<Fr>> for Recorder {
+        fn patterns(&self) -> Vec<Box<dyn GateRewritePattern<Fr, _Expression<Fr>>>> {
+            vec![Box::new(self.clone())]
+        }
+    }
+
+    fn scopes() -> Vec<ScopeSummary> {
+        common::setup();
+        let recorder = Recorder::default();
+        let mut driver = Driver::default();
+        common::synthesize_and_generate_ir(
+            &mut driver,
+            MulCircuitSynthesis::default(),
+            IRGenParamsBuilder::new().gate_callbacks(&recorder).build(),
+        );
+        recorder.0.take()
+    }
+
+    // The selector of the mul gate is only enabled in the single row of its region. Both
+    // polynomials of the gate are quadratic and multiplied by the selector.
+    #[test]
+    fn scope_of_the_mul_gate() {
+        assert_eq!(
+            scopes(),
+            vec![ScopeSummary {
+                region: "first row".to_owned(),
+                always_active: 1,
+                active_after_end: false,
+                max_degree: 3,
+            }]
+        );
+    }
+}
+
+#[cfg(feature = "picus-backend")]
+mod zero_expr {
+    use halo2_frontend_core::expressions::ExprBuilder as _;
+    use halo2_llzk_frontend::{
+        driver::Driver,
+        gates::{GateCallbacks, GateRewritePattern},
+        ir::generate::FallbackGateRewriter,
+    };
+    use halo2_midnight_integration::plonk::_Expression;
+
+    use super::*;
+
+    /// Compares the polynomials of the gates against 7 instead of 0.
+    struct SevenAsZero;
+
+    impl GateCallbacks<Fr, _Expression<Fr>> for SevenAsZero {
+        fn patterns(&self) -> Vec<Box<dyn GateRewritePattern<Fr, _Expression<Fr>>>> {
+            vec![Box::new(
+                FallbackGateRewriter::new(false)
+                    .with_zero_expr(|| _Expression::constant(Fr::from(7u64))),
+            )]
+        }
+    }
+
+    #[test]
+    fn gates_use_the_configured_zero() {
+        common::setup();
+        let mut driver = Driver::default();
+        let resolved = common::synthesize_and_generate_ir(
+            &mut driver,
+            MulCircuitSynthesis::default(),
+            IRGenParamsBuilder::new()
+                .gate_callbacks(&SevenAsZero)
+                .build(),
+        );
+        let expected = include_str!("expected/picus/mul_circuit.picus");
+        assert_eq!(expected.matches(")) 0))").count(), 2);
+        common::picus::check_picus(
+            &driver,
+            &resolved,
+            common::picus::picus_params(),
+            expected.replace(")) 0))", ")) 7))"),
+        );
+    }
+}
+
+mod cell_annotations {
+    use halo2_frontend_core::table::{Any, Column};
+    use halo2_llzk_frontend::{
+        AdviceIO, CircuitSynthesis, InstanceIO, Synthesizer, driver::Driver, ir::ResolvedIRCircuit,
+    };
+
+    use super::*;
+
+    /// Mul circuit that annotates the cells of the lhs and of the product.
+    #[derive(Default)]
+    struct AnnotatedMulCircuitSynthesis(MulCircuitSynthesis);
+
+    impl CircuitSynthesis<Fr> for AnnotatedMulCircuitSynthesis {
+        type Circuit = <MulCircuitSynthesis as CircuitSynthesis<Fr>>::Circuit;
+        type Config = <MulCircuitSynthesis as CircuitSynthesis<Fr>>::Config;
+        type CS = <MulCircuitSynthesis as CircuitSynthesis<Fr>>::CS;
+        type Error = <MulCircuitSynthesis as CircuitSynthesis<Fr>>::Error;
+
+        fn circuit(&self) -> &Self::Circuit {
+            self.0.circuit()
+        }
+
+        fn configure(cs: &mut Self::CS) -> Self::Config {
+            MulCircuitSynthesis::configure(cs)
+        }
+
+        fn advice_io(config: &Self::Config) -> anyhow::Result<AdviceIO> {
+            MulCircuitSynthesis::advice_io(config)
+        }
+
+        fn instance_io(config: &Self::Config) -> anyhow::Result<InstanceIO> {
+            MulCircuitSynthesis::instance_io(config)
+        }
+
+        fn synthesize(
+            circuit: &Self::Circuit,
+            config: Self::Config,
+            synthesizer: &mut Synthesizer<Fr>,
+            cs: &Self::CS,
+        ) -> Result<(), Self::Error> {
+            MulCircuitSynthesis::synthesize(circuit, config, synthesizer, cs)?;
+            synthesizer.annotate_cell(Column::new(0, Any::Advice), 0, "lhs".to_owned());
+            synthesizer.annotate_cell(Column::new(2, Any::Advice), 0, "product".to_owned());
+            Ok(())
+        }
+    }
+
+    fn resolved() -> ResolvedIRCircuit {
+        common::setup();
+        let mut driver = Driver::default();
+        common::synthesize_and_generate_ir(
+            &mut driver,
+            AnnotatedMulCircuitSynthesis::default(),
+            IRGenParamsBuilder::new().build(),
+        )
+    }
+
+    #[test]
+    fn synthesized_circuit_keeps_the_annotations() {
+        let mut driver = Driver::default();
+        let syn = driver
+            .synthesize(&AnnotatedMulCircuitSynthesis::default())
+            .unwrap();
+        assert_eq!(
+            syn.cell_annotation(Column::new(0, Any::Advice), 0),
+            Some("lhs")
+        );
+        assert_eq!(
+            syn.cell_annotation(Column::new(2, Any::Advice), 0),
+            Some("product")
+        );
+        assert_eq!(syn.cell_annotation(Column::new(1, Any::Advice), 0), None);
+        assert_eq!(syn.cell_annotation(Column::new(0, Any::Fixed), 0), None);
+    }
+
+    #[test]
+    fn printer_emits_annotations_as_comment_lines() {
+        let resolved = resolved();
+        let output = resolved.display().with_cell_annotations(true).to_string();
+        let comments = output
+            .lines()
+            .map(str::trim)
+            .filter(|line| line.starts_with(';'))
+            .collect::<Vec<_>>();
+        assert!(comments.iter().any(|line| line.ends_with(" lhs")));
+        assert!(comments.iter().any(|line| line.ends_with(" product")));
+
+        // The annotations don't comment out any part of the statements.
+        let code = output
+            .lines()
+            .filter(|line| !line.trim().starts_with(';'))
+            .collect::<Vec<_>>();
+        assert!(code.iter().all(|line| !line.contains(';')));
+        let opened = code
+            .iter()
+            .map(|line| line.matches('(').count())
+            .sum::<usize>();
+        let closed = code
+            .iter()
+            .map(|line| line.matches(')').count())
+            .sum::<usize>();
+        assert_eq!(opened, closed);
+
+        let plain = resolved.display().to_string();
+        assert!(!plain.contains("lhs"));
+        let plain = plain
+            .lines()
+            .filter(|line| !line.trim().starts_with(';'))
+            .collect::<Vec<_>>();
+        assert_eq!(code, plain);
+    }
+}
+
 synthesis_impl!(MulCircuitSynthesis, mul::MulCircuit<Fr>, [0], [1]);
 synthesis_impl!(
     DeepCallstackCircuitSynthesis,
@@ -234,3 +772,69 @@ synthesis_impl!(
     [0],
     [1]
 );
+
+#[cfg(feature = "json-export")]
+mod json_export {
+    use halo2_llzk_frontend::{driver::Driver, ir::ResolvedIRCircuit};
+
+    use super::*;
+
+    #[test]
+    fn resolved_circuit_round_trip() {
+        common::setup();
+        let mut driver = Driver::default();
+        let circuit = DeepCallstackCircuitSynthesis::default();
+        let resolved = common::synthesize_and_generate_ir(
+            &mut driver,
+            circuit,
+            IRGenParamsBuilder::new().build(),
+        );
+
+        let json = resolved.to_json().unwrap();
+        let parsed = ResolvedIRCircuit::from_json(&json).unwrap();
+        assert!(ResolvedIRCircuit::diff(&resolved, &parsed).is_empty());
+        assert_eq!(parsed.prime(), resolved.prime());
+        assert_eq!(parsed.display().to_string(), resolved.display().to_string());
+        assert_eq!(parsed.to_json().unwrap(), json);
+    }
+
+    #[test]
+    fn field_elements_need_the_hex_prefix() {
+        common::setup();
+        let mut driver = Driver::default();
+        let circuit = MulCircuitSynthesis::default();
+        let resolved = common::synthesize_and_generate_ir(
+            &mut driver,
+            circuit,
+            IRGenParamsBuilder::new().build(),
+        );
+
+        let json = resolved.to_json().unwrap();
+        let prime = format!("\"0x{:x}\"", resolved.prime().as_ref());
+        assert!(json.contains(&prime));
+        let unprefixed = json.replacen(&prime, &prime.replacen("0x", "", 1), 1);
+        assert!(ResolvedIRCircuit::from_json(&unprefixed).is_err());
+    }
+}
+
+#[cfg(feature = "dot-backend")]
+mod dot_export {
+    use halo2_llzk_frontend::driver::Driver;
+
+    use super::*;
+
+    #[test]
+    fn mul_circuit_graph() {
+        common::setup();
+        let mut driver = Driver::default();
+        let resolved = common::synthesize_and_generate_ir(
+            &mut driver,
+            MulCircuitSynthesis::default(),
+            IRGenParamsBuilder::new().build(),
+        );
+        let dot = resolved.to_dot().unwrap();
+        assert!(dot.starts_with("digraph"));
+        assert!(dot.contains("label=\"gate "));
+        assert!(dot.contains("style=dashed"));
+    }
+}
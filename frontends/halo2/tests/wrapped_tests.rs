@@ -0,0 +1,27 @@
+use halo2_frontend_core::table::RegionIndex as CoreRegionIndex;
+use halo2_midnight_integration::Wrapped as _;
+use halo2_proofs::{circuit::RegionIndex, plonk::Fixed};
+
+#[test]
+fn wrap_options() {
+    let index = Some(RegionIndex::from(3)).wrap().map(CoreRegionIndex::from);
+    assert_eq!(index, Some(CoreRegionIndex::from(3)));
+
+    let none: Option<RegionIndex> = None;
+    assert!(none.wrap().is_none());
+}
+
+#[test]
+fn wrap_pairs() {
+    let (index, fixed) = (RegionIndex::from(1), Fixed).wrap();
+    assert_eq!(CoreRegionIndex::from(index), CoreRegionIndex::from(1));
+    let expected = Fixed;
+    assert_eq!(format!("{fixed:?}"), format!("{expected:?}"));
+
+    let (first, second) = (Some(RegionIndex::from(2)), (Fixed, RegionIndex::from(4))).wrap();
+    assert_eq!(
+        first.map(CoreRegionIndex::from),
+        Some(CoreRegionIndex::from(2))
+    );
+    assert_eq!(CoreRegionIndex::from(second.1), CoreRegionIndex::from(4));
+}
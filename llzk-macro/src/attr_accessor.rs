@@ -0,0 +1,97 @@
+//! Macro for emitting getters and setters of named attributes.
+
+use crate::parse::AttrAccessor;
+use convert_case::{Case, Casing};
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, Span};
+use quote::quote;
+use syn::{GenericParam, Generics, Lifetime, LifetimeParam, spanned::Spanned as _};
+
+/// Generates the `get_<attr>` and `set_<attr>` methods on the operation type.
+pub fn generate(input: &AttrAccessor) -> TokenStream {
+    match generate_impl(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn generate_impl(input: &AttrAccessor) -> syn::Result<proc_macro2::TokenStream> {
+    let op_type = input.op_type();
+    let attr_name = input.attr_name();
+    let attr_type = input.attr_type();
+    let snake_name = attr_name.value().to_case(Case::Snake);
+
+    // Operation types without explicit lifetimes are owned types that only carry the context
+    // lifetime.
+    let generics = if input.generics().params.is_empty() {
+        let mut generics = Generics::default();
+        generics
+            .params
+            .push(GenericParam::Lifetime(LifetimeParam::new(Lifetime::new(
+                "'c",
+                Span::call_site(),
+            ))));
+        generics
+    } else {
+        input.generics().clone()
+    };
+    let lifetimes: Vec<Lifetime> = generics
+        .lifetimes()
+        .map(|param| param.lifetime.clone())
+        .collect();
+    if lifetimes.len() != generics.params.len() || lifetimes.len() > 2 {
+        return Err(syn::Error::new(
+            input.generics().span(),
+            "attr_accessor only accepts the context lifetime and the lifetime of the reference",
+        ));
+    }
+    let ctx_lifetime = &lifetimes[0];
+
+    // The setter needs a lifetime for the borrow of the operation. Reuse the lifetime of the
+    // reference if present.
+    let (setter_generics, ref_lifetime) = match lifetimes.get(1) {
+        Some(lifetime) => (quote! {}, lifetime.clone()),
+        None => {
+            let lifetime = Lifetime::new("'__op", Span::call_site());
+            (quote! { <#lifetime> }, lifetime)
+        }
+    };
+    // The context outlives the borrow of the operation, as in the OperationLike implementations.
+    let mut impl_generics = generics.clone();
+    if let (Some(ref_lifetime), Some(ctx_param)) =
+        (lifetimes.get(1), impl_generics.lifetimes_mut().next())
+    {
+        ctx_param.bounds.push(ref_lifetime.clone());
+    }
+    let (impl_generics, _, _) = impl_generics.split_for_impl();
+    let (_, ty_generics, _) = generics.split_for_impl();
+
+    let getter_name = Ident::new(&format!("get_{snake_name}"), Span::call_site());
+    let setter_name = Ident::new(&format!("set_{snake_name}"), Span::call_site());
+    let getter_document = format!(
+        " Returns the `{}` attribute, or `None` if the op doesn't have it or it is of a different type.",
+        attr_name.value()
+    );
+    let setter_document = format!(" Sets the `{}` attribute.", attr_name.value());
+
+    Ok(quote! {
+        impl #impl_generics #op_type #ty_generics {
+            #[doc = #getter_document]
+            pub fn #getter_name(&self) -> Option<#attr_type> {
+                melior::ir::operation::OperationLike::attribute(self, #attr_name)
+                    .ok()
+                    .and_then(|attr| <#attr_type>::try_from(attr).ok())
+            }
+
+            #[doc = #setter_document]
+            ///
+            /// Only available if the type can mutate the operation.
+            pub fn #setter_name #setter_generics(&mut self, v: #attr_type)
+            where
+                Self: melior::ir::operation::OperationMutLike<#ctx_lifetime, #ref_lifetime>,
+            {
+                melior::ir::operation::OperationMutLike::set_attribute(self, #attr_name, v.into())
+            }
+        }
+    })
+}
@@ -0,0 +1,144 @@
+//! Derive macro for operation wrapper types.
+
+use convert_case::{Case, Casing};
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{
+    Data, DeriveInput, Expr, ExprLit, Fields, GenericParam, Lifetime, LifetimeParam, Lit, Meta,
+    Result, spanned::Spanned,
+};
+
+/// Name of the field that holds the raw operation.
+const RAW_FIELD: &str = "raw";
+
+/// Generates the boilerplate of an operation wrapper type.
+pub fn generate(input: &DeriveInput) -> Result<TokenStream> {
+    let name = &input.ident;
+    let op_name = op_name(input)?;
+
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.span(),
+            "DialectOp can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new(
+            data.fields.span(),
+            "DialectOp requires a struct with named fields",
+        ));
+    };
+    if !fields
+        .named
+        .iter()
+        .any(|field| field.ident.as_ref().is_some_and(|i| i == RAW_FIELD))
+    {
+        return Err(syn::Error::new(
+            fields.span(),
+            format!("DialectOp requires a field named `{RAW_FIELD}` of type `MlirOperation`"),
+        ));
+    }
+    let other_fields = fields
+        .named
+        .iter()
+        .filter_map(|field| field.ident.as_ref())
+        .filter(|ident| *ident != RAW_FIELD);
+
+    let lifetimes: Vec<&Lifetime> = input
+        .generics
+        .lifetimes()
+        .map(|param| &param.lifetime)
+        .collect();
+    let Some(ctx_lifetime) = lifetimes.first().copied() else {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            "DialectOp requires the context lifetime as the first generic parameter",
+        ));
+    };
+
+    // The OperationLike trait needs a lifetime for the borrow of the operation. Reuse the second
+    // lifetime of the struct if present.
+    let mut like_generics = input.generics.clone();
+    let ref_lifetime = match lifetimes.get(1) {
+        Some(lifetime) => (*lifetime).clone(),
+        None => {
+            let lifetime = Lifetime::new("'__op", Span::call_site());
+            like_generics.params.insert(
+                lifetimes.len(),
+                GenericParam::Lifetime(LifetimeParam::new(lifetime.clone())),
+            );
+            lifetime
+        }
+    };
+    // The context outlives the borrow of the operation, as in the rest of the OperationLike
+    // implementations.
+    if let Some(ctx_param) = like_generics.lifetimes_mut().next() {
+        ctx_param.bounds.push(ref_lifetime.clone());
+    }
+    let (like_impl_generics, _, _) = like_generics.split_for_impl();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let constructor_doc = format!(
+        " Creates a `{name}` from a raw operation.\n\n # Safety\n\n The MLIR operation must be a valid pointer to a '{op_name}' op."
+    );
+    let isa_doc = format!(" Return `true` iff the given op is `{op_name}`.");
+    let snake_name = name.to_string().to_case(Case::Snake);
+    let isa_name = syn::Ident::new(&format!("is_{snake_name}"), name.span());
+
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #[doc = #constructor_doc]
+            pub unsafe fn from_raw(raw: mlir_sys::MlirOperation) -> Self {
+                Self {
+                    raw,
+                    #(#other_fields: Default::default(),)*
+                }
+            }
+        }
+
+        impl #like_impl_generics melior::ir::operation::OperationLike<#ctx_lifetime, #ref_lifetime>
+            for #name #ty_generics #where_clause
+        {
+            fn to_raw(&self) -> mlir_sys::MlirOperation {
+                self.raw
+            }
+        }
+
+        #[doc = #isa_doc]
+        #[inline]
+        pub fn #isa_name<'c: 'a, 'a>(op: &impl melior::ir::operation::OperationLike<'c, 'a>) -> bool {
+            crate::operation::isa(op, #op_name)
+        }
+    }
+    .into())
+}
+
+/// Extracts the name of the operation from the `#[op_name = "..."]` attribute.
+fn op_name(input: &DeriveInput) -> Result<String> {
+    let attr = input
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("op_name"))
+        .ok_or_else(|| {
+            syn::Error::new(
+                input.span(),
+                "DialectOp requires an `#[op_name = \"...\"]` attribute",
+            )
+        })?;
+    match &attr.meta {
+        Meta::NameValue(meta) => match &meta.value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(lit), ..
+            }) => Ok(lit.value()),
+            value => Err(syn::Error::new(
+                value.span(),
+                "expected a string literal with the name of the operation",
+            )),
+        },
+        meta => Err(syn::Error::new(
+            meta.span(),
+            "expected `#[op_name = \"...\"]`",
+        )),
+    }
+}
@@ -8,9 +8,12 @@ use parse::*;
 use proc_macro::TokenStream;
 use quote::quote;
 use std::error::Error as StdError;
-use syn::parse_macro_input;
+use syn::{DeriveInput, parse_macro_input};
 
+mod attr_accessor;
+mod dialect_op;
 mod error;
+mod op_is_a;
 mod parse;
 mod pass;
 
@@ -47,6 +50,62 @@ pub fn passes(stream: TokenStream) -> TokenStream {
     ))
 }
 
+/// Creates a predicate that checks if an operation is of a given kind.
+///
+/// `op_is_a!(SomeName, "some.opname")` generates `is_some_name_op`. If a wrapper type is given as
+/// third argument, `op_is_a!(SomeName, "some.opname", SomeNameOpRef<'c, 'a>)`, a
+/// `downcast_to_some_name` function is generated as well that converts an `OperationRef<'c, 'a>`
+/// into the wrapper via its `TryFrom` implementation. The wrapper may use the `'c` and `'a`
+/// lifetimes.
+#[proc_macro]
+pub fn op_is_a(stream: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(stream as OpIsA);
+
+    op_is_a::generate(&input)
+}
+
+/// Creates a getter and a setter for a named attribute of an operation type.
+///
+/// `attr_accessor!(SomeOp, "attr_name", SomeAttribute<'c>)` generates `get_attr_name` and
+/// `set_attr_name` methods on `SomeOp<'c>`. Reference types are given with their lifetimes, as in
+/// `attr_accessor!(SomeOpRef<'c, 'a>, "attr_name", SomeAttribute<'c>)`. The attribute type must
+/// be convertible from and into `melior::ir::Attribute` and may use the context lifetime.
+///
+/// The getter returns `None` if the op doesn't have the attribute or it is of a different type.
+/// The setter can only be called on types that implement `OperationMutLike`.
+#[proc_macro]
+pub fn attr_accessor(stream: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(stream as AttrAccessor);
+
+    attr_accessor::generate(&input)
+}
+
+/// Creates a function that builds a pass manager running a sequence of passes.
+///
+/// `pass_pipeline!(MyPipeline { PassA, PassB })` generates `create_my_pipeline_pass_manager`,
+/// which adds the passes created by `create_pass_a_pass` and `create_pass_b_pass`, in that order.
+#[proc_macro]
+pub fn pass_pipeline(stream: TokenStream) -> TokenStream {
+    let pipeline = parse_macro_input!(stream as PassPipeline);
+
+    pass::generate_pipeline(&pipeline)
+}
+
+/// Derives the boilerplate of an operation wrapper type.
+///
+/// The struct must have the context lifetime as its first generic parameter, a field named `raw`
+/// of type `MlirOperation` and any other fields must implement [`Default`]. The name of the
+/// operation is given with the `#[op_name = "dialect.op"]` attribute.
+///
+/// Generates the `from_raw` constructor, the `OperationLike` implementation, which provides
+/// `to_raw`, and an `is_<name>` predicate function.
+#[proc_macro_derive(DialectOp, attributes(op_name))]
+pub fn dialect_op(stream: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(stream as DeriveInput);
+
+    dialect_op::generate(&input).unwrap_or_else(|error| error.to_compile_error().into())
+}
+
 /// Converts a [`Result::Err`] into a compilation error.
 fn convert_result(result: Result<TokenStream, Box<dyn StdError>>) -> TokenStream {
     result.unwrap_or_else(|error| {
@@ -0,0 +1,49 @@
+//! Macro for emitting predicates that check the kind of an operation.
+
+use crate::parse::OpIsA;
+use convert_case::{Case, Casing};
+use proc_macro::TokenStream;
+use proc_macro2::Ident;
+use quote::quote;
+
+/// Generates the `is_<name>_op` predicate and, if a wrapper type was given, the
+/// `downcast_to_<name>` conversion.
+pub fn generate(input: &OpIsA) -> TokenStream {
+    let snake_name = input.name().to_string().to_case(Case::Snake);
+    let op_name = input.op_name();
+
+    let predicate_name = Ident::new(&format!("is_{snake_name}_op"), input.name().span());
+    let document = format!(" Return `true` iff the given op is `{}`.", op_name.value());
+    let mut stream = quote! {
+        #[doc = #document]
+        #[inline]
+        pub fn #predicate_name<'c: 'a, 'a>(
+            op: &impl melior::ir::operation::OperationLike<'c, 'a>,
+        ) -> bool {
+            crate::operation::isa(op, #op_name)
+        }
+    };
+
+    if let Some(wrapper) = input.wrapper() {
+        let downcast_name = Ident::new(&format!("downcast_to_{snake_name}"), input.name().span());
+        let document = format!(
+            " Converts the operation into `{}` if it is `{}`.",
+            quote!(#wrapper).to_string().replace(' ', ""),
+            op_name.value()
+        );
+        stream.extend(quote! {
+            #[doc = #document]
+            pub fn #downcast_name<'c: 'a, 'a>(
+                op: melior::ir::operation::OperationRef<'c, 'a>,
+            ) -> Option<#wrapper> {
+                if #predicate_name(&op) {
+                    <#wrapper as ::core::convert::TryFrom<_>>::try_from(op).ok()
+                } else {
+                    None
+                }
+            }
+        });
+    }
+
+    stream.into()
+}
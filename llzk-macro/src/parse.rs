@@ -1,7 +1,13 @@
 //! Types for parsing the macros DSLs.
 
+mod attr_accessor;
 mod identifier_list;
+mod op_is_a;
+mod pass_pipeline;
 mod pass_set;
 
+pub use attr_accessor::AttrAccessor;
 pub use identifier_list::IdentifierList;
+pub use op_is_a::OpIsA;
+pub use pass_pipeline::PassPipeline;
 pub use pass_set::PassSet;
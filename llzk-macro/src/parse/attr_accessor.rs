@@ -0,0 +1,59 @@
+//! Arguments of the [`crate::attr_accessor`] macro.
+
+use proc_macro2::Ident;
+use syn::{
+    Generics, LitStr, Result, Token, Type,
+    parse::{Parse, ParseStream},
+};
+
+/// Struct representing the small DSL used by the [`crate::attr_accessor`] macro.
+///
+/// Accepts an identifier (the operation type) with optional lifetime parameters, followed by a
+/// comma, a literal string (the name of the attribute), another comma and the type of the
+/// attribute.
+pub struct AttrAccessor {
+    op_type: Ident,
+    generics: Generics,
+    attr_name: LitStr,
+    attr_type: Type,
+}
+
+impl AttrAccessor {
+    pub const fn op_type(&self) -> &Ident {
+        &self.op_type
+    }
+
+    pub const fn generics(&self) -> &Generics {
+        &self.generics
+    }
+
+    pub const fn attr_name(&self) -> &LitStr {
+        &self.attr_name
+    }
+
+    pub const fn attr_type(&self) -> &Type {
+        &self.attr_type
+    }
+}
+
+impl Parse for AttrAccessor {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let op_type = input.parse()?;
+        let generics = input.parse()?;
+        <Token![,]>::parse(input)?;
+        let attr_name = input.parse()?;
+        <Token![,]>::parse(input)?;
+        let attr_type = input.parse()?;
+        // Allow a trailing comma.
+        if !input.is_empty() {
+            <Token![,]>::parse(input)?;
+        }
+
+        Ok(Self {
+            op_type,
+            generics,
+            attr_name,
+            attr_type,
+        })
+    }
+}
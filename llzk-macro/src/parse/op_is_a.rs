@@ -0,0 +1,56 @@
+//! Arguments of the [`crate::op_is_a`] macro.
+
+use proc_macro2::Ident;
+use syn::{
+    LitStr, Result, Token, Type,
+    parse::{Parse, ParseStream},
+};
+
+/// Struct representing the small DSL used by the [`crate::op_is_a`] macro.
+///
+/// Accepts an identifier (the name of the operation), followed by a comma and a literal string
+/// (the full name of the MLIR operation) and, optionally, a comma and the wrapper type of the
+/// operation.
+pub struct OpIsA {
+    name: Ident,
+    op_name: LitStr,
+    wrapper: Option<Type>,
+}
+
+impl OpIsA {
+    pub const fn name(&self) -> &Ident {
+        &self.name
+    }
+
+    pub const fn op_name(&self) -> &LitStr {
+        &self.op_name
+    }
+
+    pub const fn wrapper(&self) -> Option<&Type> {
+        self.wrapper.as_ref()
+    }
+}
+
+impl Parse for OpIsA {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name = input.parse()?;
+        <Token![,]>::parse(input)?;
+        let op_name = input.parse()?;
+        let wrapper = if input.is_empty() {
+            None
+        } else {
+            <Token![,]>::parse(input)?;
+            Some(input.parse()?)
+        };
+        // Allow a trailing comma.
+        if !input.is_empty() {
+            <Token![,]>::parse(input)?;
+        }
+
+        Ok(Self {
+            name,
+            op_name,
+            wrapper,
+        })
+    }
+}
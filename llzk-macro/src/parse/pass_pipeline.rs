@@ -0,0 +1,42 @@
+//! Type representing a named sequence of passes.
+
+use super::IdentifierList;
+use proc_macro2::Ident;
+use syn::{
+    Result, braced,
+    parse::{Parse, ParseStream},
+};
+
+/// Struct representing the small DSL used by the [`crate::pass_pipeline`] macro.
+///
+/// Accepts an identifier (the name of the pipeline) followed by a braced list of identifiers (the
+/// names of the passes, in the order they run).
+pub struct PassPipeline {
+    name: Ident,
+    passes: IdentifierList,
+}
+
+impl PassPipeline {
+    pub const fn name(&self) -> &Ident {
+        &self.name
+    }
+
+    pub fn passes(&self) -> &[Ident] {
+        self.passes.identifiers()
+    }
+}
+
+impl Parse for PassPipeline {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name = input.parse()?;
+
+        Ok(Self {
+            name,
+            passes: {
+                let content;
+                braced!(content in input);
+                content.parse::<IdentifierList>()?
+            },
+        })
+    }
+}
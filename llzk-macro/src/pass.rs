@@ -1,6 +1,6 @@
 //! Macro for emitting create and register functions for a set of passes.
 
-use crate::error::Error;
+use crate::{error::Error, parse::PassPipeline};
 use convert_case::{Case, Casing};
 use proc_macro::TokenStream;
 use proc_macro2::{Ident, Span};
@@ -73,6 +73,36 @@ pub fn generate(
     Ok(stream)
 }
 
+/// Generates a function that creates a pass manager with the passes of the pipeline.
+///
+/// Each pass is created with the `create_<pass>_pass` function generated by the pass macros,
+/// which must be in scope where the pipeline is defined.
+pub fn generate_pipeline(pipeline: &PassPipeline) -> TokenStream {
+    let name = pipeline.name();
+    let function_name = Ident::new(
+        &format!(
+            "create_{}_pass_manager",
+            name.to_string().to_case(Case::Snake)
+        ),
+        name.span(),
+    );
+    let document = format!(" Creates a pass manager that runs the `{name}` pipeline.");
+    let create_functions = pipeline
+        .passes()
+        .iter()
+        .map(|pass| create_function_name("create", &format!("{pass}Pass"), pass.span()));
+
+    quote! {
+        #[doc = #document]
+        pub fn #function_name(context: &melior::Context) -> melior::pass::PassManager<'_> {
+            let pass_manager = melior::pass::PassManager::new(context);
+            #(pass_manager.add_pass(#create_functions());)*
+            pass_manager
+        }
+    }
+    .into()
+}
+
 fn create_function_name(prefix: &str, pass_name: &str, span: Span) -> Ident {
     Ident::new(
         &format!("{}_{}", prefix, &pass_name.to_case(Case::Snake)),
@@ -87,5 +87,28 @@ c_str_constant!(
     LLZK_LANG_ATTR_NAME
 );
 
+// The C API does not define the following constants yet. They are defined on the Rust side with
+// the same type as the ones above so they can be switched to `c_str_constant!` once `llzk-lib`
+// exports them.
+
+/// Symbol name for the entry point function of the main component.
+///
+/// LLZK does not have a dedicated entry point function; the witness generation function of the
+/// main component is used instead.
+pub static FUNC_NAME_MAIN: LazyLock<&'static str> = LazyLock::new(|| *FUNC_NAME_COMPUTE);
+
+/// Name of the field that holds the value of the struct/component representing a signal.
+pub static COMPONENT_NAME_FIELD: LazyLock<&'static str> = LazyLock::new(|| "reg");
+
+static LLZK_VERSION: LazyLock<&'static str> = LazyLock::new(|| env!("CARGO_PKG_VERSION"));
+
+/// Returns the version of the LLZK library these bindings were built against.
+///
+/// The C API does not expose a version query yet so this falls back to the version of this
+/// crate.
+pub fn llzk_version() -> &'static str {
+    *LLZK_VERSION
+}
+
 #[cfg(test)]
 mod sanity_tests;
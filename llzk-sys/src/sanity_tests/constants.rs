@@ -1,8 +1,9 @@
 use std::ffi::{CStr, c_char};
 
 use crate::{
-    LLZK_COMPONENT_NAME_MAIN, LLZK_COMPONENT_NAME_SIGNAL, LLZK_FUNC_NAME_COMPUTE,
-    LLZK_FUNC_NAME_CONSTRAIN, LLZK_LANG_ATTR_NAME,
+    COMPONENT_NAME_FIELD, FUNC_NAME_COMPUTE, FUNC_NAME_MAIN, LLZK_COMPONENT_NAME_MAIN,
+    LLZK_COMPONENT_NAME_SIGNAL, LLZK_FUNC_NAME_COMPUTE, LLZK_FUNC_NAME_CONSTRAIN,
+    LLZK_LANG_ATTR_NAME,
 };
 
 fn unwrap(s: *const c_char) -> String {
@@ -20,3 +21,9 @@ fn test_llzk_constants() {
     assert_eq!(unwrap(unsafe { LLZK_FUNC_NAME_CONSTRAIN }), "constrain");
     assert_eq!(unwrap(unsafe { LLZK_LANG_ATTR_NAME }), "veridise.lang");
 }
+
+#[test]
+fn test_rust_side_constants() {
+    assert_eq!(*FUNC_NAME_MAIN, *FUNC_NAME_COMPUTE);
+    assert_eq!(*COMPONENT_NAME_FIELD, "reg");
+}
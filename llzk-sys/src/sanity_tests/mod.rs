@@ -2,7 +2,7 @@ use mlir_sys::*;
 use rstest::{fixture, rstest};
 use std::ffi::CString;
 
-use crate::{LlzkAffineMapOperandsBuilder, llzkRegisterAllDialects};
+use crate::{LlzkAffineMapOperandsBuilder, llzk_version, llzkRegisterAllDialects};
 
 mod builder;
 mod constants;
@@ -129,6 +129,19 @@ fn test_location(context: TestContext, registry: TestRegistry) {
     }
 }
 
+#[test]
+fn version_matches() {
+    let version = llzk_version();
+    assert!(!version.is_empty());
+    let core = version.split(['-', '+']).next().unwrap();
+    let parts = core
+        .split('.')
+        .map(|part| part.parse::<u64>())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(parts.len(), 3, "{version} is not a semver version");
+}
+
 // [`LlzkAffineMapOperandsBuilder`] MUST implement Copy.
 trait AssertCopy: Copy {}
 impl AssertCopy for LlzkAffineMapOperandsBuilder {}
@@ -26,6 +26,35 @@ fn type_new_with_dims(ctx: Context) {
     );
 }
 
+#[rstest]
+fn type_new_1d(ctx: Context) {
+    let idx_typ = Type::index(&ctx);
+    assert_eq!(
+        ArrayType::new_1d(idx_typ, 3),
+        ArrayType::new_with_dims(idx_typ, &[3])
+    );
+}
+
+#[rstest]
+fn type_concat_type(ctx: Context) {
+    let idx_typ = Type::index(&ctx);
+    let lhs = ArrayType::new_with_dims(idx_typ, &[2, 4]);
+    let rhs = ArrayType::new_with_dims(idx_typ, &[3, 4]);
+
+    assert_eq!(
+        lhs.concat_type(&rhs),
+        Ok(ArrayType::new_with_dims(idx_typ, &[5, 4]))
+    );
+    assert!(
+        lhs.concat_type(&ArrayType::new_with_dims(idx_typ, &[3, 2]))
+            .is_err()
+    );
+    assert!(
+        lhs.concat_type(&ArrayType::new_with_dims(Type::float64(&ctx), &[3, 4]))
+            .is_err()
+    );
+}
+
 #[rstest]
 fn op_new_with_values(ctx: Context) {
     let op_builder = OpBuilder::new(&ctx);
@@ -1,11 +1,11 @@
 //! Implementation of `!array.type` type.
 
-use crate::utils::IsA;
+use crate::{error::Error, utils::IsA};
 use llzk_sys::{
     llzkArrayTypeGet, llzkArrayTypeGetDim, llzkArrayTypeGetElementType, llzkArrayTypeGetNumDims,
     llzkArrayTypeGetWithNumericDims, llzkTypeIsAArrayType,
 };
-use melior::ir::{Attribute, Type, TypeLike};
+use melior::ir::{Attribute, Type, TypeLike, attribute::IntegerAttribute};
 use mlir_sys::MlirType;
 
 /// Represents the `!array.type` type.
@@ -43,6 +43,12 @@ impl<'c> ArrayType<'c> {
         }
     }
 
+    /// Creates a new one-dimensional type with the given element type and size.
+    #[inline]
+    pub fn new_1d(element_type: Type<'c>, size: i64) -> Self {
+        Self::new_with_dims(element_type, &[size])
+    }
+
     /// Returns the element type of the array.
     pub fn element_type(&self) -> Type<'c> {
         unsafe { Type::from_raw(llzkArrayTypeGetElementType(self.to_raw())) }
@@ -63,6 +69,39 @@ impl<'c> ArrayType<'c> {
     pub fn dims(&self) -> Vec<Attribute<'c>> {
         (0..self.num_dims()).map(|idx| self.dim(idx)).collect()
     }
+
+    /// Returns the type of the array obtained by concatenating an array of this type with an
+    /// array of type `other` along the first dimension.
+    ///
+    /// Both types must have the same element type and the same dimensions except the first one,
+    /// and the first dimension of both must be a constant.
+    pub fn concat_type(&self, other: &ArrayType<'c>) -> Result<ArrayType<'c>, Error> {
+        if self.element_type() != other.element_type() {
+            return Err(Error::GeneralError(
+                "cannot concatenate arrays with different element types",
+            ));
+        }
+        let lhs_dims = self.dims();
+        let rhs_dims = other.dims();
+        if lhs_dims.is_empty() || lhs_dims.len() != rhs_dims.len() || lhs_dims[1..] != rhs_dims[1..]
+        {
+            return Err(Error::GeneralError(
+                "cannot concatenate arrays with incompatible dimensions",
+            ));
+        }
+        let const_dim = |dim: Attribute<'c>| {
+            IntegerAttribute::try_from(dim)
+                .map(|dim| dim.value())
+                .map_err(|_| Error::GeneralError("cannot concatenate arrays of symbolic length"))
+        };
+        let mut dims = lhs_dims;
+        dims[0] = IntegerAttribute::new(
+            Type::index(unsafe { self.r#type.context().to_ref() }),
+            const_dim(dims[0])? + const_dim(rhs_dims[0])?,
+        )
+        .into();
+        Ok(Self::new(self.element_type(), &dims))
+    }
 }
 
 impl<'c> TypeLike<'c> for ArrayType<'c> {
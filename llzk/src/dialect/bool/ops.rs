@@ -4,6 +4,7 @@ use crate::{
     ident,
 };
 
+use melior::dialect::arith;
 use melior::ir::{
     Location, Operation, Value,
     attribute::StringAttribute,
@@ -119,6 +120,57 @@ binop!(or);
 binop!(xor);
 unop!(not);
 
+/// Creates an operation that computes `lhs -> rhs` for two `i1` values.
+///
+/// The `bool` dialect does not define an implication operation. Since `false < true`, the
+/// implication is emitted as an unsigned `arith.cmpi ule`.
+pub fn implies<'c>(
+    location: Location<'c>,
+    lhs: Value<'c, '_>,
+    rhs: Value<'c, '_>,
+) -> Result<Operation<'c>, Error> {
+    let ctx = location.context();
+    Ok(arith::cmpi(
+        unsafe { ctx.to_ref() },
+        arith::CmpiPredicate::Ule,
+        lhs,
+        rhs,
+        location,
+    ))
+}
+
+/// Creates an operation that computes `lhs <-> rhs` for two `i1` values.
+///
+/// The `bool` dialect does not define an equivalence operation so it is emitted as an
+/// `arith.cmpi eq`.
+pub fn iff<'c>(
+    location: Location<'c>,
+    lhs: Value<'c, '_>,
+    rhs: Value<'c, '_>,
+) -> Result<Operation<'c>, Error> {
+    let ctx = location.context();
+    Ok(arith::cmpi(
+        unsafe { ctx.to_ref() },
+        arith::CmpiPredicate::Eq,
+        lhs,
+        rhs,
+        location,
+    ))
+}
+
+/// Creates an operation that selects `if_true` when `condition` holds and `if_false` otherwise.
+///
+/// The `bool` dialect does not define a selection operation so it is emitted as an
+/// `arith.select`.
+pub fn select<'c>(
+    location: Location<'c>,
+    condition: Value<'c, '_>,
+    if_true: Value<'c, '_>,
+    if_false: Value<'c, '_>,
+) -> Result<Operation<'c>, Error> {
+    Ok(arith::select(condition, if_true, if_false, location))
+}
+
 /// Creates a `bool.assert` operation.
 pub fn assert<'c>(
     location: Location<'c>,
@@ -1,10 +1,18 @@
 //! `constrain` dialect.
+//!
+//! [`range`] has no counterpart in `llzk-lib`, which does not define a `constrain.range`
+//! operation. It is always emitted in its composite form and there is no Cargo feature for
+//! switching to a single operation, since enabling it would produce IR that `llzk-lib` rejects.
+//! Because the composite form spans several operations, [`range`] appends them to a block and
+//! returns `Result<(), Error>` instead of returning a single [`Operation`] like the other
+//! builders in this module.
 
+use crate::error::Error;
 use llzk_sys::mlirGetDialectHandle__llzk__constrain__;
 use melior::{
     dialect::DialectHandle,
     ir::{
-        Location, Operation, Value,
+        BlockLike, Location, Operation, Value,
         operation::{OperationBuilder, OperationLike},
     },
 };
@@ -41,3 +49,31 @@ pub fn r#in<'c>(location: Location<'c>, lhs: Value<'c, '_>, rhs: Value<'c, '_>)
 pub fn is_constrain_in<'c: 'a, 'a>(op: &impl OperationLike<'c, 'a>) -> bool {
     crate::operation::isa(op, "constrain.in")
 }
+
+/// Constrains `value` to lie within `[low, high]`, appending the operations to the given block.
+///
+/// `llzk-lib` does not define a `constrain.range` operation, so the range check is emitted as a
+/// pair of `bool.cmp` + `bool.assert` operations, one for each bound.
+pub fn range<'c, 'a>(
+    block: &impl BlockLike<'c, 'a>,
+    location: Location<'c>,
+    value: Value<'c, '_>,
+    low: Value<'c, '_>,
+    high: Value<'c, '_>,
+) -> Result<(), Error> {
+    use crate::dialect::bool;
+
+    let lower = block.append_operation(bool::ge(location, value, low)?);
+    block.append_operation(bool::assert(
+        location,
+        lower.result(0)?.into(),
+        Some("value is below the lower bound of the range"),
+    )?);
+    let upper = block.append_operation(bool::le(location, value, high)?);
+    block.append_operation(bool::assert(
+        location,
+        upper.result(0)?.into(),
+        Some("value is above the upper bound of the range"),
+    )?);
+    Ok(())
+}
@@ -8,8 +8,8 @@ pub use attrs::FeltConstAttribute;
 use llzk_sys::mlirGetDialectHandle__llzk__felt__;
 use melior::dialect::DialectHandle;
 pub use ops::{
-    add, bit_and, bit_not, bit_or, bit_xor, constant, div, inv, mul, neg, pow, shl, shr, sintdiv,
-    smod, sub, uintdiv, umod,
+    add, bit_and, bit_not, bit_or, bit_xor, constant, div, inv, mul, neg, pow, pow_const, shl, shr,
+    sintdiv, smod, sub, uintdiv, umod,
 };
 pub use ops::{
     is_felt_add, is_felt_bit_and, is_felt_bit_not, is_felt_bit_or, is_felt_bit_xor, is_felt_const,
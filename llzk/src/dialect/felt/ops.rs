@@ -2,7 +2,7 @@ use crate::{error::Error, ident};
 
 use super::{FeltConstAttribute, FeltType};
 use melior::ir::{
-    Location, Operation, Value,
+    BlockLike, Location, Operation, Value,
     operation::{OperationBuilder, OperationLike},
 };
 
@@ -97,6 +97,24 @@ pub fn constant<'c>(
         .map_err(Into::into)
 }
 
+/// Creates a `felt.pow` operation with a constant exponent.
+///
+/// The `felt.const` operation that holds the exponent is appended to the given block and the
+/// returned `felt.pow` operation uses its result as the exponent operand.
+pub fn pow_const<'c, 'a>(
+    block: &impl BlockLike<'c, 'a>,
+    location: Location<'c>,
+    base: Value<'c, '_>,
+    exponent: u64,
+) -> Result<Operation<'c>, Error> {
+    let ctx = location.context();
+    let exponent = block.append_operation(constant(
+        location,
+        FeltConstAttribute::new(unsafe { ctx.to_ref() }, exponent),
+    )?);
+    pow(location, base, exponent.result(0)?.into())
+}
+
 /// Return `true` iff the given op is `felt.const`.
 #[inline]
 pub fn is_felt_const<'c: 'a, 'a>(op: &impl OperationLike<'c, 'a>) -> bool {
@@ -108,6 +126,51 @@ mod tests {
     use super::*;
     use crate::prelude::*;
     use quickcheck_macros::quickcheck;
+    use std::marker::PhantomData;
+
+    /// Owned `felt.const` wrapper with its boilerplate derived.
+    #[derive(llzk_macro::DialectOp)]
+    #[op_name = "felt.const"]
+    struct DerivedConst<'c> {
+        raw: mlir_sys::MlirOperation,
+        _context: PhantomData<&'c melior::Context>,
+    }
+
+    /// Non-owned `felt.const` wrapper with its boilerplate derived.
+    #[derive(llzk_macro::DialectOp)]
+    #[op_name = "felt.const"]
+    struct DerivedConstRef<'c, 'a> {
+        raw: mlir_sys::MlirOperation,
+        _reference: PhantomData<&'a Operation<'c>>,
+    }
+
+    llzk_macro::attr_accessor!(DerivedConstRef<'c, 'a>, "value", FeltConstAttribute<'c>);
+
+    llzk_macro::op_is_a!(FeltConst, "felt.const", OperationRef<'c, 'a>);
+
+    #[quickcheck]
+    fn derived_op_wrappers(value: u64) {
+        let ctx = LlzkContext::new();
+        let op = constant(
+            Location::unknown(&ctx),
+            FeltConstAttribute::new(&ctx, value),
+        )
+        .unwrap();
+
+        let owned = unsafe { DerivedConst::from_raw(op.to_raw()) };
+        assert_eq!(owned.to_raw().ptr, op.to_raw().ptr);
+        assert!(is_derived_const(&owned));
+        assert!(is_derived_const(&op));
+
+        let reference = unsafe { DerivedConstRef::from_raw(op.to_raw()) };
+        assert_eq!(reference.to_raw().ptr, op.to_raw().ptr);
+        assert!(is_derived_const_ref(&reference));
+        assert!(reference.verify());
+        assert_eq!(
+            reference.get_value().map(Attribute::from),
+            Some(FeltConstAttribute::new(&ctx, value).into())
+        );
+    }
 
     #[quickcheck]
     fn felt_const_op(value: u64) {
@@ -130,4 +193,35 @@ mod tests {
         .unwrap();
         assert!(is_felt_const(&op), "operation {op:?} failed isa test");
     }
+
+    #[test]
+    fn generated_op_predicates() {
+        let ctx = LlzkContext::new();
+        let loc = Location::unknown(&ctx);
+        let block = Block::new(&[]);
+        let base = block.append_operation(constant(loc, FeltConstAttribute::new(&ctx, 3)).unwrap());
+        let pow = pow_const(&block, loc, base.result(0).unwrap().into(), 2).unwrap();
+        let pow = block.append_operation(pow);
+        assert!(is_felt_const_op(&base));
+        assert!(!is_felt_const_op(&pow));
+        assert!(downcast_to_felt_const(base).is_some());
+        assert!(downcast_to_felt_const(pow).is_none());
+    }
+
+    #[quickcheck]
+    fn felt_pow_const_op(exponent: u64) {
+        let ctx = LlzkContext::new();
+        let loc = Location::unknown(&ctx);
+        let block = Block::new(&[]);
+        let base = block.append_operation(constant(loc, FeltConstAttribute::new(&ctx, 3)).unwrap());
+        let op = pow_const(&block, loc, base.result(0).unwrap().into(), exponent).unwrap();
+        assert!(is_felt_pow(&op), "operation {op:?} failed isa test");
+        assert!(
+            block
+                .first_operation()
+                .and_then(|op| op.next_in_block())
+                .is_some_and(|op| is_felt_const(&op)),
+            "exponent constant was not appended to the block"
+        );
+    }
 }
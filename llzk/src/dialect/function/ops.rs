@@ -7,6 +7,7 @@ use crate::{
     symbol_ref::{SymbolRefAttrLike, SymbolRefAttribute},
 };
 
+use llzk_macro::attr_accessor;
 use llzk_sys::{
     llzkCallOpBuild, llzkCallOpGetCalleeIsCompute, llzkCallOpGetCalleeIsConstrain,
     llzkCallOpGetCalleeIsStructCompute, llzkCallOpGetCalleeIsStructConstrain,
@@ -26,7 +27,7 @@ use melior::{
     ir::{
         Attribute, AttributeLike, BlockLike as _, Location, Operation, RegionLike as _, Type,
         TypeLike, Value,
-        attribute::{ArrayAttribute, TypeAttribute},
+        attribute::{ArrayAttribute, StringAttribute, TypeAttribute},
         block::BlockArgument,
         operation::{OperationBuilder, OperationLike, OperationMutLike},
         r#type::FunctionType,
@@ -191,6 +192,8 @@ pub trait FuncDefOpMutLike<'c: 'a, 'a>: FuncDefOpLike<'c, 'a> + OperationMutLike
 //===----------------------------------------------------------------------===//
 
 llzk_op_type!(FuncDefOp, llzkOperationIsAFuncDefOp, "function.def");
+attr_accessor!(FuncDefOp, "sym_name", StringAttribute<'c>);
+attr_accessor!(FuncDefOp, "function_type", TypeAttribute<'c>);
 
 impl<'a, 'c: 'a> FuncDefOpLike<'c, 'a> for FuncDefOp<'c> {}
 
@@ -2,15 +2,20 @@
 
 use llzk_sys::mlirGetDialectHandle__llzk__global__;
 use melior::{
+    Context,
     dialect::DialectHandle,
     ir::{
         Attribute, Location, Operation, Type, Value,
-        attribute::{StringAttribute, TypeAttribute},
+        attribute::{ArrayAttribute, StringAttribute, TypeAttribute},
         operation::{OperationBuilder, OperationLike},
     },
 };
 
-use crate::{ident, symbol_ref::SymbolRefAttribute};
+use crate::{
+    dialect::{array::ArrayType, felt::FeltConstAttribute},
+    ident,
+    symbol_ref::SymbolRefAttribute,
+};
 
 /// Returns a handle to the `global` dialect.
 pub fn handle() -> DialectHandle {
@@ -48,6 +53,35 @@ pub fn def<'c>(
         .expect("valid operation")
 }
 
+/// Creates the attribute used as initial value of a global array of felts.
+///
+/// The values are given in the order of the flattened/linearized array.
+pub fn array_initial_value<'c>(
+    context: &'c Context,
+    values: &[FeltConstAttribute<'c>],
+) -> Attribute<'c> {
+    let values: Vec<Attribute> = values.iter().map(|value| (*value).into()).collect();
+    ArrayAttribute::new(context, &values).into()
+}
+
+/// Constructs a 'global.def' operation of array type initialized with the given felt values.
+pub fn def_array<'c>(
+    location: Location<'c>,
+    name: &str,
+    r#type: ArrayType<'c>,
+    constant: bool,
+    values: &[FeltConstAttribute<'c>],
+) -> Operation<'c> {
+    let ctx = location.context();
+    def(
+        location,
+        name,
+        r#type.into(),
+        constant,
+        Some(array_initial_value(unsafe { ctx.to_ref() }, values)),
+    )
+}
+
 /// Return `true` iff the given op is `global.def`.
 #[inline]
 pub fn is_global_def<'c: 'a, 'a>(op: &impl OperationLike<'c, 'a>) -> bool {
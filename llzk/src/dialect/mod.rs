@@ -18,7 +18,14 @@ pub mod module {
     use std::ffi::CStr;
 
     use llzk_sys::LLZK_LANG_ATTR_NAME;
-    use melior::ir::{Location, Module, attribute::StringAttribute, operation::OperationMutLike};
+    use melior::ir::{
+        Location, Module,
+        attribute::StringAttribute,
+        operation::{OperationLike as _, OperationMutLike},
+    };
+
+    /// Name of the attribute that holds the LLZK version a module was generated for.
+    pub const LLZK_VERSION_ATTR_NAME: &str = "llzk_version";
 
     /// Creates a new `builtin.module` operation preconfigured to meet LLZK's specifications.
     pub fn llzk_module<'c>(location: Location<'c>) -> Module<'c> {
@@ -34,6 +41,28 @@ pub mod module {
         );
         module
     }
+
+    /// Creates a new LLZK `builtin.module` operation that records the LLZK version it was generated
+    /// for.
+    pub fn llzk_module_versioned<'c>(location: Location<'c>, version: &str) -> Module<'c> {
+        let mut module = llzk_module(location);
+        let ctx = location.context();
+        module.as_operation_mut().set_attribute(
+            LLZK_VERSION_ATTR_NAME,
+            StringAttribute::new(unsafe { ctx.to_ref() }, version).into(),
+        );
+        module
+    }
+
+    /// Returns the LLZK version recorded in the module, if any.
+    pub fn llzk_module_version<'c>(module: &Module<'c>) -> Option<&'c str> {
+        module
+            .as_operation()
+            .attribute(LLZK_VERSION_ATTR_NAME)
+            .ok()
+            .and_then(|attr| StringAttribute::try_from(attr).ok())
+            .map(|attr| attr.value())
+    }
 }
 
 /// Extensions for the 'scf' dialect.
@@ -1,3 +1,4 @@
+use llzk_macro::attr_accessor;
 use llzk_sys::{
     llzkFieldDefOpGetHasPublicAttr, llzkFieldDefOpSetPublicAttr, llzkFieldReadOpBuild,
     llzkOperationIsAFieldDefOp, llzkOperationIsAStructDefOp, llzkStructDefOpGetBody,
@@ -212,6 +213,7 @@ pub trait StructDefOpMutLike<'c: 'a, 'a>:
 //===----------------------------------------------------------------------===//
 
 llzk_op_type!(StructDefOp, llzkOperationIsAStructDefOp, "struct.def");
+attr_accessor!(StructDefOp, "sym_name", StringAttribute<'c>);
 
 impl<'a, 'c: 'a> StructDefOpLike<'c, 'a> for StructDefOp<'c> {}
 
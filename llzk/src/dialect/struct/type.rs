@@ -2,12 +2,15 @@
 
 use crate::utils::FromRaw;
 use crate::utils::IsA;
-use llzk_sys::{llzkStructTypeGetName, llzkStructTypeGetWithArrayAttr, llzkTypeIsAStructType};
+use llzk_sys::{
+    llzkStructTypeGetName, llzkStructTypeGetParams, llzkStructTypeGetWithArrayAttr,
+    llzkTypeIsAStructType,
+};
 use melior::{
     Context,
     ir::{
         Attribute, AttributeLike as _, Type, TypeLike,
-        attribute::{ArrayAttribute, FlatSymbolRefAttribute},
+        attribute::{ArrayAttribute, FlatSymbolRefAttribute, TypeAttribute},
     },
 };
 use mlir_sys::MlirType;
@@ -48,6 +51,48 @@ impl<'c> StructType<'c> {
         Self::new(FlatSymbolRefAttribute::new(context, name), &params)
     }
 
+    /// Creates a new struct type that instantiates the parameters of a polymorphic struct with
+    /// concrete types.
+    pub fn with_params(
+        context: &'c Context,
+        name: FlatSymbolRefAttribute<'c>,
+        params: &[Type<'c>],
+    ) -> Self {
+        let params: Vec<Attribute> = params
+            .iter()
+            .map(|param| TypeAttribute::new(*param).into())
+            .collect();
+        unsafe {
+            Self::from_raw(llzkStructTypeGetWithArrayAttr(
+                name.to_raw(),
+                ArrayAttribute::new(context, &params).to_raw(),
+            ))
+        }
+    }
+
+    /// Returns the attributes that are bound to the struct's parameters.
+    pub fn param_attrs(&self) -> Vec<Attribute<'c>> {
+        let params = unsafe { Attribute::from_raw(llzkStructTypeGetParams(self.to_raw())) };
+        ArrayAttribute::try_from(params)
+            .map(|params| {
+                (0..params.len())
+                    .map(|idx| params.element(idx).expect("index within bounds"))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the types that are bound to the struct's parameters.
+    ///
+    /// Parameters bound to something other than a type (i.e. symbols or constants) are skipped.
+    pub fn params(&self) -> Vec<Type<'c>> {
+        self.param_attrs()
+            .into_iter()
+            .filter_map(|param| TypeAttribute::try_from(param).ok())
+            .map(|param| param.value())
+            .collect()
+    }
+
     /// Get the struct's name.
     pub fn name(&self) -> FlatSymbolRefAttribute<'c> {
         FlatSymbolRefAttribute::try_from(unsafe {
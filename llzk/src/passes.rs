@@ -1,6 +1,6 @@
 //! LLZK passes.
 
-use llzk_macro::passes;
+use llzk_macro::{pass_pipeline, passes};
 
 passes!(
     "LLZKTransformation",
@@ -31,6 +31,12 @@ passes!(
     [mlirCreateLLZKValidationFieldWriteValidatorPass]
 );
 
+pass_pipeline!(Cleanup {
+    RedundantOperationElimination,
+    RedundantReadAndWriteElimination,
+    UnusedDeclarationElimination
+});
+
 /// Registers all the available LLZK passes.
 pub fn register_all_llzk_passes() {
     register_llzk_transformation_passes();
@@ -75,4 +81,11 @@ mod tests {
         super::register_field_write_validator_pass();
         pm.add_pass(super::create_field_write_validator_pass());
     }
+
+    #[test]
+    fn generated_pipeline_functions() {
+        let ctx = Context::new();
+        super::register_llzk_transformation_passes();
+        let _pm = super::create_cleanup_pass_manager(&ctx);
+    }
 }
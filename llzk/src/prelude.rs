@@ -6,7 +6,7 @@ pub use crate::dialect::bool::prelude::*;
 pub use crate::dialect::felt::prelude::*;
 pub use crate::dialect::function::prelude::*;
 pub use crate::dialect::llzk::prelude::*;
-pub use crate::dialect::module::llzk_module;
+pub use crate::dialect::module::{llzk_module, llzk_module_version, llzk_module_versioned};
 pub use crate::dialect::pod::prelude::*;
 pub use crate::dialect::r#struct::prelude::*;
 pub use crate::error::Error as LlzkError;
@@ -25,7 +25,9 @@ pub mod array {
 }
 /// Exports functions from the 'bool' dialect
 pub mod bool {
-    pub use crate::dialect::bool::{and, assert, eq, ge, gt, le, lt, ne, not, or, xor};
+    pub use crate::dialect::bool::{
+        and, assert, eq, ge, gt, iff, implies, le, lt, ne, not, or, select, xor,
+    };
     pub use crate::dialect::bool::{
         is_bool_and, is_bool_assert, is_bool_cmp, is_bool_not, is_bool_or, is_bool_xor,
     };
@@ -39,14 +41,14 @@ pub mod cast {
 
 /// Exports functions from the 'constrain' dialect
 pub mod constrain {
-    pub use crate::dialect::constrain::{eq, r#in};
+    pub use crate::dialect::constrain::{eq, r#in, range};
     pub use crate::dialect::constrain::{is_constrain_eq, is_constrain_in};
 }
 /// Exports functions from the 'felt' dialect
 pub mod felt {
     pub use crate::dialect::felt::{
-        add, bit_and, bit_not, bit_or, bit_xor, constant, div, inv, mul, neg, pow, shl, shr,
-        sintdiv, smod, sub, uintdiv, umod,
+        add, bit_and, bit_not, bit_or, bit_xor, constant, div, inv, mul, neg, pow, pow_const, shl,
+        shr, sintdiv, smod, sub, uintdiv, umod,
     };
     pub use crate::dialect::felt::{
         is_felt_add, is_felt_bit_and, is_felt_bit_not, is_felt_bit_or, is_felt_bit_xor,
@@ -62,7 +64,7 @@ pub mod function {
 }
 /// Exports functions from the 'global' dialect
 pub mod global {
-    pub use crate::dialect::global::{def, read, write};
+    pub use crate::dialect::global::{array_initial_value, def, def_array, read, write};
     pub use crate::dialect::global::{is_global_def, is_global_read, is_global_write};
 }
 /// Exports functions from the 'pod' dialect
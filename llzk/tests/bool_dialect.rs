@@ -280,6 +280,134 @@ fn f_ge() {
     assert_eq!(ir, expected);
 }
 
+#[test]
+fn f_implies() {
+    common::setup();
+    let context = LlzkContext::new();
+    let module = llzk_module(Location::unknown(&context));
+    let loc = Location::unknown(&context);
+    let bool_type: Type = IntegerType::new(&context, 1).into();
+    let f = function::def(
+        loc,
+        "f_implies",
+        FunctionType::new(&context, &[bool_type, bool_type], &[bool_type]),
+        &[],
+        None,
+    )
+    .unwrap();
+    {
+        let block = Block::new(&[(bool_type, loc), (bool_type, loc)]);
+        let op = block.append_operation(
+            bool::implies(
+                loc,
+                block.argument(0).unwrap().into(),
+                block.argument(1).unwrap().into(),
+            )
+            .unwrap(),
+        );
+        block.append_operation(function::r#return(loc, &[op.result(0).unwrap().into()]));
+        f.region(0)
+            .expect("function.def must have at least 1 region")
+            .append_block(block);
+    }
+
+    let f = module.body().append_operation(f.into());
+    assert!(f.verify());
+    log::info!("Op passed verification");
+    let ir = format!("{f}");
+    let expected = r"function.def @f_implies(%arg0: i1, %arg1: i1) -> i1 {
+  %0 = arith.cmpi ule, %arg0, %arg1 : i1
+  function.return %0 : i1
+}";
+    assert_eq!(ir, expected);
+}
+
+#[test]
+fn f_iff() {
+    common::setup();
+    let context = LlzkContext::new();
+    let module = llzk_module(Location::unknown(&context));
+    let loc = Location::unknown(&context);
+    let bool_type: Type = IntegerType::new(&context, 1).into();
+    let f = function::def(
+        loc,
+        "f_iff",
+        FunctionType::new(&context, &[bool_type, bool_type], &[bool_type]),
+        &[],
+        None,
+    )
+    .unwrap();
+    {
+        let block = Block::new(&[(bool_type, loc), (bool_type, loc)]);
+        let op = block.append_operation(
+            bool::iff(
+                loc,
+                block.argument(0).unwrap().into(),
+                block.argument(1).unwrap().into(),
+            )
+            .unwrap(),
+        );
+        block.append_operation(function::r#return(loc, &[op.result(0).unwrap().into()]));
+        f.region(0)
+            .expect("function.def must have at least 1 region")
+            .append_block(block);
+    }
+
+    let f = module.body().append_operation(f.into());
+    assert!(f.verify());
+    log::info!("Op passed verification");
+    let ir = format!("{f}");
+    let expected = r"function.def @f_iff(%arg0: i1, %arg1: i1) -> i1 {
+  %0 = arith.cmpi eq, %arg0, %arg1 : i1
+  function.return %0 : i1
+}";
+    assert_eq!(ir, expected);
+}
+
+#[test]
+fn f_select() {
+    common::setup();
+    let context = LlzkContext::new();
+    let module = llzk_module(Location::unknown(&context));
+    let loc = Location::unknown(&context);
+    let felt_type: Type = FeltType::new(&context).into();
+    let bool_type: Type = IntegerType::new(&context, 1).into();
+    let f = function::def(
+        loc,
+        "f_select",
+        FunctionType::new(&context, &[bool_type, felt_type, felt_type], &[felt_type]),
+        &[],
+        None,
+    )
+    .unwrap();
+    {
+        let block = Block::new(&[(bool_type, loc), (felt_type, loc), (felt_type, loc)]);
+        let op = block.append_operation(
+            bool::select(
+                loc,
+                block.argument(0).unwrap().into(),
+                block.argument(1).unwrap().into(),
+                block.argument(2).unwrap().into(),
+            )
+            .unwrap(),
+        );
+        block.append_operation(function::r#return(loc, &[op.result(0).unwrap().into()]));
+        f.region(0)
+            .expect("function.def must have at least 1 region")
+            .append_block(block);
+    }
+
+    let f = module.body().append_operation(f.into());
+    assert!(f.verify());
+    log::info!("Op passed verification");
+    let ir = format!("{f}");
+    let expected = r"function.def @f_select(%arg0: i1, %arg1: !felt.type, %arg2: !felt.type) -> !felt.type {
+  %0 = arith.select %arg0, %arg1, %arg2 : !felt.type
+  function.return %0 : !felt.type
+}";
+    assert_eq!(ir, expected);
+}
+
 #[test]
 fn f_assert() {
     common::setup();
@@ -0,0 +1,46 @@
+use llzk::prelude::*;
+use melior::ir::{Location, Type, r#type::FunctionType};
+
+mod common;
+
+#[test]
+fn f_range() {
+    common::setup();
+    let context = LlzkContext::new();
+    let module = llzk_module(Location::unknown(&context));
+    let loc = Location::unknown(&context);
+    let felt_type: Type = FeltType::new(&context).into();
+    let f = function::def(
+        loc,
+        "f_range",
+        FunctionType::new(&context, &[felt_type, felt_type, felt_type], &[]),
+        &[],
+        None,
+    )
+    .unwrap();
+    f.set_allow_witness_attr(true);
+    {
+        let block = Block::new(&[(felt_type, loc), (felt_type, loc), (felt_type, loc)]);
+        constrain::range(
+            &block,
+            loc,
+            block.argument(0).unwrap().into(),
+            block.argument(1).unwrap().into(),
+            block.argument(2).unwrap().into(),
+        )
+        .unwrap();
+        block.append_operation(function::r#return(loc, &[]));
+        f.region(0)
+            .expect("function.def must have at least 1 region")
+            .append_block(block);
+    }
+
+    let f = module.body().append_operation(f.into());
+    assert!(f.verify());
+    log::info!("Op passed verification");
+    let ir = format!("{f}");
+    assert!(ir.contains("bool.cmp ge(%arg0, %arg1)"));
+    assert!(ir.contains("bool.cmp le(%arg0, %arg2)"));
+    assert_eq!(ir.matches("bool.assert").count(), 2);
+    assert!(!ir.contains("constrain.range"));
+}
@@ -39,6 +39,39 @@ fn f_constant() {
     assert_eq!(ir, expected);
 }
 
+#[test]
+fn f_pow_const() {
+    common::setup();
+    let context = LlzkContext::new();
+    let module = llzk_module(Location::unknown(&context));
+    let loc = Location::unknown(&context);
+    let felt_type: Type = FeltType::new(&context).into();
+    let f = function::def(
+        loc,
+        "f_pow_const",
+        FunctionType::new(&context, &[felt_type], &[felt_type]),
+        &[],
+        None,
+    )
+    .unwrap();
+    {
+        let block = Block::new(&[(felt_type, loc)]);
+        let pow = felt::pow_const(&block, loc, block.argument(0).unwrap().into(), 3).unwrap();
+        let felt = block.append_operation(pow);
+        block.append_operation(function::r#return(loc, &[felt.result(0).unwrap().into()]));
+        f.region(0)
+            .expect("function.def must have at least 1 region")
+            .append_block(block);
+    }
+
+    let f = module.body().append_operation(f.into());
+    assert!(f.verify());
+    log::info!("Op passed verification");
+    let ir = format!("{f}");
+    assert!(ir.contains("%felt_const_3 = felt.const  3"));
+    assert!(ir.contains("felt.pow %arg0, %felt_const_3"));
+}
+
 #[test]
 fn f_add() {
     common::setup();
@@ -37,6 +37,32 @@ fn empty_function() {
     assert_eq!(ir, expected);
 }
 
+#[test]
+fn function_attr_accessors() {
+    common::setup();
+    let context = LlzkContext::new();
+    let loc = Location::unknown(&context);
+    let felt_type: Type = FeltType::new(&context).into();
+    let func_type = FunctionType::new(&context, &[], &[felt_type]);
+    let mut f = function::def(loc, "before", func_type, &[], None).unwrap();
+
+    assert_eq!(f.get_sym_name().map(|name| name.value()), Some("before"));
+    assert_eq!(
+        f.get_function_type().map(|typ| typ.value()),
+        Some(func_type.into())
+    );
+
+    f.set_sym_name(StringAttribute::new(&context, "after"));
+    assert_eq!(f.get_sym_name().map(|name| name.value()), Some("after"));
+
+    // The attribute exists but is not a type attribute.
+    f.set_attribute(
+        "function_type",
+        StringAttribute::new(&context, "bad").into(),
+    );
+    assert!(f.get_function_type().is_none());
+}
+
 #[test]
 fn function_call() {
     common::setup();
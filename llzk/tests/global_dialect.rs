@@ -0,0 +1,22 @@
+use llzk::prelude::*;
+use melior::ir::{Location, Type};
+
+mod common;
+
+#[test]
+fn global_array_def() {
+    common::setup();
+    let context = LlzkContext::new();
+    let loc = Location::unknown(&context);
+    let module = llzk_module(loc);
+    let felt_type: Type = FeltType::new(&context).into();
+    let values = [1, 2, 3].map(|value| FeltConstAttribute::new(&context, value));
+    let op = global::def_array(loc, "table", ArrayType::new_1d(felt_type, 3), true, &values);
+    assert!(global::is_global_def(&op));
+    let op = module.body().append_operation(op);
+    assert!(op.verify());
+    log::info!("Op passed verification");
+    let ir = format!("{op}");
+    assert!(ir.contains("@table"));
+    assert!(ir.contains("!array.type<3 x !felt.type>"));
+}
@@ -24,3 +24,15 @@ fn create_loop_bounds_attr() {
     let expected = "#llzk.loopbounds<0 to 10 step 1>";
     assert_eq!(ir, expected);
 }
+
+#[test]
+fn versioned_module() {
+    common::setup();
+    let context = LlzkContext::new();
+    let loc = Location::unknown(&context);
+
+    let module = llzk_module_versioned(loc, "1.0.0");
+    assert!(module.as_operation().verify());
+    assert_eq!(llzk_module_version(&module), Some("1.0.0"));
+    assert_eq!(llzk_module_version(&llzk_module(loc)), None);
+}
@@ -28,6 +28,38 @@ fn empty_struct() {
     assert_test!(s, module, @file "expected/empty_struct.mlir" );
 }
 
+#[test]
+fn struct_sym_name_accessor() {
+    common::setup();
+    let context = LlzkContext::new();
+    let loc = Location::unknown(&context);
+    let typ = StructType::from_str(&context, "empty");
+    let mut s = r#struct::def(loc, "empty", &[], default_funcs(loc, typ)).unwrap();
+
+    assert_eq!(s.get_sym_name().map(|name| name.value()), Some("empty"));
+    s.set_sym_name(StringAttribute::new(&context, "renamed"));
+    assert_eq!(s.get_sym_name().map(|name| name.value()), Some("renamed"));
+    assert_eq!(s.name(), "renamed");
+}
+
+#[test]
+fn struct_type_with_params() {
+    common::setup();
+    let context = LlzkContext::new();
+    let felt_type: Type = FeltType::new(&context).into();
+    let index_type = Type::index(&context);
+    let name = FlatSymbolRefAttribute::new(&context, "pair");
+    let typ = StructType::with_params(&context, name, &[felt_type, index_type]);
+    assert_eq!(typ.name().value(), "pair");
+    assert_eq!(typ.param_attrs().len(), 2);
+    assert_eq!(typ.params(), [felt_type, index_type]);
+
+    // Symbolic parameters are not types.
+    let typ = StructType::from_str_params(&context, "empty", &["T"]);
+    assert_eq!(typ.param_attrs().len(), 1);
+    assert!(typ.params().is_empty());
+}
+
 #[test]
 fn empty_struct_with_one_param() {
     common::setup();
@@ -0,0 +1,4 @@
+//! Analyses over Picus programs.
+
+pub mod calls;
+pub mod liveness;
@@ -0,0 +1,105 @@
+//! Validation of the calls between modules.
+
+use std::{collections::HashMap, fmt};
+
+use crate::{
+    Program,
+    stmt::traits::{CallLike as _, MaybeCallLike as _},
+    vars::VarKind,
+};
+
+/// A call that does not receive as many outputs as its callee declares.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallOutputMismatch {
+    /// Name of the module that performs the call.
+    pub caller: String,
+    /// Name of the called module.
+    pub callee: String,
+    /// Number of outputs declared by the callee.
+    pub expected: usize,
+    /// Number of output variables of the call.
+    pub found: usize,
+}
+
+impl fmt::Display for CallOutputMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Call to module {} in module {} has {} output variables but the callee declares {}",
+            self.callee, self.caller, self.found, self.expected
+        )
+    }
+}
+
+impl std::error::Error for CallOutputMismatch {}
+
+/// Checks that every call in a program has as many output variables as outputs are declared by
+/// its callee.
+///
+/// Calls to modules that are not part of the program are not checked.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PicusCallValidator;
+
+impl PicusCallValidator {
+    /// Returns the calls in the program whose number of outputs does not match their callee.
+    pub fn validate<K: VarKind>(&self, program: &Program<K>) -> Vec<CallOutputMismatch> {
+        let output_counts = program
+            .modules()
+            .iter()
+            .map(|m| (m.name(), m.vars().outputs().count()))
+            .collect::<HashMap<_, _>>();
+        program
+            .modules()
+            .iter()
+            .flat_map(|module| {
+                module.stmts().iter().filter_map(|stmt| {
+                    let call = stmt.as_call()?;
+                    let expected = *output_counts.get(call.callee())?;
+                    let found = call.outputs().len();
+                    (expected != found).then(|| CallOutputMismatch {
+                        caller: module.name().to_owned(),
+                        callee: call.callee().to_owned(),
+                        expected,
+                        found,
+                    })
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::ParsedVar;
+
+    #[test]
+    fn mismatched_outputs() {
+        let program: Program<ParsedVar> = "(prime-number 7)
+(begin-module f)
+(input x)
+(output y)
+(output z)
+(end-module)
+(begin-module main)
+(input a)
+(output b)
+(call [b] f [a])
+(call [b a] f [a])
+(call [b] g [a])
+(end-module)
+"
+        .parse()
+        .unwrap();
+        let mismatches = PicusCallValidator.validate(&program);
+        assert_eq!(
+            mismatches,
+            [CallOutputMismatch {
+                caller: "main".to_owned(),
+                callee: "f".to_owned(),
+                expected: 2,
+                found: 1,
+            }]
+        );
+    }
+}
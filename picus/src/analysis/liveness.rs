@@ -0,0 +1,162 @@
+//! Inter-procedural liveness of variables.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    expr::traits::MaybeVarLike as _,
+    stmt::traits::{
+        CallLike as _, ConstraintLike as _, ExprArgs as _, FreeVars as _, MaybeCallLike as _,
+    },
+    vars::{VarKind, VarStr, Vars},
+    Module, Program,
+};
+
+/// Computes the live variables of each module in the program, indexed by module name.
+///
+/// A variable is live in a module if it is an output of the module, appears in a constraint, or
+/// is used for computing an input of a call whose corresponding input is live in the callee.
+/// Liveness is propagated backwards across the call graph until a fixpoint is reached. Calls to
+/// modules that are not part of the program are conservatively assumed to use all their inputs.
+pub fn cross_module_liveness<K: VarKind>(program: &Program<K>) -> HashMap<String, HashSet<VarStr>> {
+    let modules = program
+        .modules()
+        .iter()
+        .map(|m| (m.name(), m))
+        .collect::<HashMap<_, _>>();
+    let mut live: HashMap<String, HashSet<VarStr>> = program
+        .modules()
+        .iter()
+        .map(|m| (m.name().to_owned(), local_roots(m)))
+        .collect();
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for module in program.modules() {
+            let mut new_live = HashSet::new();
+            for stmt in module.stmts() {
+                let Some(call) = stmt.as_call() else {
+                    continue;
+                };
+                let inputs = call.inputs();
+                // Guarded calls put the guard before the outputs and inputs. The guard decides if
+                // the call applies so its variables are always live.
+                let args = stmt.args();
+                let guard = &args[..args.len() - call.outputs().len() - inputs.len()];
+                for expr in guard {
+                    new_live.extend(expr.free_vars().into_iter().cloned());
+                }
+                let callee_inputs = modules.get(call.callee()).map(|callee| {
+                    let callee_live = &live[callee.name()];
+                    sorted_inputs(callee.vars())
+                        .into_iter()
+                        .map(|input| callee_live.contains(input))
+                        .collect::<Vec<_>>()
+                });
+                for (idx, input) in inputs.iter().enumerate() {
+                    let is_live = callee_inputs
+                        .as_ref()
+                        .map(|callee_inputs| callee_inputs.get(idx).copied().unwrap_or(true))
+                        .unwrap_or(true);
+                    if is_live {
+                        new_live.extend(input.free_vars().into_iter().cloned());
+                    }
+                }
+            }
+
+            let module_live = live.get_mut(module.name()).unwrap();
+            let before = module_live.len();
+            module_live.extend(new_live);
+            changed |= module_live.len() != before;
+        }
+    }
+    live
+}
+
+/// Variables that are live in the module regardless of the calls it performs.
+fn local_roots<K: VarKind>(module: &Module<K>) -> HashSet<VarStr> {
+    module
+        .vars()
+        .iter()
+        .filter(|(k, _)| k.is_output())
+        .map(|(_, v)| v.clone())
+        .chain(
+            module
+                .stmts()
+                .iter()
+                .filter(|s| s.is_constraint())
+                .flat_map(|s| s.free_vars().into_iter().cloned().collect::<Vec<_>>()),
+        )
+        .collect()
+}
+
+/// Returns the inputs of the module in declaration order.
+fn sorted_inputs<K: VarKind>(vars: &Vars<K>) -> Vec<&VarStr> {
+    let mut inputs = vars
+        .iter()
+        .filter_map(|(k, v)| k.get_input_no().map(|no| (no, v)))
+        .collect::<Vec<_>>();
+    inputs.sort_by_key(|(no, _)| *no);
+    inputs.into_iter().map(|(_, v)| v).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::ParsedVar;
+
+    fn names(vars: &HashSet<VarStr>) -> Vec<String> {
+        let mut names = vars.iter().map(ToString::to_string).collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn liveness_follows_the_inputs_used_by_callees() {
+        let program: Program<ParsedVar> = "(prime-number 7)
+(begin-module f)
+(input x)
+(input w)
+(output y)
+(assert (= y (* x x)))
+(end-module)
+(begin-module main)
+(input a)
+(input c)
+(output b)
+(call [b] f [a c])
+(end-module)
+"
+        .parse()
+        .unwrap();
+        let live = cross_module_liveness(&program);
+        assert_eq!(names(&live["f"]), ["x", "y"]);
+        // `c` is only passed to an input of `f` that is not live.
+        assert_eq!(names(&live["main"]), ["a", "b"]);
+    }
+
+    #[test]
+    fn guarded_calls_skip_the_guard() {
+        let program: Program<ParsedVar> = "(prime-number 7)
+(begin-module f)
+(input x)
+(input w)
+(output y)
+(assert (= y (* x x)))
+(end-module)
+(begin-module main)
+(input a)
+(input c)
+(input g)
+(output b)
+if g {(call [b] f [a c])}
+(end-module)
+"
+        .parse()
+        .unwrap();
+        let live = cross_module_liveness(&program);
+        assert_eq!(names(&live["f"]), ["x", "y"]);
+        // The guard is live and `c` is still matched with the unused input of `f`.
+        assert_eq!(names(&live["main"]), ["a", "b", "g"]);
+    }
+}
@@ -545,6 +545,19 @@ impl<K: VarKind> fmt::Display for Display<'_, K> {
     }
 }
 
+/// Renders a text representation into a string.
+pub(crate) fn render(repr: TextRepresentation<'_>) -> String {
+    struct Rendered<'a>(TextRepresentation<'a>);
+
+    impl fmt::Display for Rendered<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            Displayer::new(f).fmt_repr(self.0.clone())
+        }
+    }
+
+    Rendered(repr).to_string()
+}
+
 struct Displayer<'a, 'b> {
     f: &'a mut fmt::Formatter<'b>,
 }
@@ -151,15 +151,15 @@ binary_expr_common!(ConstraintKind);
 binary_expr_common!(Boolean);
 
 impl<K: Clone + PartialEq> BinaryExpr<K> {
-    fn lhs(&self) -> Expr {
+    pub(crate) fn lhs(&self) -> Expr {
         self.1.clone()
     }
 
-    fn rhs(&self) -> Expr {
+    pub(crate) fn rhs(&self) -> Expr {
         self.2.clone()
     }
 
-    fn op(&self) -> &K {
+    pub(crate) fn op(&self) -> &K {
         &self.0
     }
 }
@@ -18,6 +18,7 @@ use crate::{
 };
 
 mod impls;
+pub mod smt;
 pub mod traits;
 mod util;
 
@@ -196,6 +197,23 @@ pub fn ne(lhs: &Expr, rhs: &Expr) -> Expr {
     constraint(ConstraintKind::Ne, lhs, rhs)
 }
 
+/// If the expression is a multiplication returns its operands.
+pub(crate) fn as_mul(expr: &dyn ExprLike) -> Option<(Expr, Expr)> {
+    expr.as_any()
+        .downcast_ref::<BinaryExpr<BinaryOp>>()
+        .filter(|e| *e.op() == BinaryOp::Mul)
+        .map(|e| (e.lhs(), e.rhs()))
+}
+
+/// Returns true if both expressions are equal after renaming the variables in `a` according to
+/// `rename`. Variables not present in the map must have the same name in both expressions.
+pub fn structurally_eq(a: &Expr, b: &Expr, rename: &HashMap<VarStr, VarStr>) -> bool {
+    match a.renamed(rename) {
+        Some(renamed) => renamed == *b.as_ref(),
+        None => *a == *b.as_ref(),
+    }
+}
+
 pub fn neg(expr: &Expr) -> Expr {
     Wrap::new(NegExpr::new(expr.clone()))
 }
@@ -235,3 +253,29 @@ pub fn r#true() -> Expr {
 pub fn r#false() -> Expr {
     ne(&r#const(0), &r#const(0))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(name: &str) -> VarStr {
+        VarStr::try_from(name.to_owned()).unwrap()
+    }
+
+    #[test]
+    fn structural_equality_modulo_renaming() {
+        let x = known_var(&name("x"));
+        let y = known_var(&name("y"));
+        let z = known_var(&name("z"));
+        let rename = HashMap::from([(name("x"), name("z"))]);
+
+        // x * y == z * y after renaming x to z.
+        assert!(structurally_eq(&mul(&x, &y), &mul(&z, &y), &rename));
+        // Variables that are not renamed must keep their names.
+        assert!(!structurally_eq(&mul(&x, &y), &mul(&z, &z), &rename));
+        let y_plus_one = add(&y, &r#const(1));
+        assert!(structurally_eq(&y_plus_one, &y_plus_one, &rename));
+        // The structure of the expressions must match.
+        assert!(!structurally_eq(&mul(&x, &y), &add(&z, &y), &rename));
+    }
+}
@@ -0,0 +1,124 @@
+//! Conversion of Picus expressions into SMT-LIB2 terms.
+//!
+//! Field elements are encoded as integers. Arithmetic is emitted as plain integer arithmetic
+//! and the operands of comparisons are reduced modulo the prime, which is referenced through the
+//! [`SMT_PRIME`] constant. Divisions are encoded as multiplications by the inverse of the
+//! divisor, represented by the uninterpreted function [`SMT_INV`].
+
+use anyhow::{bail, Result};
+
+use crate::{
+    display::{render, TextRepresentation},
+    felt::Felt,
+};
+
+use super::{
+    impls::{BinaryExpr, BinaryOp, Boolean, ConstraintKind, DetExpr, NegExpr, NotExpr},
+    traits::ExprLike,
+    Expr,
+};
+
+/// Name of the SMT-LIB2 constant that holds the prime of the field.
+pub const SMT_PRIME: &str = "prime!";
+
+/// Name of the SMT-LIB2 function that computes the multiplicative inverse of a field element.
+pub const SMT_INV: &str = "inv!";
+
+/// Types that can be converted into SMT-LIB2 syntax.
+pub trait ToSmtLib2 {
+    /// Returns the SMT-LIB2 term that represents the value, or an error if the value can't be
+    /// encoded.
+    fn to_smt_lib2(&self) -> Result<String>;
+}
+
+impl ToSmtLib2 for Expr {
+    fn to_smt_lib2(&self) -> Result<String> {
+        smt_term(self.as_ref()).map(render)
+    }
+}
+
+/// Returns the name of the variable as a valid SMT-LIB2 symbol.
+pub(crate) fn smt_symbol(name: &str) -> String {
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("|{name}|")
+    } else {
+        name.to_owned()
+    }
+}
+
+/// Returns the preamble that defines the prime and, if required, the inverse function.
+pub(crate) fn smt_preamble(prime: &Felt, uses_inverse: bool) -> Vec<String> {
+    let mut preamble = vec![format!("(define-fun {SMT_PRIME} () Int {prime})")];
+    if uses_inverse {
+        preamble.push(format!("(declare-fun {SMT_INV} (Int) Int)"));
+        preamble.push(format!(
+            "(assert (forall ((x Int)) (=> (and (< 0 x) (< x {SMT_PRIME})) (= (mod (* x ({SMT_INV} x)) {SMT_PRIME}) 1))))"
+        ));
+    }
+    preamble
+}
+
+/// Returns true if the expression contains a division.
+pub(crate) fn uses_division(expr: &dyn ExprLike) -> bool {
+    expr.as_any()
+        .downcast_ref::<BinaryExpr<BinaryOp>>()
+        .is_some_and(|e| *e.op() == BinaryOp::Div)
+        || expr.args().iter().any(|arg| uses_division(arg.as_ref()))
+}
+
+fn reduced(term: TextRepresentation<'static>) -> TextRepresentation<'static> {
+    owned_list!("mod", term, SMT_PRIME)
+}
+
+fn smt_term(expr: &dyn ExprLike) -> Result<TextRepresentation<'static>> {
+    if let Some(value) = expr.as_const() {
+        return Ok(TextRepresentation::owned_atom(value.to_string()));
+    }
+    if let Some(name) = expr.var_name() {
+        return Ok(TextRepresentation::owned_atom(smt_symbol(name.as_ref())));
+    }
+
+    let args = expr.args();
+    let arg = |idx: usize| smt_term(args[idx].as_ref());
+    let any = expr.as_any();
+
+    if any.is::<NegExpr>() {
+        return Ok(owned_list!("-", arg(0)?));
+    }
+    if any.is::<NotExpr>() {
+        return Ok(owned_list!("not", arg(0)?));
+    }
+    if any.is::<DetExpr>() {
+        // Determinism is a property checked by Picus and has no meaning as an assertion.
+        bail!("Determinism can't be encoded in SMT-LIB2: {expr:?}");
+    }
+    if let Some(e) = any.downcast_ref::<BinaryExpr<BinaryOp>>() {
+        return Ok(match e.op() {
+            BinaryOp::Add => owned_list!("+", arg(0)?, arg(1)?),
+            BinaryOp::Sub => owned_list!("-", arg(0)?, arg(1)?),
+            BinaryOp::Mul => owned_list!("*", arg(0)?, arg(1)?),
+            BinaryOp::Div => owned_list!("*", arg(0)?, owned_list!(SMT_INV, reduced(arg(1)?))),
+        });
+    }
+    if let Some(e) = any.downcast_ref::<BinaryExpr<ConstraintKind>>() {
+        let op = match e.op() {
+            ConstraintKind::Lt => "<",
+            ConstraintKind::Le => "<=",
+            ConstraintKind::Gt => ">",
+            ConstraintKind::Ge => ">=",
+            ConstraintKind::Eq => "=",
+            ConstraintKind::Ne => "distinct",
+        };
+        return Ok(owned_list!(op, reduced(arg(0)?), reduced(arg(1)?)));
+    }
+    if let Some(e) = any.downcast_ref::<BinaryExpr<Boolean>>() {
+        let op = match e.op() {
+            Boolean::And => "and",
+            Boolean::Or => "or",
+            Boolean::Implies => "=>",
+            Boolean::Iff => "=",
+        };
+        return Ok(owned_list!(op, arg(0)?, arg(1)?));
+    }
+    bail!("Unsupported expression kind: {expr:?}")
+}
@@ -139,3 +139,98 @@ pub trait ExprLike:
     + GetExprHash
 {
 }
+
+/// Visitor for traversing the expression tree.
+///
+/// Implementations override [`ExprVisitor::visit`] and call [`ExprVisitor::visit_children`] when
+/// they want to continue the traversal into the arguments of the expression.
+pub trait ExprVisitor {
+    /// Visits the expression. Does nothing by default.
+    #[allow(unused_variables)]
+    fn visit(&mut self, expr: &Expr) {}
+
+    /// Visits each argument of the expression.
+    fn visit_children(&mut self, expr: &Expr) {
+        for arg in expr.args() {
+            self.visit(&arg);
+        }
+    }
+}
+
+/// Transformer for rewriting the expression tree.
+///
+/// The default traversal is bottom-up. The arguments of the expression are transformed first
+/// and then [`ExprTransformer::transform_node`] is called on the expression with the transformed
+/// arguments.
+pub trait ExprTransformer {
+    /// Transforms a single node whose arguments have already been transformed. Returns the node
+    /// unchanged by default.
+    fn transform_node(&mut self, expr: &Expr) -> Expr {
+        expr.clone()
+    }
+
+    /// Transforms the expression and its arguments.
+    fn transform(&mut self, expr: &Expr) -> Expr {
+        let args = expr
+            .args()
+            .iter()
+            .map(|arg| Some(self.transform(arg)))
+            .collect::<Vec<_>>();
+        let expr = expr
+            .replace_args(&args)
+            .expect("the number of arguments did not change")
+            .unwrap_or_else(|| expr.clone());
+        self.transform_node(&expr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr::{self, known_var};
+
+    fn var(name: &str) -> Expr {
+        known_var(&VarStr::try_from(name.to_owned()).unwrap())
+    }
+
+    /// Counts the nodes of the visited expressions.
+    #[derive(Default)]
+    struct NodeCounter(usize);
+
+    impl ExprVisitor for NodeCounter {
+        fn visit(&mut self, expr: &Expr) {
+            self.0 += 1;
+            self.visit_children(expr);
+        }
+    }
+
+    /// Replaces the variable `x` with the constant 3.
+    struct ReplaceX;
+
+    impl ExprTransformer for ReplaceX {
+        fn transform_node(&mut self, expr: &Expr) -> Expr {
+            match expr.var_name() {
+                Some(name) if name.as_ref() == "x" => expr::r#const(3),
+                _ => expr.clone(),
+            }
+        }
+    }
+
+    #[test]
+    fn visitor_reaches_every_node() {
+        // (x * 2) + y
+        let e = expr::add(&expr::mul(&var("x"), &expr::r#const(2)), &var("y"));
+        let mut counter = NodeCounter::default();
+        counter.visit(&e);
+        assert_eq!(counter.0, 5);
+    }
+
+    #[test]
+    fn transformer_rewrites_the_arguments() {
+        // (x * x) + y
+        let e = expr::add(&expr::mul(&var("x"), &var("x")), &var("y"));
+        let three = expr::r#const(3);
+        let expected = expr::add(&expr::mul(&three, &three), &var("y"));
+        assert!(ReplaceX.transform(&e) == *expected.as_ref());
+    }
+}
@@ -2,6 +2,7 @@
 #![deny(missing_debug_implementations)]
 #![warn(missing_docs)]
 
+pub mod analysis;
 #[macro_use]
 pub mod display;
 pub mod expr;
@@ -9,9 +10,10 @@ pub mod felt;
 pub mod ident;
 mod module;
 pub mod opt;
+pub mod parse;
 mod program;
 pub mod stmt;
 pub mod vars;
 
 pub use module::{Module, ModuleLike, ModuleRef, ModuleWithVars};
-pub use program::Program;
+pub use program::{OptimizationDelta, Program, ProgramStats, optimization_delta};
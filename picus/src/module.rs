@@ -1,6 +1,6 @@
 use std::{
     cell::RefCell,
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     fmt,
     ops::{Deref, DerefMut},
     rc::Rc,
@@ -8,12 +8,20 @@ use std::{
 
 use crate::{
     display::{ListItem, TextRepresentable, TextRepresentation},
-    expr::{self, traits::ConstraintEmitter, Expr},
+    expr::{
+        self,
+        smt::{smt_preamble, smt_symbol, uses_division, ToSmtLib2 as _, SMT_PRIME},
+        traits::{ConstraintEmitter, ExprSize as _, MaybeVarLike as _},
+        Expr,
+    },
     felt::Felt,
     ident::Ident,
     stmt::{
         self,
-        traits::{ConstraintLike as _, FreeVars as _, StmtConstantFolding as _},
+        traits::{
+            CallLike as _, ConstraintLike as _, ExprArgs as _, FreeVars as _, MaybeCallLike as _,
+            StmtConstantFolding as _,
+        },
         Stmt,
     },
     vars::{VarAllocator, VarKind, VarStr, Vars},
@@ -140,6 +148,7 @@ impl<K: VarKind> ModuleLike<K> for Module<K> {
             .stmts()
             .iter()
             .map(|s| s.fold(prime).unwrap_or(s.clone()))
+            .filter(|s| !stmt::is_vacuous(s))
             .collect();
     }
 
@@ -201,6 +210,166 @@ impl<K: VarKind> Module<K> {
             .collect()
     }
 
+    /// Returns the variables that appear in the statements of the module.
+    pub fn free_vars(&self) -> HashSet<&VarStr> {
+        self.stmts.iter().flat_map(|s| s.free_vars()).collect()
+    }
+
+    /// Returns the variables that appear in statements that are not constraints (e.g. calls) but
+    /// never appear in a constraint.
+    pub fn unconstrained_vars(&self) -> HashSet<&VarStr> {
+        let (constraints, others): (Vec<_>, Vec<_>) =
+            self.stmts.iter().partition(|s| s.is_constraint());
+        let constrained = constraints
+            .into_iter()
+            .flat_map(|s| s.free_vars())
+            .collect::<HashSet<_>>();
+        others
+            .into_iter()
+            .flat_map(|s| s.free_vars())
+            .filter(|v| !constrained.contains(v))
+            .collect()
+    }
+
+    /// Returns true if both modules have the same statements after renaming the inputs and
+    /// outputs by position and the temporaries by order of first appearance. The names of the
+    /// modules are not compared.
+    pub fn is_duplicate_of(&self, other: &Self) -> bool {
+        if self.stmts.len() != other.stmts.len() {
+            return false;
+        }
+        let Some(rename) = self.rename_to(other) else {
+            return false;
+        };
+        std::iter::zip(&self.stmts, &other.stmts)
+            .all(|(lhs, rhs)| stmt::structurally_eq(lhs, rhs, &rename))
+    }
+
+    /// Returns the variables of the module that are not inputs nor outputs in the order they
+    /// first appear in the statements.
+    fn temporaries_by_appearance(&self) -> Vec<VarStr> {
+        fn walk(expr: &Expr, out: &mut Vec<VarStr>) {
+            match expr.var_name() {
+                Some(name) => out.push(name.clone()),
+                None => expr.args().iter().for_each(|arg| walk(arg, out)),
+            }
+        }
+
+        let mut vars = vec![];
+        for arg in self.stmts.iter().flat_map(|s| s.args()) {
+            walk(&arg, &mut vars);
+        }
+        let mut seen = HashSet::new();
+        vars.retain(|v| {
+            !self
+                .vars
+                .lookup_key(v)
+                .is_some_and(|k| k.is_input() || k.is_output())
+                && seen.insert(v.clone())
+        });
+        vars
+    }
+
+    /// Returns the renaming that maps the variables of this module to the variables in the same
+    /// position of the other module, or `None` if their number does not match.
+    fn rename_to(&self, other: &Self) -> Option<HashMap<VarStr, VarStr>> {
+        fn by_position<K: VarKind>(
+            vars: &Vars<K>,
+            position: impl Fn(&K) -> Option<usize>,
+        ) -> Vec<VarStr> {
+            let mut vars = vars
+                .iter()
+                .filter_map(|(k, v)| position(k).map(|no| (no, v.clone())))
+                .collect::<Vec<_>>();
+            vars.sort_by_key(|(no, _)| *no);
+            vars.into_iter().map(|(_, v)| v).collect()
+        }
+
+        let pairs = [
+            (
+                by_position(&self.vars, K::get_input_no),
+                by_position(&other.vars, K::get_input_no),
+            ),
+            (
+                by_position(&self.vars, K::get_output_no),
+                by_position(&other.vars, K::get_output_no),
+            ),
+            (
+                self.temporaries_by_appearance(),
+                other.temporaries_by_appearance(),
+            ),
+        ];
+        if pairs.iter().any(|(lhs, rhs)| lhs.len() != rhs.len()) {
+            return None;
+        }
+        Some(
+            pairs
+                .into_iter()
+                .flat_map(|(lhs, rhs)| std::iter::zip(lhs, rhs))
+                .collect(),
+        )
+    }
+
+    /// Returns an SMT-LIB2 script that declares the variables of the module, asserts its
+    /// constraints and checks their satisfiability. Field elements are encoded as integers in the
+    /// range `[0, prime)`. Calls to other modules are not encoded and are emitted as comments.
+    ///
+    /// Fails if a constraint can't be encoded, e.g. because it refers to the determinism of a
+    /// variable.
+    pub fn to_smt_lib2_assertions(&self, prime: &Felt) -> anyhow::Result<String> {
+        let constraints = self
+            .stmts
+            .iter()
+            .filter(|s| s.is_constraint())
+            .flat_map(stmt::constraint_terms)
+            .collect::<Vec<_>>();
+        let uses_inverse = constraints.iter().any(|e| uses_division(e.as_ref()));
+
+        let mut names = self
+            .vars
+            .inputs()
+            .chain(self.vars.outputs())
+            .map(ToOwned::to_owned)
+            .chain(self.free_vars().into_iter().map(|v| v.to_string()))
+            .collect::<Vec<_>>();
+        names.sort();
+        names.dedup();
+
+        let mut lines = vec![format!("; Module {}", self.name())];
+        lines.extend(smt_preamble(prime, uses_inverse));
+        for name in names {
+            let name = smt_symbol(&name);
+            lines.push(format!("(declare-const {name} Int)"));
+            lines.push(format!(
+                "(assert (and (<= 0 {name}) (< {name} {SMT_PRIME})))"
+            ));
+        }
+        for stmt in &self.stmts {
+            if let Some(call) = stmt.as_call() {
+                lines.push(format!("; Call to {} is not encoded", call.callee()));
+            }
+        }
+        for constraint in &constraints {
+            lines.push(format!("(assert {})", constraint.to_smt_lib2()?));
+        }
+        lines.push("(check-sat)".to_owned());
+        Ok(lines.join("\n"))
+    }
+
+    /// Replaces the callee of the call statements that call a module in the map with its
+    /// associated new name.
+    pub(crate) fn rename_callees(&mut self, renames: &HashMap<String, String>) {
+        self.stmts = std::mem::take(&mut self.stmts)
+            .into_iter()
+            .map(|s| match s.as_call() {
+                Some(call) if renames.contains_key(call.callee()) => {
+                    call.with_new_callee(renames[call.callee()].clone())
+                }
+                _ => s,
+            })
+            .collect();
+    }
+
     fn summarize(&self) -> ModuleSummary {
         let input_count = self.vars.inputs().count();
         let output_count = self.vars.outputs().count();
@@ -291,3 +460,140 @@ impl<K: VarKind> TextRepresentable for Module<K> {
         self.name.width_hint()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::ModuleLike as _;
+    use crate::{Program, parse::ParsedVar, vars::VarStr};
+
+    #[test]
+    fn vars_only_used_in_calls_are_unconstrained() {
+        let program: Program<ParsedVar> = "(prime-number 7)
+(begin-module f)
+(input x)
+(output y)
+(end-module)
+(begin-module main)
+(input a)
+(output b)
+(call [b] f [a])
+(assert (= a 1))
+(end-module)
+"
+        .parse()
+        .unwrap();
+        let main = &program.modules()[1];
+        let names = |vars: HashSet<&VarStr>| {
+            let mut names = vars
+                .into_iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            names.sort();
+            names
+        };
+        assert_eq!(names(main.free_vars()), ["a", "b"]);
+        assert_eq!(names(main.unconstrained_vars()), ["b"]);
+    }
+
+    #[test]
+    fn guarded_constraints_constrain_their_vars() {
+        let program: Program<ParsedVar> = "(prime-number 7)
+(begin-module f)
+(input x)
+(output y)
+(end-module)
+(begin-module main)
+(input a)
+(output b)
+(call [b] f [a])
+if b { (assert (= a 1)) }
+(end-module)
+"
+        .parse()
+        .unwrap();
+        let main = &program.modules()[1];
+        assert_eq!(main.constraints_len(), 1);
+        assert!(main.unconstrained_vars().is_empty());
+    }
+
+    #[test]
+    fn duplicates_modulo_renaming() {
+        let mut program: Program<ParsedVar> = "(prime-number 7)
+(begin-module f)
+(input x)
+(output y)
+(assert (= t (* x x)))
+(assert (= y (+ t 1)))
+(end-module)
+(begin-module g)
+(input a)
+(output b)
+(assert (= u (* a a)))
+(assert (= b (+ u 1)))
+(end-module)
+(begin-module h)
+(input a)
+(output b)
+(assert (= u (* a a)))
+(assert (= b (+ a 1)))
+(end-module)
+(begin-module main)
+(input a)
+(output b)
+(assert (= u (* a a)))
+(assert (= b (+ u 1)))
+(end-module)
+"
+        .parse()
+        .unwrap();
+        let modules = program.modules();
+        assert!(modules[0].is_duplicate_of(&modules[1]));
+        assert!(!modules[0].is_duplicate_of(&modules[2]));
+
+        assert_eq!(program.deduplicate_modules("main"), 1);
+        let names = program
+            .modules()
+            .iter()
+            .map(|m| m.name().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["f", "h", "main"]);
+    }
+
+    #[test]
+    fn smt_export() {
+        let program: Program<ParsedVar> = "(prime-number 7)
+(begin-module main)
+(input x)
+(output y)
+(assert (= y (* x x)))
+(end-module)
+"
+        .parse()
+        .unwrap();
+        let smt = program.modules()[0]
+            .to_smt_lib2_assertions(program.prime())
+            .unwrap();
+        assert!(smt.contains("(assert (= (mod y prime!) (mod (* x x) prime!)))"));
+        assert!(smt.ends_with("(check-sat)"));
+    }
+
+    #[test]
+    fn smt_export_rejects_determinism() {
+        let program: Program<ParsedVar> = "(prime-number 7)
+(begin-module main)
+(input x)
+(output y)
+(assert (=> (det y) (= y x)))
+(end-module)
+"
+        .parse()
+        .unwrap();
+        assert!(
+            program.modules()[0]
+                .to_smt_lib2_assertions(program.prime())
+                .is_err()
+        );
+    }
+}
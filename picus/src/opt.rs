@@ -1,10 +1,15 @@
-use std::marker::PhantomData;
+use std::{
+    collections::HashMap,
+    marker::PhantomData,
+    time::{Duration, Instant},
+};
 
 use anyhow::Result;
 
 use crate::{
+    display::{render, TextRepresentable as _},
     expr::{traits::ExprLike, Expr},
-    stmt::traits::StmtLike,
+    stmt::traits::{ExprArgs as _, StmtLike},
     vars::VarKind,
     Module, Program,
 };
@@ -17,6 +22,60 @@ pub trait Optimizer<I: ?Sized, O>: std::fmt::Debug {
 
 pub trait MutOptimizer<T: ?Sized>: std::fmt::Debug {
     fn optimize(&mut self, t: &mut T) -> Result<()>;
+
+    /// Returns the name of the optimizer. Defaults to the name of the implementing type.
+    fn name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+}
+
+/// Wraps an optimizer and logs the program before and after each run.
+///
+/// The program is only rendered if the log level is `Debug` or lower.
+#[derive(Debug)]
+pub struct DebugOptimizer<O>(O);
+
+impl<O, K: VarKind> MutOptimizer<Program<K>> for DebugOptimizer<O>
+where
+    O: MutOptimizer<Program<K>>,
+{
+    fn optimize(&mut self, program: &mut Program<K>) -> Result<()> {
+        let enabled = log::log_enabled!(log::Level::Debug);
+        if enabled {
+            log::debug!("Before {}:\n{}", self.0.name(), render(program.to_repr()));
+        }
+        self.0.optimize(program)?;
+        if enabled {
+            log::debug!("After {}:\n{}", self.0.name(), render(program.to_repr()));
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        self.0.name()
+    }
+}
+
+/// Adds [`DebugOptimizer`] wrapping to every optimizer of programs.
+pub trait WithDebug<K: VarKind>: MutOptimizer<Program<K>> + Sized {
+    /// Wraps the optimizer in a [`DebugOptimizer`].
+    fn with_debug(self) -> DebugOptimizer<Self> {
+        DebugOptimizer(self)
+    }
+}
+
+impl<K: VarKind, T: MutOptimizer<Program<K>>> WithDebug<K> for T {}
+
+/// Statistics about the execution of an optimization pass.
+#[derive(Debug, Clone)]
+pub struct PassStats {
+    /// Name of the pass.
+    pub name: &'static str,
+    /// Time it took to run the pass.
+    pub duration: Duration,
+    /// Number of expressions that were different after running the pass, regardless of their
+    /// position in the program.
+    pub exprs_modified: usize,
 }
 
 #[derive(Debug)]
@@ -26,9 +85,20 @@ impl<K: VarKind + 'static> OptimizerPipelineBuilder<K> {
     pub fn new() -> Self {
         Self(OptimizerPipeline {
             passes: Default::default(),
+            max_iterations: None,
         })
     }
 
+    /// Runs the passes of the pipeline repeatedly until a full run over the pipeline does not
+    /// change the program or until `n` passes have been executed in total.
+    ///
+    /// By default the pipeline runs each pass only once.
+    pub fn with_max_iterations(self, n: u32) -> Self {
+        let mut b = self;
+        b.0.max_iterations = Some(n);
+        b
+    }
+
     pub fn add_pass_with_params<P: ProgramOptimizer<K> + 'static>(
         self,
         params: impl Into<P>,
@@ -106,6 +176,119 @@ where
 #[derive(Debug)]
 pub struct OptimizerPipeline<K: VarKind> {
     passes: Vec<Box<dyn MutOptimizer<Program<K>>>>,
+    /// Maximum number of pass executions. If set the pipeline runs until convergence.
+    max_iterations: Option<u32>,
+}
+
+impl<K: VarKind> OptimizerPipeline<K> {
+    /// Runs the pipeline and returns statistics about each pass execution, ordered by duration in
+    /// descending order.
+    pub fn run_with_stats(&mut self, program: &mut Program<K>) -> Result<Vec<PassStats>> {
+        let mut stats = self.run(program, true)?;
+        stats.sort_by(|lhs, rhs| rhs.duration.cmp(&lhs.duration));
+        Ok(stats)
+    }
+
+    /// Runs the passes of the pipeline. Statistics are only collected if `with_stats` is true,
+    /// otherwise the returned list is empty.
+    fn run(&mut self, program: &mut Program<K>, with_stats: bool) -> Result<Vec<PassStats>> {
+        match self.max_iterations {
+            Some(max) => self.run_until_convergence(program, max, with_stats),
+            None => self
+                .passes
+                .iter_mut()
+                .filter_map(|pass| {
+                    run_pass(pass.as_mut(), program, with_stats, false)
+                        .map(|(stats, _)| stats)
+                        .transpose()
+                })
+                .collect(),
+        }
+    }
+
+    /// Runs the passes until the program stops changing or the maximum number of pass executions
+    /// is reached.
+    fn run_until_convergence(
+        &mut self,
+        program: &mut Program<K>,
+        max: u32,
+        with_stats: bool,
+    ) -> Result<Vec<PassStats>> {
+        let mut stats = vec![];
+        let mut executions = 0;
+        for iteration in 0.. {
+            log::debug!("Starting optimization iteration {iteration}");
+            let mut changed = false;
+            for pass in self.passes.iter_mut() {
+                if executions >= max {
+                    log::debug!("Reached the maximum of {max} pass executions");
+                    return Ok(stats);
+                }
+                executions += 1;
+                let (pass_stats, pass_changed) =
+                    run_pass(pass.as_mut(), program, with_stats, true)?;
+                if pass_changed {
+                    log::debug!("Pass {} changed the program", pass.name());
+                    changed = true;
+                }
+                stats.extend(pass_stats);
+            }
+            if !changed {
+                log::debug!("Optimization converged after {} iterations", iteration + 1);
+                break;
+            }
+        }
+        Ok(stats)
+    }
+}
+
+/// Runs the pass and returns its statistics, if requested, and whether the program changed or
+/// not. Changes are only tracked if `track_changes` is true.
+fn run_pass<K: VarKind>(
+    pass: &mut dyn MutOptimizer<Program<K>>,
+    program: &mut Program<K>,
+    with_stats: bool,
+    track_changes: bool,
+) -> Result<(Option<PassStats>, bool)> {
+    let text_before = track_changes.then(|| program.display().to_string());
+    let exprs_before = with_stats.then(|| expr_fingerprints(program));
+    let start = Instant::now();
+    pass.optimize(program)?;
+    let duration = start.elapsed();
+    let stats = exprs_before.map(|exprs_before| PassStats {
+        name: pass.name(),
+        duration,
+        exprs_modified: exprs_modified(exprs_before, expr_fingerprints(program)),
+    });
+    let changed = text_before.is_some_and(|text| program.display().to_string() != text);
+    Ok((stats, changed))
+}
+
+/// Returns a textual representation of each expression in the program.
+fn expr_fingerprints<K: VarKind>(program: &Program<K>) -> Vec<String> {
+    program
+        .modules()
+        .iter()
+        .flat_map(|m| m.stmts())
+        .flat_map(|s| s.args())
+        .map(|e| format!("{e:?}"))
+        .collect()
+}
+
+/// Returns the number of expressions that changed between both lists regardless of their
+/// position. That is, the larger of the number of expressions only present before and the number
+/// of expressions only present after.
+fn exprs_modified(before: Vec<String>, after: Vec<String>) -> usize {
+    let mut counts = HashMap::<String, isize>::new();
+    for expr in before {
+        *counts.entry(expr).or_default() += 1;
+    }
+    for expr in after {
+        *counts.entry(expr).or_default() -= 1;
+    }
+    let removed = counts.values().filter(|n| **n > 0).sum::<isize>();
+    let added = -counts.values().filter(|n| **n < 0).sum::<isize>();
+    removed.max(added) as usize
 }
 
 impl<K: VarKind> From<OptimizerPipelineBuilder<K>> for OptimizerPipeline<K> {
@@ -116,7 +299,7 @@ impl<K: VarKind> From<OptimizerPipelineBuilder<K>> for OptimizerPipeline<K> {
 
 impl<K: VarKind> MutOptimizer<Program<K>> for OptimizerPipeline<K> {
     fn optimize(&mut self, program: &mut Program<K>) -> Result<()> {
-        self.passes.as_mut_slice().optimize(program)
+        self.run(program, false).map(|_| ())
     }
 }
 
@@ -250,3 +433,130 @@ impl<T> MutOptimizer<T> for &mut [Box<dyn MutOptimizer<T>>] {
 //    I: Iterator<Item = VarStr>,
 //{
 //}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::ParsedVar;
+
+    #[derive(Debug, Default)]
+    struct ReverseStmtsPass;
+
+    impl<K: VarKind> MutOptimizer<Module<K>> for ReverseStmtsPass {
+        fn optimize(&mut self, module: &mut Module<K>) -> Result<()> {
+            module.stmts_mut().reverse();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn reordering_does_not_modify_exprs() {
+        let mut program: Program<ParsedVar> = "(prime-number 7)
+(begin-module main)
+(input x)
+(output y)
+(assert (= y (* x x)))
+(assert (= x 1))
+(end-module)
+"
+        .parse()
+        .unwrap();
+        let mut pipeline: OptimizerPipeline<ParsedVar> = OptimizerPipelineBuilder::new()
+            .add_pass::<ReverseStmtsPass>()
+            .into();
+        let stats = pipeline.run_with_stats(&mut program).unwrap();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].exprs_modified, 0);
+    }
+
+    /// Reverses the statements of the modules the first time it runs.
+    #[derive(Debug, Default)]
+    struct ReverseStmtsOncePass {
+        done: bool,
+    }
+
+    impl<K: VarKind> MutOptimizer<Program<K>> for ReverseStmtsOncePass {
+        fn optimize(&mut self, program: &mut Program<K>) -> Result<()> {
+            if !std::mem::replace(&mut self.done, true) {
+                ReverseStmtsPass.optimize(program)?;
+            }
+            Ok(())
+        }
+    }
+
+    fn two_asserts() -> Program<ParsedVar> {
+        "(prime-number 7)
+(begin-module main)
+(input x)
+(output y)
+(assert (= y (* x x)))
+(assert (= x 1))
+(end-module)
+"
+        .parse()
+        .unwrap()
+    }
+
+    #[test]
+    fn iterations_stop_when_the_program_converges() {
+        let mut program = two_asserts();
+        let mut pipeline: OptimizerPipeline<ParsedVar> = OptimizerPipelineBuilder::new()
+            .add_pass::<ReverseStmtsOncePass>()
+            .with_max_iterations(10)
+            .into();
+        // The first execution changes the program and the second one does not.
+        let stats = pipeline.run_with_stats(&mut program).unwrap();
+        assert_eq!(stats.len(), 2);
+    }
+
+    #[test]
+    fn iterations_stop_at_the_maximum() {
+        let mut program = two_asserts();
+        // Reversing the statements changes the program on every execution.
+        let mut pipeline: OptimizerPipeline<ParsedVar> = OptimizerPipelineBuilder::new()
+            .add_pass::<ReverseStmtsPass>()
+            .with_max_iterations(5)
+            .into();
+        let stats = pipeline.run_with_stats(&mut program).unwrap();
+        assert_eq!(stats.len(), 5);
+
+        let mut pipeline: OptimizerPipeline<ParsedVar> = OptimizerPipelineBuilder::new()
+            .add_pass::<ReverseStmtsPass>()
+            .into();
+        let stats = pipeline.run_with_stats(&mut program).unwrap();
+        assert_eq!(stats.len(), 1);
+    }
+
+    #[test]
+    fn debug_optimizer_preserves_the_pass() {
+        let mut expected = two_asserts();
+        ReverseStmtsPass.optimize(&mut expected).unwrap();
+
+        let mut program = two_asserts();
+        let mut pass = WithDebug::<ParsedVar>::with_debug(ReverseStmtsOncePass::default());
+        assert_eq!(
+            MutOptimizer::<Program<ParsedVar>>::name(&pass),
+            MutOptimizer::<Program<ParsedVar>>::name(&ReverseStmtsOncePass::default())
+        );
+        pass.optimize(&mut program).unwrap();
+        assert_eq!(
+            program.display().to_string(),
+            expected.display().to_string()
+        );
+        // The state of the wrapped pass is kept between runs.
+        pass.optimize(&mut program).unwrap();
+        assert_eq!(
+            program.display().to_string(),
+            expected.display().to_string()
+        );
+    }
+
+    #[test]
+    fn exprs_modified_ignores_positions() {
+        let exprs = |e: &[&str]| e.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+        assert_eq!(exprs_modified(exprs(&["a", "b"]), exprs(&["b", "a"])), 0);
+        assert_eq!(exprs_modified(exprs(&["a", "b"]), exprs(&["a", "c"])), 1);
+        assert_eq!(exprs_modified(exprs(&["a", "b", "c"]), exprs(&["b"])), 2);
+        assert_eq!(exprs_modified(exprs(&["a"]), exprs(&["a", "a"])), 1);
+    }
+}
@@ -2,8 +2,10 @@ mod consolidate_var_names;
 mod ensure_max_size;
 mod fold;
 mod replace_consts;
+mod strength_reduction;
 
 pub use consolidate_var_names::ConsolidateVarNamesPass;
 pub use ensure_max_size::EnsureMaxExprSizePass;
 pub use fold::FoldExprsPass;
 pub use replace_consts::ReplaceKnownConstsPass;
+pub use strength_reduction::StrengthReductionPass;
@@ -0,0 +1,163 @@
+use anyhow::Result;
+
+use crate::{
+    expr::{
+        self,
+        traits::{ConstantFolding as _, ExprLike, MaybeVarLike as _},
+        Expr,
+    },
+    felt::Felt,
+    opt::{MutOptimizer, Optimizer},
+    vars::VarKind,
+    Program,
+};
+
+/// Rewrites multiplications by small constants into additions.
+///
+/// Multiplications by a power of two are rewritten into a chain of doublings (`x * 4` becomes
+/// `(x + x) + (x + x)`) and multiplications by other constants into repeated additions (`x * 3`
+/// becomes `x + x + x`). Multiplications by constants larger than `max_const` are left as-is.
+///
+/// Only multiplications of a variable or a constant are rewritten. Since every rewrite copies its
+/// operand `n` times, rewriting nested multiplications would make the printed expressions grow
+/// with the product of the constants.
+#[derive(Debug)]
+pub struct StrengthReductionPass {
+    max_const: usize,
+}
+
+impl StrengthReductionPass {
+    /// Largest constant that can be rewritten into additions.
+    pub const MAX_CONST: usize = 8;
+
+    /// Creates a new pass that rewrites multiplications by constants up to `max_const`.
+    ///
+    /// Thresholds larger than [`StrengthReductionPass::MAX_CONST`] are clamped to it.
+    pub fn new(max_const: usize) -> Self {
+        Self {
+            max_const: max_const.min(Self::MAX_CONST),
+        }
+    }
+}
+
+impl Default for StrengthReductionPass {
+    fn default() -> Self {
+        Self::new(4)
+    }
+}
+
+impl From<usize> for StrengthReductionPass {
+    fn from(max_const: usize) -> Self {
+        Self::new(max_const)
+    }
+}
+
+impl<K: VarKind> MutOptimizer<Program<K>> for StrengthReductionPass {
+    fn optimize(&mut self, t: &mut Program<K>) -> Result<()> {
+        let mut inner = StrengthReductionPassImpl(self.max_const);
+        let opt: &mut dyn MutOptimizer<Program<K>> = &mut inner;
+        opt.optimize(t)
+    }
+}
+
+#[derive(Debug)]
+struct StrengthReductionPassImpl(usize);
+
+impl StrengthReductionPassImpl {
+    /// If the expression is a constant between 2 and the threshold returns its value.
+    fn small_const(&self, e: &Expr) -> Option<usize> {
+        let c = e.as_const()?;
+        (2..=self.0).find(|n| Felt::from(*n) == c)
+    }
+
+    /// Returns true if the expression is a variable or a constant.
+    fn is_leaf(e: &Expr) -> bool {
+        e.var_name().is_some() || e.as_const().is_some()
+    }
+
+    fn reduce(&self, e: &Expr, n: usize) -> Expr {
+        if n.is_power_of_two() {
+            (0..n.trailing_zeros()).fold(e.clone(), |acc, _| expr::add(&acc, &acc))
+        } else {
+            (1..n).fold(e.clone(), |acc, _| expr::add(&acc, e))
+        }
+    }
+}
+
+impl Optimizer<dyn ExprLike, Expr> for StrengthReductionPassImpl {
+    fn optimize(&mut self, i: &dyn ExprLike) -> Result<Expr> {
+        let args = i
+            .args()
+            .iter()
+            .map(|arg| Optimizer::<dyn ExprLike, Expr>::optimize(self, arg.as_ref()).map(Some))
+            .collect::<Result<Vec<_>>>()?;
+        let e = i.replace_args(&args)?.unwrap_or_else(|| i.wrap());
+
+        Ok(match expr::as_mul(e.as_ref()) {
+            Some((lhs, rhs)) => match (self.small_const(&lhs), self.small_const(&rhs)) {
+                (Some(n), _) if Self::is_leaf(&rhs) => self.reduce(&rhs, n),
+                (_, Some(n)) if Self::is_leaf(&lhs) => self.reduce(&lhs, n),
+                _ => e,
+            },
+            None => e,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        opt::{OptimizerPipeline, OptimizerPipelineBuilder},
+        parse::ParsedVar,
+    };
+
+    fn reduce(max_const: usize, assertion: &str) -> String {
+        let mut program: Program<ParsedVar> = format!(
+            "(prime-number 17)\n(begin-module main)\n(input x)\n(output y)\n{assertion}\n(end-module)\n"
+        )
+        .parse()
+        .unwrap();
+        let mut pipeline: OptimizerPipeline<ParsedVar> = OptimizerPipelineBuilder::new()
+            .add_pass_with_params::<StrengthReductionPass>(max_const)
+            .into();
+        pipeline.optimize(&mut program).unwrap();
+        program.display().to_string()
+    }
+
+    fn unchanged(assertion: &str) -> String {
+        reduce(0, assertion)
+    }
+
+    #[test]
+    fn small_constants_become_additions() {
+        assert_eq!(
+            reduce(4, "(assert (= y (* x 3)))"),
+            unchanged("(assert (= y (+ (+ x x) x)))")
+        );
+        assert_eq!(
+            reduce(4, "(assert (= y (* 4 x)))"),
+            unchanged("(assert (= y (+ (+ x x) (+ x x))))")
+        );
+    }
+
+    #[test]
+    fn constants_above_the_threshold_are_kept() {
+        assert_eq!(
+            reduce(4, "(assert (= y (* x 5)))"),
+            unchanged("(assert (= y (* x 5)))")
+        );
+        assert_eq!(
+            reduce(100, "(assert (= y (* x 9)))"),
+            unchanged("(assert (= y (* x 9)))")
+        );
+    }
+
+    #[test]
+    fn nested_multiplications_are_not_expanded() {
+        assert_eq!(
+            reduce(4, "(assert (= y (* (* x 4) 4)))"),
+            unchanged("(assert (= y (* (+ (+ x x) (+ x x)) 4)))")
+        );
+    }
+}
@@ -0,0 +1,441 @@
+//! Parser for the text format emitted by [`Program::display`](crate::Program::display).
+
+use std::{collections::HashSet, str::FromStr as _};
+
+use anyhow::{Result, anyhow, bail};
+use nom::{
+    Finish as _, IResult,
+    branch::alt,
+    bytes::complete::{tag, take_while1},
+    character::complete::{char, digit1, multispace0, not_line_ending},
+    combinator::{all_consuming, map, map_res, opt, verify},
+    multi::{many_m_n, many0, many1},
+    sequence::{delimited, pair, preceded, terminated, tuple},
+};
+
+use crate::{
+    Module,
+    expr::{self, Expr},
+    felt::{Felt, FeltRepr},
+    stmt::{self, Stmt, traits::FreeVars as _},
+    vars::{VarKind, VarStr, Vars},
+};
+
+/// Key of the variables of a parsed program.
+///
+/// The text format only declares the inputs and outputs of the modules. Any other variable that
+/// appears in the statements of a module is a temporary.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub enum ParsedVar {
+    /// The n-th input of the module.
+    Input(usize, VarStr),
+    /// The n-th output of the module.
+    Output(usize, VarStr),
+    /// A variable that is neither an input nor an output.
+    Temp(VarStr),
+}
+
+impl ParsedVar {
+    /// Returns the name of the variable.
+    pub fn name(&self) -> &VarStr {
+        match self {
+            ParsedVar::Input(_, var) | ParsedVar::Output(_, var) | ParsedVar::Temp(var) => var,
+        }
+    }
+}
+
+impl From<ParsedVar> for VarStr {
+    fn from(value: ParsedVar) -> Self {
+        match value {
+            ParsedVar::Input(_, var) | ParsedVar::Output(_, var) | ParsedVar::Temp(var) => var,
+        }
+    }
+}
+
+impl VarKind for ParsedVar {
+    fn is_input(&self) -> bool {
+        matches!(self, ParsedVar::Input(..))
+    }
+
+    fn get_input_no(&self) -> Option<usize> {
+        match self {
+            ParsedVar::Input(no, _) => Some(*no),
+            _ => None,
+        }
+    }
+
+    fn is_output(&self) -> bool {
+        matches!(self, ParsedVar::Output(..))
+    }
+
+    fn get_output_no(&self) -> Option<usize> {
+        match self {
+            ParsedVar::Output(no, _) => Some(*no),
+            _ => None,
+        }
+    }
+
+    fn is_temp(&self) -> bool {
+        matches!(self, ParsedVar::Temp(_))
+    }
+}
+
+/// Parses the prime and the modules of a program.
+pub(crate) fn parse_program(s: &str) -> Result<(Felt, Vec<Module<ParsedVar>>)> {
+    let (_, program) =
+        all_consuming(terminated(pair(ws(prime), many0(ws(module))), multispace0))(s)
+            .finish()
+            .map_err(|e| {
+                let line = s[..s.len() - e.input.len()].matches('\n').count() + 1;
+                anyhow!(
+                    "Failed to parse Picus program at line {line} ({:?}): {:?}",
+                    e.code,
+                    e.input.lines().next().unwrap_or_default()
+                )
+            })?;
+    Ok(program)
+}
+
+/// Skips the whitespace before the parser.
+fn ws<'a, O, P>(p: P) -> impl FnMut(&'a str) -> IResult<&'a str, O>
+where
+    P: FnMut(&'a str) -> IResult<&'a str, O>,
+{
+    preceded(multispace0, p)
+}
+
+/// Parses a parenthesized form that starts with the given keyword.
+fn keyword<'a, O, P>(kw: &'static str, body: P) -> impl FnMut(&'a str) -> IResult<&'a str, O>
+where
+    P: FnMut(&'a str) -> IResult<&'a str, O>,
+{
+    delimited(pair(char('('), tag(kw)), body, ws(char(')')))
+}
+
+/// Parses a list of items between square brackets.
+fn list<'a, O, P>(item: P) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    P: FnMut(&'a str) -> IResult<&'a str, O>,
+{
+    delimited(char('['), many0(ws(item)), ws(char(']')))
+}
+
+fn ident(i: &str) -> IResult<&str, &str> {
+    take_while1(|c: char| c.is_ascii_alphanumeric() || c == '_')(i)
+}
+
+fn felt(i: &str) -> IResult<&str, Felt> {
+    map_res(digit1, |s: &str| FeltRepr::from_str(s).map(Felt::new))(i)
+}
+
+/// Parses the text of a comment, without the leading `; `.
+fn comment(i: &str) -> IResult<&str, &str> {
+    preceded(pair(char(';'), opt(char(' '))), not_line_ending)(i)
+}
+
+fn prime(i: &str) -> IResult<&str, Felt> {
+    keyword("prime-number", ws(felt))(i)
+}
+
+fn atom(i: &str) -> IResult<&str, Expr> {
+    map_res(ident, |s: &str| -> Result<Expr> {
+        if s.bytes().all(|b| b.is_ascii_digit()) {
+            return Ok(expr::r#const(Felt::new(FeltRepr::from_str(s)?)));
+        }
+        Ok(expr::known_var(&VarStr::try_from(s.to_owned())?))
+    })(i)
+}
+
+fn operation(i: &str) -> IResult<&str, Expr> {
+    map_res(
+        delimited(
+            char('('),
+            pair(
+                ws(take_while1(|c: char| {
+                    !c.is_whitespace() && c != '(' && c != ')'
+                })),
+                many1(ws(expression)),
+            ),
+            ws(char(')')),
+        ),
+        |(op, args): (&str, Vec<Expr>)| build_expr(op, &args),
+    )(i)
+}
+
+fn expression(i: &str) -> IResult<&str, Expr> {
+    alt((operation, atom))(i)
+}
+
+/// Creates the expression of the operator applied to the arguments.
+fn build_expr(op: &str, args: &[Expr]) -> Result<Expr> {
+    Ok(match (op, args) {
+        ("-", [e]) => expr::neg(e),
+        ("!", [e]) => expr::not(e),
+        ("det", [e]) => expr::det(e),
+        (op, [lhs, rhs]) => {
+            let f: fn(&Expr, &Expr) -> Expr = match op {
+                "+" => expr::add,
+                "-" => expr::sub,
+                "*" => expr::mul,
+                "/" => expr::div,
+                "<" => expr::lt,
+                "<=" => expr::le,
+                ">" => expr::gt,
+                ">=" => expr::ge,
+                "=" => expr::eq,
+                "!=" => expr::ne,
+                "&&" => expr::and,
+                "||" => expr::or,
+                "=>" => expr::implies,
+                "<=>" => expr::iff,
+                _ => bail!("Unknown binary operator {op:?}"),
+            };
+            f(lhs, rhs)
+        }
+        _ => bail!("Operator {op:?} does not take {} arguments", args.len()),
+    })
+}
+
+fn statement(i: &str) -> IResult<&str, Stmt> {
+    alt((
+        map(comment, |c| stmt::comment(c.to_owned())),
+        map(keyword("assert", ws(expression)), stmt::constrain),
+        map(
+            keyword("post-condition", ws(expression)),
+            stmt::post_condition,
+        ),
+        map_res(
+            keyword("assume-deterministic", ws(expression)),
+            stmt::assume_deterministic,
+        ),
+        map_res(
+            keyword(
+                "call",
+                tuple((ws(list(expression)), ws(ident), ws(list(expression)))),
+            ),
+            |(outputs, callee, inputs): (Vec<Expr>, &str, Vec<Expr>)| {
+                stmt::call(callee.to_owned(), inputs, outputs, None)
+            },
+        ),
+        map(
+            preceded(
+                tag("if"),
+                pair(
+                    ws(expression),
+                    delimited(ws(char('{')), ws(statement), ws(char('}'))),
+                ),
+            ),
+            |(guard, body): (Expr, Stmt)| stmt::guarded(guard, body),
+        ),
+    ))(i)
+}
+
+fn module(i: &str) -> IResult<&str, Module<ParsedVar>> {
+    map_res(
+        tuple((
+            keyword("begin-module", ws(ident)),
+            // Summary of the module.
+            many_m_n(
+                0,
+                4,
+                ws(verify(comment, |c: &str| c.starts_with("Number of "))),
+            ),
+            many0(ws(keyword("input", ws(ident)))),
+            many0(ws(keyword("output", ws(ident)))),
+            many0(ws(statement)),
+            ws(tag("(end-module)")),
+            // Name of the module.
+            opt(ws(comment)),
+        )),
+        |(name, _, inputs, outputs, stmts, _, _)| build_module(name, &inputs, &outputs, stmts),
+    )(i)
+}
+
+/// Creates a module with the given inputs and outputs. The rest of variables used by the
+/// statements are declared as temporaries.
+fn build_module(
+    name: &str,
+    inputs: &[&str],
+    outputs: &[&str],
+    stmts: Vec<Stmt>,
+) -> Result<Module<ParsedVar>> {
+    let var = |s: &str| VarStr::try_from(s.to_owned());
+    let mut keys = inputs
+        .iter()
+        .copied()
+        .enumerate()
+        .map(|(no, input)| Ok(ParsedVar::Input(no, var(input)?)))
+        .chain(
+            outputs
+                .iter()
+                .copied()
+                .enumerate()
+                .map(|(no, output)| Ok(ParsedVar::Output(no, var(output)?))),
+        )
+        .collect::<Result<Vec<_>>>()?;
+    let mut declared = HashSet::new();
+    for key in &keys {
+        if !declared.insert(key.name()) {
+            bail!("Variable {} is declared twice in module {name}", key.name());
+        }
+    }
+    let temps = stmts
+        .iter()
+        .flat_map(|s| s.free_vars())
+        .filter(|v| !declared.contains(v))
+        .cloned()
+        .collect::<HashSet<_>>();
+    keys.extend(temps.into_iter().map(ParsedVar::Temp));
+
+    Ok(Module {
+        name: name.to_owned().into(),
+        stmts,
+        vars: keys.into_iter().collect::<Vars<_>>(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use proptest::{collection::vec, prelude::*, sample::select};
+
+    use super::*;
+    use crate::Program;
+
+    const VARS: &[&str] = &["in_0", "in_1", "in_2", "out_0", "out_1", "t_0", "t_1"];
+
+    const UNARY_OPS: &[fn(&Expr) -> Expr] = &[expr::neg, expr::not, expr::det];
+
+    const BINARY_OPS: &[fn(&Expr, &Expr) -> Expr] = &[
+        expr::add,
+        expr::sub,
+        expr::mul,
+        expr::div,
+        expr::lt,
+        expr::le,
+        expr::gt,
+        expr::ge,
+        expr::eq,
+        expr::ne,
+        expr::and,
+        expr::or,
+        expr::implies,
+        expr::iff,
+    ];
+
+    fn var() -> impl Strategy<Value = Expr> {
+        select(VARS).prop_map(|v| expr::known_var(&VarStr::try_from(v.to_owned()).unwrap()))
+    }
+
+    fn arb_expr() -> impl Strategy<Value = Expr> {
+        prop_oneof![(0usize..1000).prop_map(expr::r#const::<usize>), var()].prop_recursive(
+            4,
+            32,
+            2,
+            |inner| {
+                prop_oneof![
+                    (select(UNARY_OPS), inner.clone()).prop_map(|(op, e)| op(&e)),
+                    (select(BINARY_OPS), inner.clone(), inner)
+                        .prop_map(|(op, lhs, rhs)| op(&lhs, &rhs)),
+                ]
+            },
+        )
+    }
+
+    fn arb_call() -> impl Strategy<Value = Stmt> {
+        (vec(var(), 0..3), 0..10usize, vec(arb_expr(), 0..3)).prop_map(
+            |(outputs, callee, inputs)| {
+                stmt::call(format!("m_{callee}"), inputs, outputs, None).unwrap()
+            },
+        )
+    }
+
+    fn arb_stmt() -> impl Strategy<Value = Stmt> {
+        prop_oneof![
+            arb_expr().prop_map(stmt::constrain),
+            arb_expr().prop_map(stmt::post_condition),
+            "[a-z ]{0,16}".prop_map(stmt::comment),
+            var().prop_map(|v| stmt::assume_deterministic(v).unwrap()),
+            arb_call(),
+            (
+                arb_expr(),
+                prop_oneof![arb_call(), arb_expr().prop_map(stmt::constrain)]
+            )
+                .prop_map(|(guard, body)| stmt::guarded(guard, body)),
+        ]
+    }
+
+    fn arb_module(idx: usize) -> impl Strategy<Value = Module<ParsedVar>> {
+        (0..=3usize, 0..=2usize, vec(arb_stmt(), 0..=50)).prop_map(move |(ins, outs, stmts)| {
+            build_module(&format!("m_{idx}"), &VARS[..ins], &VARS[3..3 + outs], stmts).unwrap()
+        })
+    }
+
+    fn arb_program() -> impl Strategy<Value = Program<ParsedVar>> {
+        (0..=10usize)
+            .prop_flat_map(|n| (2usize..10_000, (0..n).map(arb_module).collect::<Vec<_>>()))
+            .prop_map(|(prime, modules)| {
+                Program::new(
+                    prime,
+                    modules
+                        .into_iter()
+                        .map(|m| Rc::new(RefCell::new(m)))
+                        .collect(),
+                )
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(program in arb_program()) {
+            let text = program.display().to_string();
+            let parsed: Program<ParsedVar> = text.parse().unwrap();
+            prop_assert_eq!(parsed.prime(), program.prime());
+            prop_assert_eq!(parsed.modules().len(), program.modules().len());
+            for (parsed, module) in std::iter::zip(parsed.modules(), program.modules()) {
+                prop_assert_eq!(parsed.name(), module.name());
+                prop_assert!(parsed.is_duplicate_of(module));
+            }
+            prop_assert_eq!(parsed.display().to_string(), text);
+        }
+    }
+
+    #[test]
+    fn parse_module() {
+        let text = "(prime-number 7)
+(begin-module main)
+; Number of inputs:      1
+; Number of outputs:     1
+; Number of temporaries: 1
+; Number of constraints: 1
+(input x)
+(output y)
+(assert (= y (+ x t)))
+(call [y] f [(- x)])
+(end-module) ; main
+";
+        let program: Program<ParsedVar> = text.parse().unwrap();
+        assert_eq!(*program.prime(), Felt::from(7usize));
+        let main = &program.modules()[0];
+        assert_eq!(main.name(), "main");
+        assert_eq!(main.vars().inputs().collect::<Vec<_>>(), ["x"]);
+        assert_eq!(main.vars().outputs().collect::<Vec<_>>(), ["y"]);
+        assert_eq!(main.vars().temporaries().collect::<Vec<_>>(), ["t"]);
+        assert_eq!(main.stmts().len(), 2);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(
+            "(prime-number 7)\n(begin-module main)\n"
+                .parse::<Program<ParsedVar>>()
+                .is_err()
+        );
+        assert!(
+            "(prime-number 7)\n(begin-module m)\n(input x)\n(input x)\n(end-module)"
+                .parse::<Program<ParsedVar>>()
+                .is_err()
+        );
+    }
+}
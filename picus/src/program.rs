@@ -1,13 +1,17 @@
 use std::{
     collections::{HashMap, HashSet},
     ops::Add,
+    str::FromStr,
+    sync::OnceLock,
 };
 
 use crate::{
     Module, ModuleRef,
     display::{Display, TextRepresentable, TextRepresentation},
+    expr::{Expr, traits::ExprSize as _},
     felt::Felt,
-    stmt::traits::{CallLike as _, MaybeCallLike as _},
+    parse::{ParsedVar, parse_program},
+    stmt::traits::{CallLike as _, ExprArgs as _, MaybeCallLike as _},
     vars::VarKind,
 };
 use anyhow::{Result, anyhow};
@@ -16,6 +20,7 @@ use anyhow::{Result, anyhow};
 pub struct Program<K: VarKind> {
     prime: PrimeNumber,
     modules: Vec<Module<K>>,
+    stats: OnceLock<ProgramStats>,
 }
 
 impl<K: VarKind> Program<K> {
@@ -24,9 +29,18 @@ impl<K: VarKind> Program<K> {
     }
 
     pub fn modules_mut(&mut self) -> &mut [Module<K>] {
+        self.stats.take();
         &mut self.modules
     }
 
+    /// Returns statistics about the program.
+    ///
+    /// The statistics are computed on the first call and cached until the program is modified.
+    pub fn stats(&self) -> &ProgramStats {
+        self.stats
+            .get_or_init(|| ProgramStats::compute(&self.modules))
+    }
+
     fn module_names(&self) -> HashSet<&str> {
         self.modules.iter().map(|m| m.name.as_str()).collect()
     }
@@ -40,6 +54,7 @@ impl<K: VarKind> Program<K> {
     }
 
     pub fn merge(&mut self, other: Program<K>) -> Result<()> {
+        self.check_same_prime(&other)?;
         let collisions: HashSet<String> = self
             .module_names()
             .intersection(&other.module_names())
@@ -74,26 +89,93 @@ impl<K: VarKind> Program<K> {
             // renames
             .collect::<Result<Vec<_>>>()?;
         let renames = renames;
+        self.stats.take();
         self.modules.extend(renamed.into_iter().map(|m| {
             let mut m = m;
-
-            m.stmts = m
-                .stmts
-                .into_iter()
-                .map(|s| match s.as_call() {
-                    Some(call) if renames.contains_key(call.callee()) => {
-                        call.with_new_callee(renames[call.callee()].clone())
-                    }
-                    _ => s,
-                })
-                .collect();
-
+            m.rename_callees(&renames);
             m
         }));
 
         Ok(())
     }
 
+    /// Combines the modules of both programs into a new program.
+    ///
+    /// Unlike [`Program::merge`], the modules are not renamed and the merge fails if both
+    /// programs have a module with the same name. The modules of `other` are appended in
+    /// dependency order, callees before their callers. Both programs must use the same prime.
+    pub fn merge_disjoint(self, other: Program<K>) -> Result<Program<K>> {
+        self.check_same_prime(&other)?;
+        if let Some(name) = self
+            .module_names()
+            .intersection(&other.module_names())
+            .min()
+        {
+            anyhow::bail!("Cannot merge programs: module '{name}' is defined in both programs");
+        }
+        let mut merged = self;
+        merged.stats.take();
+        merged.modules.extend(sort_by_dependencies(other.modules)?);
+        Ok(merged)
+    }
+
+    fn check_same_prime(&self, other: &Program<K>) -> Result<()> {
+        if self.prime() != other.prime() {
+            anyhow::bail!(
+                "Cannot merge programs with different primes ({} and {})",
+                self.prime(),
+                other.prime()
+            );
+        }
+        Ok(())
+    }
+
+    /// Removes modules that are duplicates of a previous module in the program and redirects
+    /// the calls to the removed modules to the module that was kept.
+    ///
+    /// Two modules are duplicates if they have the same statements modulo a renaming of their
+    /// variables (see [`Module::is_duplicate_of`]). The module named `entrypoint` is never removed
+    /// nor used as a replacement. Since removing modules can make their callers become duplicates
+    /// this process is repeated until no more modules are removed. Returns the number of modules
+    /// that were removed.
+    pub fn deduplicate_modules(&mut self, entrypoint: &str) -> usize {
+        let mut removed = 0;
+        loop {
+            let mut renames: HashMap<String, String> = Default::default();
+            let mut kept: Vec<Module<K>> = Vec::with_capacity(self.modules.len());
+            for module in std::mem::take(&mut self.modules) {
+                if module.name() == entrypoint {
+                    kept.push(module);
+                    continue;
+                }
+                match kept
+                    .iter()
+                    .find(|k| k.name() != entrypoint && k.is_duplicate_of(&module))
+                {
+                    Some(canonical) => {
+                        log::debug!(
+                            "Module {} is a duplicate of module {}",
+                            module.name(),
+                            canonical.name()
+                        );
+                        renames.insert(module.name().to_owned(), canonical.name().to_owned());
+                    }
+                    None => kept.push(module),
+                }
+            }
+            self.modules = kept;
+            if renames.is_empty() {
+                break;
+            }
+            removed += renames.len();
+            for module in &mut self.modules {
+                module.rename_callees(&renames);
+            }
+        }
+        self.stats.take();
+        removed
+    }
+
     pub fn new(prime: impl Into<Felt>, modules: Vec<ModuleRef<K>>) -> Self
     where
         K: VarKind + Clone,
@@ -101,6 +183,7 @@ impl<K: VarKind> Program<K> {
         Self {
             prime: PrimeNumber(prime.into()),
             modules: modules.into_iter().map(Into::into).collect(),
+            stats: OnceLock::new(),
         }
     }
 }
@@ -115,6 +198,171 @@ impl<K: VarKind> Add for Program<K> {
     }
 }
 
+impl FromStr for Program<ParsedVar> {
+    type Err = anyhow::Error;
+
+    /// Parses a program from the text emitted by [`Program::display`].
+    fn from_str(s: &str) -> Result<Self> {
+        let (prime, modules) = parse_program(s)?;
+        Ok(Self {
+            prime: PrimeNumber(prime),
+            modules,
+            stats: OnceLock::new(),
+        })
+    }
+}
+
+/// Sorts the modules such that the modules called by a module come before it.
+///
+/// Calls to modules that are not in the list are ignored. Fails if the calls between the modules
+/// have cycles.
+fn sort_by_dependencies<K: VarKind>(modules: Vec<Module<K>>) -> Result<Vec<Module<K>>> {
+    let names: HashMap<String, usize> = modules
+        .iter()
+        .enumerate()
+        .map(|(idx, m)| (m.name().to_owned(), idx))
+        .collect();
+    let callees: Vec<Vec<usize>> = modules
+        .iter()
+        .map(|m| {
+            m.stmts()
+                .iter()
+                .filter_map(|s| s.as_call().and_then(|c| names.get(c.callee()).copied()))
+                .collect()
+        })
+        .collect();
+
+    #[derive(Clone, Copy, PartialEq)]
+    enum Mark {
+        Unvisited,
+        Visiting,
+        Done,
+    }
+
+    fn visit(
+        idx: usize,
+        callees: &[Vec<usize>],
+        marks: &mut [Mark],
+        order: &mut Vec<usize>,
+    ) -> Result<()> {
+        match marks[idx] {
+            Mark::Done => return Ok(()),
+            Mark::Visiting => anyhow::bail!("Detected a cycle in the calls between modules"),
+            Mark::Unvisited => {}
+        }
+        marks[idx] = Mark::Visiting;
+        for callee in &callees[idx] {
+            visit(*callee, callees, marks, order)?;
+        }
+        marks[idx] = Mark::Done;
+        order.push(idx);
+        Ok(())
+    }
+
+    let mut marks = vec![Mark::Unvisited; modules.len()];
+    let mut order = Vec::with_capacity(modules.len());
+    for idx in 0..modules.len() {
+        visit(idx, &callees, &mut marks, &mut order)?;
+    }
+
+    let mut modules: Vec<Option<Module<K>>> = modules.into_iter().map(Some).collect();
+    Ok(order
+        .into_iter()
+        .map(|idx| modules[idx].take().unwrap())
+        .collect())
+}
+
+/// Statistics about the contents of a [`Program`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProgramStats {
+    /// Number of modules in the program.
+    pub module_count: usize,
+    /// Number of variables declared across all modules.
+    pub total_var_count: usize,
+    /// Number of statements across all modules.
+    pub total_stmt_count: usize,
+    /// Number of expression nodes across all statements.
+    pub total_expr_nodes: usize,
+    /// Depth of the deepest expression in the program.
+    pub max_expr_depth: usize,
+}
+
+impl ProgramStats {
+    fn compute<K: VarKind>(modules: &[Module<K>]) -> Self {
+        let exprs = || {
+            modules
+                .iter()
+                .flat_map(|m| m.stmts())
+                .flat_map(|s| s.args())
+        };
+        Self {
+            module_count: modules.len(),
+            total_var_count: modules.iter().map(|m| m.vars().keys().count()).sum(),
+            total_stmt_count: modules.iter().map(|m| m.stmts().len()).sum(),
+            total_expr_nodes: exprs().map(|e| e.size()).sum(),
+            max_expr_depth: exprs().map(|e| expr_depth(&e)).max().unwrap_or_default(),
+        }
+    }
+}
+
+fn expr_depth(expr: &Expr) -> usize {
+    1 + expr.args().iter().map(expr_depth).max().unwrap_or_default()
+}
+
+/// Difference between the statistics of a program before and after a transformation.
+///
+/// Each field is the value after the transformation minus the value before it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OptimizationDelta {
+    /// Change in the number of modules.
+    pub module_count: isize,
+    /// Change in the number of variables.
+    pub total_var_count: isize,
+    /// Change in the number of statements.
+    pub total_stmt_count: isize,
+    /// Change in the number of expression nodes.
+    pub total_expr_nodes: isize,
+    /// Change in the depth of the deepest expression.
+    pub max_expr_depth: isize,
+}
+
+impl OptimizationDelta {
+    /// Returns the ratio between the number of expression nodes after and before the
+    /// transformation.
+    pub fn expr_nodes_ratio(before: &ProgramStats, after: &ProgramStats) -> f64 {
+        if before.total_expr_nodes == 0 {
+            return 1.0;
+        }
+        after.total_expr_nodes as f64 / before.total_expr_nodes as f64
+    }
+}
+
+impl std::fmt::Display for OptimizationDelta {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "modules: {:+}, vars: {:+}, stmts: {:+}, expr nodes: {:+}, max expr depth: {:+}",
+            self.module_count,
+            self.total_var_count,
+            self.total_stmt_count,
+            self.total_expr_nodes,
+            self.max_expr_depth
+        )
+    }
+}
+
+/// Computes the difference between two sets of statistics.
+pub fn optimization_delta(before: &ProgramStats, after: &ProgramStats) -> OptimizationDelta {
+    let diff = |b: usize, a: usize| a as isize - b as isize;
+    OptimizationDelta {
+        module_count: diff(before.module_count, after.module_count),
+        total_var_count: diff(before.total_var_count, after.total_var_count),
+        total_stmt_count: diff(before.total_stmt_count, after.total_stmt_count),
+        total_expr_nodes: diff(before.total_expr_nodes, after.total_expr_nodes),
+        max_expr_depth: diff(before.max_expr_depth, after.max_expr_depth),
+    }
+}
+
 //impl<F: IntoPrime, K: VarKind + Clone> From<Vec<ModuleRef<K>>> for Program<F, K> {
 //    fn from(modules: Vec<ModuleRef<K>>) -> Self {
 //        Self {
@@ -159,3 +407,60 @@ impl<K: VarKind> TextRepresentable for Program<K> {
         self.prime.width_hint()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::ParsedVar;
+
+    fn program(text: &str) -> Program<ParsedVar> {
+        text.parse().unwrap()
+    }
+
+    fn names(program: &Program<ParsedVar>) -> Vec<&str> {
+        program.modules().iter().map(Module::name).collect()
+    }
+
+    #[test]
+    fn merge_disjoint_appends_callees_first() {
+        let lhs = program(
+            "(prime-number 7)
+(begin-module a)
+(input x)
+(output y)
+(end-module)
+",
+        );
+        let rhs = program(
+            "(prime-number 7)
+(begin-module main)
+(input a)
+(output b)
+(call [b] f [a])
+(end-module)
+(begin-module f)
+(input x)
+(output y)
+(assert (= y (* x x)))
+(end-module)
+",
+        );
+        let merged = lhs.merge_disjoint(rhs).unwrap();
+        assert_eq!(names(&merged), ["a", "f", "main"]);
+    }
+
+    #[test]
+    fn merge_disjoint_rejects_collisions_and_different_primes() {
+        let module = "(begin-module main)
+(input x)
+(output y)
+(end-module)
+";
+        let seven = || program(&format!("(prime-number 7)\n{module}"));
+        let err = seven().merge_disjoint(seven()).unwrap_err();
+        assert!(err.to_string().contains("module 'main'"));
+
+        let eleven = program(&format!("(prime-number 11)\n{module}"));
+        assert!(seven().merge_disjoint(eleven).is_err());
+    }
+}
@@ -5,7 +5,7 @@ use anyhow::{Result, anyhow, bail};
 use crate::{
     display::{ListPunctuation, TextRepresentable, TextRepresentation},
     expr::{
-        Expr, known_var,
+        self, Expr, known_var,
         traits::{ConstantFolding, ConstraintExpr, MaybeVarLike},
     },
     felt::Felt,
@@ -177,6 +177,10 @@ impl CallLike for CallStmt {
     fn outputs(&self) -> &[VarStr] {
         &self.outputs.0
     }
+
+    fn inputs(&self) -> &[Expr] {
+        &self.inputs.0
+    }
 }
 
 impl MaybeCallLike for CallStmt {
@@ -502,3 +506,173 @@ impl FreeVars for PostConditionStmt {
 }
 
 impl StmtLike for PostConditionStmt {}
+
+//===----------------------------------------------------------------------===//
+// ConditionalConstraintStmt
+//===----------------------------------------------------------------------===//
+
+/// A statement that only applies when its guard is not zero.
+///
+/// If the body is a constraint, the statement is a constraint too and is represented by the
+/// implication `(guard != 0) => body`.
+#[derive(Debug, PartialEq)]
+pub struct ConditionalConstraintStmt {
+    guard: Expr,
+    body: Stmt,
+    implication: Option<Expr>,
+}
+
+impl ConditionalConstraintStmt {
+    pub fn new(guard: Expr, body: Stmt) -> Self {
+        let implication = Self::make_implication(&guard, &body);
+        Self {
+            guard,
+            body,
+            implication,
+        }
+    }
+
+    fn make_implication(guard: &Expr, body: &Stmt) -> Option<Expr> {
+        if !body.is_constraint() {
+            return None;
+        }
+        let body = body.borrow();
+        let term = match body.as_any().downcast_ref::<Self>() {
+            Some(inner) => inner.implication.clone()?,
+            None => body.args().into_iter().next()?,
+        };
+        Some(expr::implies(
+            &expr::ne(guard, &expr::r#const(0usize)),
+            &term,
+        ))
+    }
+
+    pub fn guard(&self) -> &Expr {
+        &self.guard
+    }
+
+    pub fn body(&self) -> &Stmt {
+        &self.body
+    }
+
+    /// Returns the implication that represents the statement if its body is a constraint.
+    pub fn implication(&self) -> Option<&Expr> {
+        self.implication.as_ref()
+    }
+
+    /// Returns true if the guard is the constant zero, meaning that the body never applies.
+    pub fn is_vacuous(&self) -> bool {
+        self.guard.is_zero()
+    }
+}
+
+impl ExprArgs for ConditionalConstraintStmt {
+    fn args(&self) -> Vec<Expr> {
+        std::iter::once(self.guard.clone())
+            .chain(self.body.args())
+            .collect()
+    }
+
+    fn replace_arg(&mut self, idx: usize, expr: Expr) -> Result<()> {
+        if idx == 0 {
+            self.guard = expr;
+        } else {
+            self.body.replace_arg(idx - 1, expr)?;
+        }
+        self.implication = Self::make_implication(&self.guard, &self.body);
+        Ok(())
+    }
+}
+
+impl ConstraintLike for ConditionalConstraintStmt {
+    fn is_constraint(&self) -> bool {
+        self.body.is_constraint()
+    }
+
+    fn constraint_expr(&self) -> Option<&dyn ConstraintExpr> {
+        self.implication.as_ref().and_then(|e| e.constraint_expr())
+    }
+}
+
+impl CallLike for ConditionalConstraintStmt {
+    fn callee(&self) -> &str {
+        self.body
+            .as_call()
+            .expect("body must be a call")
+            .into_inner()
+            .callee()
+    }
+
+    fn with_new_callee(&self, callee: String) -> Stmt {
+        Wrap::new(
+            Self::new(
+                self.guard.clone(),
+                self.body
+                    .as_call()
+                    .expect("body must be a call")
+                    .with_new_callee(callee),
+            )
+            .into(),
+        )
+    }
+
+    fn outputs(&self) -> &[VarStr] {
+        self.body
+            .as_call()
+            .expect("body must be a call")
+            .into_inner()
+            .outputs()
+    }
+
+    fn inputs(&self) -> &[Expr] {
+        self.body
+            .as_call()
+            .expect("body must be a call")
+            .into_inner()
+            .inputs()
+    }
+}
+
+impl MaybeCallLike for ConditionalConstraintStmt {
+    fn as_call<'a>(&'a self) -> Option<CallLikeAdaptor<'a>> {
+        self.body.as_call().map(|_| CallLikeAdaptor::new(self))
+    }
+}
+
+impl StmtConstantFolding for ConditionalConstraintStmt {
+    fn fold(&self, prime: &Felt) -> Option<Stmt> {
+        let guard = self.guard.fold(prime).unwrap_or(self.guard.clone());
+        let body = self.body.fold(prime).unwrap_or(self.body.clone());
+        if guard.as_const().is_some_and(|g| !g.is_zero()) {
+            return Some(body);
+        }
+        Some(Wrap::new(Self::new(guard, body).into()))
+    }
+}
+
+impl TextRepresentable for ConditionalConstraintStmt {
+    fn to_repr(&self) -> TextRepresentation<'_> {
+        owned_list!(
+            "if",
+            &self.guard,
+            TextRepresentation::owned_list(&[(&self.body).into()])
+                .with_punct(ListPunctuation::Brackets)
+        )
+        .with_punct(ListPunctuation::None)
+        .break_line()
+    }
+
+    fn width_hint(&self) -> usize {
+        8 + self.guard.width_hint() + self.body.width_hint()
+    }
+}
+
+impl FreeVars for ConditionalConstraintStmt {
+    fn free_vars(&self) -> HashSet<&VarStr> {
+        let mut fv = self.guard.free_vars();
+        fv.extend(self.body.free_vars());
+        fv
+    }
+}
+
+impl StmtLike for ConditionalConstraintStmt {}
@@ -3,15 +3,18 @@ use crate::expr::traits::ConstraintExpr;
 use crate::felt::Felt;
 use crate::vars::VarStr;
 use anyhow::{Result, anyhow};
-use impls::{AssumeDeterministicStmt, CallStmt, CommentLine, ConstraintStmt, PostConditionStmt};
-use std::collections::HashSet;
+use impls::{
+    AssumeDeterministicStmt, CallStmt, CommentLine, ConditionalConstraintStmt, ConstraintStmt,
+    PostConditionStmt,
+};
+use std::collections::{HashMap, HashSet};
 use std::{cell::RefCell, rc::Rc};
 use traits::{
     CallLikeAdaptor, ConstraintLike, ExprArgs, FreeVars, MaybeCallLike, StmtConstantFolding,
     StmtLike,
 };
 
-use crate::expr::Expr;
+use crate::expr::{self, Expr};
 
 mod impls;
 pub mod traits;
@@ -74,8 +77,14 @@ impl<T> StmtLike for Wrap<T> where T: StmtLike + PartialEq + ?Sized {}
 // Factories
 //===----------------------------------------------------------------------===//
 
-pub fn call(callee: String, inputs: Vec<Expr>, outputs: Vec<Expr>) -> Result<Stmt> {
-    Ok(Wrap::new(
+/// Creates a call statement. If a guard is given the call only applies when the guard is not zero.
+pub fn call(
+    callee: String,
+    inputs: Vec<Expr>,
+    outputs: Vec<Expr>,
+    guard: Option<Expr>,
+) -> Result<Stmt> {
+    let stmt: Stmt = Wrap::new(
         CallStmt::new(
             callee,
             inputs,
@@ -89,7 +98,11 @@ pub fn call(callee: String, inputs: Vec<Expr>, outputs: Vec<Expr>) -> Result<Stm
                 .collect::<Result<Vec<_>>>()?,
         )
         .into(),
-    ))
+    );
+    Ok(match guard {
+        Some(guard) => guarded(guard, stmt),
+        None => stmt,
+    })
 }
 
 pub fn assume_deterministic(expr: Expr) -> Result<Stmt> {
@@ -114,3 +127,52 @@ pub fn comment(s: String) -> Stmt {
 pub fn post_condition(expr: Expr) -> Stmt {
     Wrap::new(PostConditionStmt::new(expr).into())
 }
+
+/// Creates a statement that only applies when the guard is not zero.
+pub fn guarded(guard: Expr, body: Stmt) -> Stmt {
+    Wrap::new(ConditionalConstraintStmt::new(guard, body).into())
+}
+
+/// Returns true if both statements are equal after renaming the variables in `a` according to
+/// `rename`. Variables not present in the map must have the same name in both statements.
+pub fn structurally_eq(a: &Stmt, b: &Stmt, rename: &HashMap<VarStr, VarStr>) -> bool {
+    let (a_ref, b_ref) = (a.borrow(), b.borrow());
+    if a_ref.as_any().type_id() != b_ref.as_any().type_id() {
+        return false;
+    }
+    if let (Some(a_cond), Some(b_cond)) = (
+        a_ref.as_any().downcast_ref::<ConditionalConstraintStmt>(),
+        b_ref.as_any().downcast_ref::<ConditionalConstraintStmt>(),
+    ) {
+        return expr::structurally_eq(a_cond.guard(), b_cond.guard(), rename)
+            && structurally_eq(a_cond.body(), b_cond.body(), rename);
+    }
+    let (a_args, b_args) = (a_ref.args(), b_ref.args());
+    if a_args.is_empty() && b_args.is_empty() {
+        return *a_ref == *b_ref;
+    }
+    a_ref.as_call().map(|c| c.callee().to_owned()) == b_ref.as_call().map(|c| c.callee().to_owned())
+        && a_args.len() == b_args.len()
+        && std::iter::zip(&a_args, &b_args).all(|(a, b)| expr::structurally_eq(a, b, rename))
+}
+
+/// Returns the expressions that a constraint statement asserts. Guarded constraints are
+/// represented by their implication.
+pub(crate) fn constraint_terms(stmt: &Stmt) -> Vec<Expr> {
+    match stmt
+        .borrow()
+        .as_any()
+        .downcast_ref::<ConditionalConstraintStmt>()
+    {
+        Some(cond) => cond.implication().into_iter().cloned().collect(),
+        None => stmt.args(),
+    }
+}
+
+/// Returns true if the statement is guarded by a constant zero and thus never applies.
+pub(crate) fn is_vacuous(stmt: &Stmt) -> bool {
+    stmt.borrow()
+        .as_any()
+        .downcast_ref::<ConditionalConstraintStmt>()
+        .is_some_and(ConditionalConstraintStmt::is_vacuous)
+}
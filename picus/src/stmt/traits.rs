@@ -31,6 +31,8 @@ pub trait CallLike : std::fmt::Debug {
     fn with_new_callee(&self, new_name: String) -> Stmt;
 
     fn outputs(&self) -> &[VarStr];
+
+    fn inputs(&self) -> &[Expr];
 }
 
 pub trait StmtConstantFolding {
@@ -48,6 +50,11 @@ impl<'a> CallLikeAdaptor<'a> {
     pub fn new(c: &'a dyn CallLike) -> Self {
         Self(c)
     }
+
+    /// Returns the adapted call.
+    pub fn into_inner(self) -> &'a dyn CallLike {
+        self.0
+    }
 }
 
 impl CallLike for CallLikeAdaptor<'_> {
@@ -62,6 +69,10 @@ impl CallLike for CallLikeAdaptor<'_> {
     fn outputs(&self) -> &[VarStr] {
         self.0.outputs()
     }
+
+    fn inputs(&self) -> &[Expr] {
+        self.0.inputs()
+    }
 }
 
 pub trait MaybeCallLike {